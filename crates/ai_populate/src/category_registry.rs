@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /* ========= Catégories symboliques (ids usize modifiables) ========= */
 // Tu peux renommer/ajouter/supprimer des catégories quand tu veux.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryRegistry {
     // id  -> nom lisible
     names: Vec<String>,
@@ -28,4 +30,4 @@ impl CategoryRegistry {
     pub fn name(&self, id: usize) -> &str {
         self.names.get(id).map(|s| s.as_str()).unwrap_or("?")
     }
-}
\ No newline at end of file
+}
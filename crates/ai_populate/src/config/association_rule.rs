@@ -0,0 +1,29 @@
+//! Règle d'association automatique des caractères (groupes de formes proches)
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AssociationRule {
+    pub enabled: bool,
+    /// Ignorer la casse ('a' ~ 'A')
+    pub fold_case: bool,
+    /// Ignorer les accents ('e' ~ 'é'), après décomposition NFKD
+    pub strip_accents: bool,
+    /// Distance de Levenshtein maximale entre formes d'un même groupe
+    pub max_distance: usize,
+    /// Type inscrit dans `Encoded::associations`
+    pub kind: String,
+}
+
+impl Default for AssociationRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fold_case: true,
+            strip_accents: true,
+            max_distance: 1,
+            kind: "auto+sim".into(),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Construction de l'orchestrateur à partir du fichier de pipeline, avec
+//! des erreurs localisées (`modules[1].fallbacks[0] : ...`)
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use strsim::levenshtein;
+
+use crate::config::{
+    ConfigError, ConfigIssue, ConfigResult, ModuleConfig, OrderedModule, PipelineConfig,
+    TextNlpParams,
+};
+use crate::headerless_image_module::HeaderlessImageModule;
+use crate::image_module::ImageModule;
+use crate::orchestrator::{Module, Orchestrator};
+use crate::plugin::PluginHost;
+use crate::TextNlpModule;
+
+/// Modules intégrés, déclarables par leur nom
+const BUILTINS: [&str; 3] = ["TextNlpModule", "ImageModule", "HeaderlessImageModule"];
+
+fn unknown_module(at: String, name: &str) -> ConfigIssue {
+    let closest = BUILTINS
+        .iter()
+        .map(|b| (levenshtein(&b.to_lowercase(), &name.to_lowercase()), b))
+        .min();
+    let message = match closest {
+        Some((d, b)) if d <= 3 => format!("module inconnu '{name}' (vouliez-vous dire '{b}' ?)"),
+        _ => format!("module inconnu '{name}' (connus : {})", BUILTINS.join(", ")),
+    };
+    ConfigIssue::new(at, message)
+}
+
+/// Lit la section `params` d'un module (valeurs par défaut si absente)
+pub(crate) fn parse_params<T: DeserializeOwned + Default>(
+    m: &ModuleConfig,
+    at: &str,
+) -> Result<T, ConfigIssue> {
+    match &m.params {
+        None => Ok(T::default()),
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| ConfigIssue::new(format!("{at}.params"), e.to_string())),
+    }
+}
+
+fn check_text_params(params: &TextNlpParams, at: &str) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    for (k, table) in params.overrides.iter().enumerate() {
+        let at = format!("{at}.params.overrides[{k}]");
+        if table.category.trim().is_empty() {
+            issues.push(ConfigIssue::new(format!("{at}.category"), "catégorie vide"));
+        }
+        if table.chars.is_empty() {
+            issues.push(ConfigIssue::new(format!("{at}.chars"), "aucun caractère"));
+        }
+    }
+    if params.associations.kind.trim().is_empty() {
+        issues.push(ConfigIssue::new(
+            format!("{at}.params.associations.kind"),
+            "type d'association vide",
+        ));
+    }
+    issues
+}
+
+/// Instancie un module intégré à partir de sa déclaration
+fn instantiate(
+    m: &ModuleConfig,
+    name: &str,
+    at: &str,
+) -> Result<Box<dyn Module>, Vec<ConfigIssue>> {
+    let no_params = || match &m.params {
+        Some(v) if !v.as_object().is_some_and(|o| o.is_empty()) => Err(vec![ConfigIssue::new(
+            format!("{at}.params"),
+            format!("{name} n'a pas de paramètres"),
+        )]),
+        _ => Ok(()),
+    };
+    match name {
+        "TextNlpModule" => {
+            let params: TextNlpParams = parse_params(m, at).map_err(|issue| vec![issue])?;
+            let issues = check_text_params(&params, at);
+            if !issues.is_empty() {
+                return Err(issues);
+            }
+            Ok(Box::new(TextNlpModule::new(params)))
+        }
+        "ImageModule" => no_params().map(|_| Box::new(ImageModule) as Box<dyn Module>),
+        "HeaderlessImageModule" => {
+            no_params().map(|_| Box::new(HeaderlessImageModule) as Box<dyn Module>)
+        }
+        _ => Err(vec![unknown_module(format!("{at}.module"), name)]),
+    }
+}
+
+impl PipelineConfig {
+    /// Construit l'orchestrateur : modules intégrés, fallbacks, plugins,
+    /// puis validation du graphe. Les problèmes du fichier sont tous
+    /// rapportés ensemble.
+    pub fn build(&self, plugins: &mut PluginHost) -> ConfigResult<Orchestrator> {
+        let mut issues = Vec::new();
+        if self.modules.is_empty() && self.plugins.is_empty() {
+            issues.push(ConfigIssue::new("modules", "aucun module déclaré"));
+        }
+        let mut orchestrator = Orchestrator::new();
+        let mut declared: HashMap<&str, usize> = HashMap::new();
+        for (i, m) in self.modules.iter().enumerate() {
+            let at = format!("modules[{i}]");
+            if let Some(j) = declared.insert(&m.module, i) {
+                issues.push(ConfigIssue::new(
+                    format!("{at}.module"),
+                    format!("'{}' déjà déclaré en modules[{j}]", m.module),
+                ));
+                continue;
+            }
+            let module = instantiate(m, &m.module, &at).map_err(|found| issues.extend(found));
+            for (k, fb) in m.fallbacks.iter().enumerate() {
+                let at_fb = format!("{at}.fallbacks[{k}]");
+                if fb == &m.module {
+                    issues.push(ConfigIssue::new(
+                        at_fb,
+                        "un module ne peut pas être son propre fallback",
+                    ));
+                    continue;
+                }
+                match instantiate(&ModuleConfig::new(fb), fb, &at_fb) {
+                    Ok(fallback) => orchestrator.add_fallback(&m.module, fallback),
+                    Err(found) => issues.extend(found.into_iter().map(|issue| ConfigIssue {
+                        at: at_fb.clone(),
+                        ..issue
+                    })),
+                }
+            }
+            let Ok(module) = module else {
+                continue;
+            };
+            if m.after.is_empty() {
+                orchestrator.add_module(module);
+            } else {
+                orchestrator.add_module(Box::new(OrderedModule {
+                    inner: module,
+                    after: m.after.clone(),
+                }));
+            }
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError::Invalid { issues });
+        }
+
+        for (i, p) in self.plugins.iter().enumerate() {
+            let path = self.base_dir.join(&p.path);
+            if let Err(error) = plugins.load(&mut orchestrator, &path) {
+                return Err(ConfigError::Plugin {
+                    at: format!("plugins[{i}].path"),
+                    error,
+                });
+            }
+        }
+
+        // `after` peut citer un plugin : vérifié une fois ceux-ci chargés
+        for (i, m) in self.modules.iter().enumerate() {
+            for (k, dep) in m.after.iter().enumerate() {
+                if !declared.contains_key(dep.as_str()) && !plugins.names().contains(&dep.as_str())
+                {
+                    issues.push(ConfigIssue::new(
+                        format!("modules[{i}].after[{k}]"),
+                        format!("'{dep}' n'est ni un module déclaré ni un plugin chargé"),
+                    ));
+                }
+            }
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError::Invalid { issues });
+        }
+        orchestrator.validate().map_err(ConfigError::Pipeline)?;
+        Ok(orchestrator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    fn config(toml: &str) -> PipelineConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn issues(config: &PipelineConfig) -> Vec<(String, String)> {
+        match config.build(&mut PluginHost::new()) {
+            Err(ConfigError::Invalid { issues }) => {
+                issues.into_iter().map(|i| (i.at, i.message)).collect()
+            }
+            Err(e) => panic!("erreur inattendue : {e}"),
+            Ok(_) => panic!("configuration acceptée"),
+        }
+    }
+
+    fn locations(issues: &[(String, String)]) -> Vec<&str> {
+        issues.iter().map(|(at, _)| at.as_str()).collect()
+    }
+
+    #[test]
+    fn the_default_pipeline_builds() {
+        let orchestrator = PipelineConfig::default()
+            .build(&mut PluginHost::new())
+            .unwrap();
+        assert!(orchestrator.has_module("TextNlpModule"));
+        assert!(orchestrator.has_module("ImageModule"));
+    }
+
+    #[test]
+    fn every_issue_is_located() {
+        let found = issues(&config(
+            r#"
+            [[modules]]
+            module = "TextNlpModule"
+            params = { overrides = [{ category = " ", chars = [] }] }
+
+            [[modules]]
+            module = "ImageModule"
+            fallbacks = ["HeaderlesImageModule", "ImageModule"]
+
+            [[modules]]
+            module = "ImageModule"
+
+            [[modules]]
+            module = "AudioModule"
+            params = { rate = 8000 }
+            "#,
+        ));
+        assert_eq!(
+            locations(&found),
+            [
+                "modules[0].params.overrides[0].category",
+                "modules[0].params.overrides[0].chars",
+                "modules[1].fallbacks[0]",
+                "modules[1].fallbacks[1]",
+                "modules[2].module",
+                "modules[3].module",
+            ]
+        );
+        assert!(found[2]
+            .1
+            .contains("vouliez-vous dire 'HeaderlessImageModule'"));
+        assert!(found[4].1.contains("déjà déclaré en modules[1]"));
+    }
+
+    #[test]
+    fn params_are_checked_against_the_module() {
+        let found = issues(&config(
+            r#"
+            [[modules]]
+            module = "TextNlpModule"
+            params = { overides = [] }
+
+            [[modules]]
+            module = "ImageModule"
+            params = { quality = 3 }
+            "#,
+        ));
+        assert_eq!(
+            locations(&found),
+            ["modules[0].params", "modules[1].params"]
+        );
+        assert!(found[0].1.contains("overides"), "{}", found[0].1);
+    }
+
+    #[test]
+    fn after_and_plugins_are_resolved_last() {
+        let found = issues(&config(
+            r#"
+            [[modules]]
+            module = "TextNlpModule"
+            after = ["ImageModule", "UpperTextPlugin"]
+
+            [[modules]]
+            module = "ImageModule"
+            "#,
+        ));
+        assert_eq!(locations(&found), ["modules[0].after[1]"]);
+
+        let missing = config(
+            r#"
+            modules = []
+            plugins = [{ path = "absent.so" }, { path = "autre.so" }]
+            "#,
+        );
+        match missing.build(&mut PluginHost::new()) {
+            Err(ConfigError::Plugin { at, .. }) => assert_eq!(at, "plugins[0].path"),
+            _ => panic!("plugin absent accepté"),
+        }
+        assert_eq!(locations(&issues(&config("modules = []"))), ["modules"]);
+    }
+
+    #[test]
+    fn load_reports_the_file_and_format() {
+        let dir = std::env::temp_dir().join(format!("ai_populate_config_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        let toml = write("pipeline.toml", "[[modules]]\nmodule = \"ImageModule\"\n");
+        let loaded = PipelineConfig::load(&toml).unwrap();
+        assert_eq!(loaded.base_dir, dir);
+        assert_eq!(loaded.modules, [ModuleConfig::new("ImageModule")]);
+
+        let json = write("pipeline.json", r#"{ "modules": [], "extra": 1 }"#);
+        assert!(matches!(
+            PipelineConfig::load(&json),
+            Err(ConfigError::Parse { message, .. }) if message.contains("extra")
+        ));
+        let yaml = write("pipeline.yaml", "modules: []");
+        assert!(matches!(
+            PipelineConfig::load(&yaml),
+            Err(ConfigError::UnsupportedFormat { .. })
+        ));
+        assert!(matches!(
+            PipelineConfig::load(Path::new("absent.toml")),
+            Err(ConfigError::Io { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
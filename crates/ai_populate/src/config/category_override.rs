@@ -0,0 +1,13 @@
+//! Table d'override : ces caractères prennent cette catégorie
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryOverride {
+    pub category: String,
+    pub chars: Vec<char>,
+    /// Ne remplace que la première occurrence de chaque caractère
+    #[serde(default)]
+    pub first_only: bool,
+}
@@ -0,0 +1,91 @@
+//! Erreurs de lecture ou de validation du fichier de pipeline
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::orchestrator::PipelineError;
+use crate::plugin::PluginError;
+
+/// Problème localisé dans le fichier (ex: `modules[1].fallbacks[0]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub at: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn new(at: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            at: at.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} : {}", self.at, self.message)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    UnsupportedFormat {
+        path: PathBuf,
+    },
+    /// Syntaxe ou structure (le message donne la ligne et la colonne)
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    /// Contenu incohérent : tous les problèmes trouvés
+    Invalid {
+        issues: Vec<ConfigIssue>,
+    },
+    /// Un plugin déclaré n'a pas pu être chargé
+    Plugin {
+        at: String,
+        error: PluginError,
+    },
+    /// Le pipeline construit ne valide pas
+    Pipeline(Vec<PipelineError>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => write!(f, "{} : {error}", path.display()),
+            ConfigError::UnsupportedFormat { path } => write!(
+                f,
+                "{} : format non reconnu (extension .toml ou .json attendue)",
+                path.display()
+            ),
+            ConfigError::Parse { path, message } => {
+                write!(f, "{} : {}", path.display(), message.trim_end())
+            }
+            ConfigError::Invalid { issues } => {
+                write!(f, "configuration invalide")?;
+                for issue in issues {
+                    write!(f, "\n  {issue}")?;
+                }
+                Ok(())
+            }
+            ConfigError::Plugin { at, error } => write!(f, "{at} : {error}"),
+            ConfigError::Pipeline(errors) => {
+                write!(f, "pipeline invalide")?;
+                for e in errors {
+                    write!(f, "\n  {e}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub type ConfigResult<T> = Result<T, ConfigError>;
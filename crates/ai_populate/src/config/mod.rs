@@ -0,0 +1,18 @@
+pub mod association_rule;
+pub mod builder;
+pub mod category_override;
+pub mod config_error;
+pub mod module_config;
+pub mod ordered_module;
+pub mod pipeline_config;
+pub mod plugin_config;
+pub mod text_nlp_params;
+
+pub use association_rule::AssociationRule;
+pub use category_override::CategoryOverride;
+pub use config_error::{ConfigError, ConfigIssue, ConfigResult};
+pub use module_config::ModuleConfig;
+pub use ordered_module::OrderedModule;
+pub use pipeline_config::PipelineConfig;
+pub use plugin_config::PluginConfig;
+pub use text_nlp_params::TextNlpParams;
@@ -0,0 +1,31 @@
+//! Déclaration d'un module intégré dans le fichier de pipeline
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModuleConfig {
+    /// Nom du module intégré (ex: "TextNlpModule")
+    pub module: String,
+    /// Paramètres propres au module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+    /// Modules intégrés tentés, dans l'ordre, si celui-ci échoue
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    /// Modules à exécuter avant celui-ci (ordre explicite, en plus des
+    /// dépendances de données)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+impl ModuleConfig {
+    pub fn new(module: &str) -> Self {
+        Self {
+            module: module.into(),
+            params: None,
+            fallbacks: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+}
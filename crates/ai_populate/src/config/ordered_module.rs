@@ -0,0 +1,38 @@
+//! Module déclaré avec `after` : ajoute ces dépendances explicites à
+//! celles du module
+
+use crate::orchestrator::{DataPacket, Module, ModuleError, ModuleResult, Port};
+
+pub struct OrderedModule {
+    pub inner: Box<dyn Module>,
+    pub after: Vec<String>,
+}
+
+impl Module for OrderedModule {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+    fn modality(&self) -> &str {
+        self.inner.modality()
+    }
+    fn inputs(&self) -> Vec<Port> {
+        self.inner.inputs()
+    }
+    fn output(&self) -> Port {
+        self.inner.output()
+    }
+    fn depends_on(&self) -> Vec<String> {
+        let mut deps = self.inner.depends_on();
+        deps.extend(self.after.iter().cloned());
+        deps
+    }
+    fn process(&self, input: &DataPacket) -> ModuleResult {
+        self.inner.process(input)
+    }
+    fn process_all(&self, inputs: &[&DataPacket]) -> ModuleResult {
+        self.inner.process_all(inputs)
+    }
+    fn fallback(&self, input: &DataPacket, error: &ModuleError) -> Option<DataPacket> {
+        self.inner.fallback(input, error)
+    }
+}
@@ -0,0 +1,79 @@
+//! Fichier de pipeline (TOML ou JSON) : modules, paramètres, plugins
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::builder::parse_params;
+use crate::config::{ConfigError, ConfigResult, ModuleConfig, PluginConfig, TextNlpParams};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    /// Modules intégrés, dans l'ordre d'ajout
+    pub modules: Vec<ModuleConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginConfig>,
+    /// Répertoire de référence des chemins relatifs (celui du fichier)
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+}
+
+impl Default for PipelineConfig {
+    /// Le pipeline de démonstration : texte, et image avec son fallback
+    fn default() -> Self {
+        let mut image = ModuleConfig::new("ImageModule");
+        image.fallbacks.push("HeaderlessImageModule".into());
+        Self {
+            modules: vec![ModuleConfig::new("TextNlpModule"), image],
+            plugins: Vec::new(),
+            base_dir: PathBuf::from("."),
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Lit un fichier `.toml` ou `.json` (le format suit l'extension)
+    pub fn load(path: &Path) -> ConfigResult<Self> {
+        let src = fs::read_to_string(path).map_err(|error| ConfigError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let parse_error = |message: String| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+        let mut config: PipelineConfig = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&src).map_err(|e| parse_error(e.to_string()))?,
+            Some("json") => serde_json::from_str(&src).map_err(|e| parse_error(e.to_string()))?,
+            _ => {
+                return Err(ConfigError::UnsupportedFormat {
+                    path: path.to_path_buf(),
+                })
+            }
+        };
+        config.base_dir = path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        Ok(config)
+    }
+
+    /// Paramètres du premier TextNlpModule déclaré (défaut sinon)
+    pub fn text_nlp_params(&self) -> ConfigResult<TextNlpParams> {
+        let declared = self
+            .modules
+            .iter()
+            .enumerate()
+            .find(|(_, m)| m.module == "TextNlpModule");
+        match declared {
+            Some((i, m)) => {
+                parse_params(m, &format!("modules[{i}]")).map_err(|issue| ConfigError::Invalid {
+                    issues: vec![issue],
+                })
+            }
+            None => Ok(TextNlpParams::default()),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Plugin à charger au démarrage du pipeline
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginConfig {
+    /// Bibliothèque dynamique, relative au fichier de configuration
+    pub path: PathBuf,
+}
@@ -0,0 +1,33 @@
+//! Paramètres de TextNlpModule (section `params` de sa déclaration)
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AssociationRule, CategoryOverride};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TextNlpParams {
+    /// Tables appliquées dans l'ordre (la dernière l'emporte)
+    pub overrides: Vec<CategoryOverride>,
+    pub associations: AssociationRule,
+}
+
+impl Default for TextNlpParams {
+    fn default() -> Self {
+        Self {
+            overrides: vec![
+                CategoryOverride {
+                    category: "SpecialPunct".into(),
+                    chars: vec!['!'],
+                    first_only: true,
+                },
+                CategoryOverride {
+                    category: "LetterAccented".into(),
+                    chars: vec!['Ç', 'À', 'É'],
+                    first_only: false,
+                },
+            ],
+            associations: AssociationRule::default(),
+        }
+    }
+}
@@ -1,14 +1,24 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::{
-    category_registry::CategoryRegistry, char_meta::CharMeta, default_category_id, uppercase_flag,
+    category_registry::CategoryRegistry, char_meta::CharMeta, default_category_id, grapheme_flags,
+    grapheme_meta::GraphemeMeta, token::Token, token_kind::TokenKind, uppercase_flag,
 };
 
 /* ========= Encodage ========= */
-#[derive(Debug, Clone)]
+/// Produit par `encode` ; relu depuis l'extérieur (JSON, plugin), il doit
+/// passer `check` avant d'être utilisé
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Encoded {
     pub bytes: Vec<u8>,       // données “brutes” UTF-8 (source de vérité)
     pub metas: Vec<CharMeta>, // 1 entrée par caractère Unicode
+    /// 1 entrée par grappe de graphèmes (ce que le lecteur voit comme un caractère)
+    #[serde(default)]
+    pub graphemes: Vec<GraphemeMeta>,
     pub registry: CategoryRegistry,
     /// association_id -> (type, membres)
     pub associations: HashMap<usize, (String, Vec<usize>)>,
@@ -19,6 +29,64 @@ impl Encoded {
         String::from_utf8(self.bytes.clone()).expect("UTF-8 valide")
     }
 
+    /// Vérifie la cohérence d'un encodage désérialisé : `bytes` en UTF-8,
+    /// metas et grappes qui couvrent `bytes` dans l'ordre, sans trou ni
+    /// chevauchement, membres d'association existants. Les accesseurs
+    /// (`decode_utf8`, `tokens`, ...) supposent ces invariants.
+    pub fn check(&self) -> Result<(), String> {
+        let text =
+            std::str::from_utf8(&self.bytes).map_err(|e| format!("octets non UTF-8 ({e})"))?;
+        let mut end = 0;
+        for (i, m) in self.metas.iter().enumerate() {
+            let span = &m.byte_span;
+            if span.start != end || text.get(span.clone()) != Some(m.ch.encode_utf8(&mut [0; 4])) {
+                return Err(format!("meta {i} : {span:?} ne contient pas {:?}", m.ch));
+            }
+            end = span.end;
+        }
+        if end != self.bytes.len() {
+            return Err(format!(
+                "metas : {end} octets couverts sur {}",
+                self.bytes.len()
+            ));
+        }
+        if !self.graphemes.is_empty() {
+            let (mut end, mut meta) = (0, 0);
+            for (i, g) in self.graphemes.iter().enumerate() {
+                let (bytes, metas) = (&g.byte_span, &g.meta_span);
+                let first = self.metas.get(metas.start).map(|m| m.byte_span.start);
+                let last = metas
+                    .end
+                    .checked_sub(1)
+                    .and_then(|j| self.metas.get(j))
+                    .map(|m| m.byte_span.end);
+                if bytes.start != end
+                    || metas.start != meta
+                    || metas.is_empty()
+                    || first != Some(bytes.start)
+                    || last != Some(bytes.end)
+                {
+                    return Err(format!(
+                        "grappe {i} : octets {bytes:?} et metas {metas:?} incohérents"
+                    ));
+                }
+                (end, meta) = (bytes.end, metas.end);
+            }
+            if meta != self.metas.len() {
+                return Err(format!(
+                    "grappes : {meta} metas couvertes sur {}",
+                    self.metas.len()
+                ));
+            }
+        }
+        for (id, (_, members)) in &self.associations {
+            if let Some(i) = members.iter().find(|&&i| i >= self.metas.len()) {
+                return Err(format!("association {id} : meta {i} inexistante"));
+            }
+        }
+        Ok(())
+    }
+
     /// change la catégorie d’un caractère (par index de meta)
     pub fn set_category(&mut self, meta_index: usize, new_cat: usize) {
         if let Some(m) = self.metas.get_mut(meta_index) {
@@ -26,16 +94,117 @@ impl Encoded {
         }
     }
 
-    /// permet des overrides par caractère (ex: ‘!’ => “SpecialPunct”)
+    /// change la catégorie d’une grappe (par index de grappe)
+    pub fn set_grapheme_category(&mut self, index: usize, new_cat: usize) {
+        if let Some(g) = self.graphemes.get_mut(index) {
+            g.cat_id = new_cat;
+        }
+    }
+
+    /// Texte d'une grappe tel qu'il apparaît dans `bytes` (NFC ou NFD)
+    pub fn grapheme_text(&self, grapheme: &GraphemeMeta) -> &str {
+        std::str::from_utf8(&self.bytes[grapheme.byte_span.clone()]).expect("UTF-8 valide")
+    }
+
+    /// Points de code d'une grappe
+    pub fn grapheme_metas(&self, grapheme: &GraphemeMeta) -> &[CharMeta] {
+        &self.metas[grapheme.meta_span.clone()]
+    }
+
+    /// Découpe le texte en tokens (frontières de mots Unicode, UAX #29) ;
+    /// les suites de ponctuation ou d'espaces forment un seul token. Les
+    /// tokens couvrent tout le texte : concaténés, ils redonnent `bytes`.
+    /// Calculés à la demande, ils suivent les overrides de catégorie.
+    pub fn tokens(&self) -> Vec<Token> {
+        let text = std::str::from_utf8(&self.bytes).expect("UTF-8 valide");
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut meta = 0;
+        let mut grapheme = 0;
+        for (start, segment) in text.split_word_bound_indices() {
+            let end = start + segment.len();
+            let first = meta;
+            let first_grapheme = grapheme;
+            while meta < self.metas.len() && self.metas[meta].byte_span.start < end {
+                meta += 1;
+            }
+            while grapheme < self.graphemes.len() && self.graphemes[grapheme].byte_span.start < end
+            {
+                grapheme += 1;
+            }
+            let kind = TokenKind::of(segment);
+            match tokens.last_mut() {
+                Some(last) if last.kind == kind && kind.merges_runs() => {
+                    last.byte_span.end = end;
+                    last.meta_span.end = meta;
+                    last.grapheme_span.end = grapheme;
+                }
+                _ => tokens.push(Token {
+                    byte_span: start..end,
+                    meta_span: first..meta,
+                    grapheme_span: first_grapheme..grapheme,
+                    kind,
+                    cat_id: 0,
+                }),
+            }
+        }
+        for t in &mut tokens {
+            t.cat_id = self.aggregate_category(t);
+        }
+        tokens
+    }
+
+    /// Texte d'un token (tranche de `bytes`)
+    pub fn token_text(&self, token: &Token) -> &str {
+        std::str::from_utf8(&self.bytes[token.byte_span.clone()]).expect("UTF-8 valide")
+    }
+
+    /// Catégorie majoritaire des grappes (la première en cas d'égalité) :
+    /// un texte NFD s'agrège comme sa forme NFC. Sans grappes (encodage relu
+    /// sans le champ), on se rabat sur les points de code.
+    fn aggregate_category(&self, token: &Token) -> usize {
+        if self.graphemes.is_empty() {
+            majority(self.metas[token.meta_span.clone()].iter().map(|m| m.cat_id))
+        } else {
+            majority(
+                self.graphemes[token.grapheme_span.clone()]
+                    .iter()
+                    .map(|g| g.cat_id),
+            )
+        }
+    }
+
+    /// permet des overrides par caractère (ex: ‘!’ => “SpecialPunct”) ;
+    /// côté grappes, on compare la forme NFC (‘E’ + U+0301 vaut ‘É’)
     pub fn apply_overrides_by_char(&mut self, overrides: &HashMap<char, usize>) {
         for m in &mut self.metas {
             if let Some(&new_id) = overrides.get(&m.ch) {
                 m.cat_id = new_id;
             }
         }
+        for g in &mut self.graphemes {
+            if let Some(&new_id) = g.composed().and_then(|c| overrides.get(&c)) {
+                g.cat_id = new_id;
+            }
+        }
     }
 }
 
+/// Valeur la plus fréquente (la première en cas d'égalité), 0 si aucune
+fn majority(cats: impl Iterator<Item = usize>) -> usize {
+    let mut counts: Vec<(usize, usize)> = Vec::new();
+    for cat in cats {
+        match counts.iter_mut().find(|(c, _)| *c == cat) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((cat, 1)),
+        }
+    }
+    let best = counts.iter().map(|(_, n)| *n).max().unwrap_or(0);
+    counts
+        .iter()
+        .find(|(_, n)| *n == best)
+        .map_or(0, |(cat, _)| *cat)
+}
+
 /* ========= Encodeur principal ========= */
 pub fn encode(text: &str, mut registry: CategoryRegistry) -> Encoded {
     // on garde une copie brute UTF-8 (source de vérité immuable)
@@ -64,10 +233,203 @@ pub fn encode(text: &str, mut registry: CategoryRegistry) -> Encoded {
         });
     }
 
+    // puis par grappes de graphèmes : catégorie et flags de la forme NFC,
+    // pour qu'un texte NFD se catégorise comme son équivalent composé
+    let mut graphemes = Vec::new();
+    let mut meta = 0;
+    for (start, cluster) in text.grapheme_indices(true) {
+        let end = start + cluster.len();
+        let first = meta;
+        while meta < metas.len() && metas[meta].byte_span.start < end {
+            meta += 1;
+        }
+        let nfc: String = cluster.nfc().collect();
+        let base = nfc.chars().next().expect("grappe non vide");
+        graphemes.push(GraphemeMeta {
+            byte_span: start..end,
+            meta_span: first..meta,
+            cat_id: default_category_id(&mut registry, base),
+            flags: grapheme_flags(base, meta - first),
+            nfc,
+        });
+    }
+
     Encoded {
         bytes,
         metas,
+        graphemes,
         registry,
         associations: HashMap::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enc(text: &str) -> Encoded {
+        encode(text, CategoryRegistry::default())
+    }
+
+    fn tokens(e: &Encoded) -> Vec<(&str, TokenKind)> {
+        e.tokens()
+            .iter()
+            .map(|t| (e.token_text(t), t.kind))
+            .collect()
+    }
+
+    #[test]
+    fn tokens_cover_the_text() {
+        for text in [
+            "Bonjour, le monde !?! 3.14 l'eau",
+            "",
+            "   ",
+            "Ça va ?\n\tE\u{301}te\u{301} 👍🏽 …",
+        ] {
+            let e = enc(text);
+            let tokens = e.tokens();
+            let joined: Vec<u8> = tokens
+                .iter()
+                .flat_map(|t| e.bytes[t.byte_span.clone()].to_vec())
+                .collect();
+            assert_eq!(joined, e.bytes, "{text:?}");
+            // les tokens se suivent, dans les trois index
+            let (mut byte, mut meta, mut grapheme) = (0, 0, 0);
+            for t in &tokens {
+                assert_eq!(t.byte_span.start, byte);
+                assert_eq!(t.meta_span.start, meta);
+                assert_eq!(t.grapheme_span.start, grapheme);
+                (byte, meta, grapheme) = (t.byte_span.end, t.meta_span.end, t.grapheme_span.end);
+                let chars: String = e.metas[t.meta_span.clone()].iter().map(|m| m.ch).collect();
+                assert_eq!(chars, e.token_text(t));
+            }
+            assert_eq!(
+                (byte, meta, grapheme),
+                (e.bytes.len(), e.metas.len(), e.graphemes.len())
+            );
+        }
+    }
+
+    #[test]
+    fn punctuation_and_whitespace_runs_merge() {
+        let e = enc("Quoi?!...   Non, 3.14 l'eau");
+        assert_eq!(
+            tokens(&e),
+            [
+                ("Quoi", TokenKind::Word),
+                ("?!...", TokenKind::Punct),
+                ("   ", TokenKind::Whitespace),
+                ("Non", TokenKind::Word),
+                (",", TokenKind::Punct),
+                (" ", TokenKind::Whitespace),
+                ("3.14", TokenKind::Number),
+                (" ", TokenKind::Whitespace),
+                ("l'eau", TokenKind::Word),
+            ]
+        );
+        // les mots voisins restent distincts
+        assert_eq!(
+            tokens(&enc("un deux")),
+            [
+                ("un", TokenKind::Word),
+                (" ", TokenKind::Whitespace),
+                ("deux", TokenKind::Word)
+            ]
+        );
+    }
+
+    #[test]
+    fn token_category_follows_the_majority_of_graphemes() {
+        let mut e = enc("Été É");
+        let accented = e.registry.id_or_insert("LetterAccented");
+        e.apply_overrides_by_char(&HashMap::from([('É', accented), ('é', accented)]));
+        let letter = e.registry.id_or_insert("Letter");
+        let cats: Vec<usize> = e.tokens().iter().map(|t| t.cat_id).collect();
+        // "Été" : deux grappes accentuées sur trois ; "É" : la seule
+        assert_eq!(cats[0], accented);
+        assert_eq!(cats[2], accented);
+        let mut e = enc("Étés");
+        e.set_grapheme_category(0, accented);
+        assert_eq!(e.tokens()[0].cat_id, letter);
+    }
+
+    #[test]
+    fn token_category_falls_back_to_code_points_without_graphemes() {
+        let mut e = enc("Été, oui");
+        let accented = e.registry.id_or_insert("LetterAccented");
+        e.apply_overrides_by_char(&HashMap::from([('É', accented), ('é', accented)]));
+        let with_graphemes: Vec<usize> = e.tokens().iter().map(|t| t.cat_id).collect();
+
+        // relu d'un JSON sans `graphemes` (valeur par défaut du champ)
+        let mut json = serde_json::to_value(&e).unwrap();
+        json.as_object_mut().unwrap().remove("graphemes");
+        let e: Encoded = serde_json::from_value(json).unwrap();
+        assert!(e.graphemes.is_empty());
+        e.check().unwrap();
+        let cats: Vec<usize> = e.tokens().iter().map(|t| t.cat_id).collect();
+        assert_eq!(cats, with_graphemes);
+        assert_eq!(cats[0], accented);
+        assert_ne!(cats[1], 0);
+    }
+
+    fn categories(e: &Encoded) -> Vec<(String, &str, u8)> {
+        e.graphemes
+            .iter()
+            .map(|g| {
+                (
+                    g.nfc.clone(),
+                    e.registry.name(g.cat_id),
+                    g.flags & crate::FLAG_UPPERCASE,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nfd_text_categorizes_like_its_nfc_form() {
+        let text = "Ça été À l'É 1€ !";
+        let (nfc, nfd) = (
+            enc(&text.nfc().collect::<String>()),
+            enc(&text.nfd().collect::<String>()),
+        );
+        assert!(nfd.metas.len() > nfc.metas.len());
+        assert_eq!(nfd.graphemes.len(), nfc.graphemes.len());
+        assert_eq!(categories(&nfd), categories(&nfc));
+
+        let e_acute = &nfd.graphemes[3];
+        assert_eq!(nfd.grapheme_text(e_acute), "e\u{301}");
+        assert_eq!(e_acute.composed(), Some('é'));
+        assert_ne!(e_acute.flags & crate::FLAG_MULTI_CODEPOINT, 0);
+        assert_eq!(nfd.grapheme_metas(e_acute).len(), 2);
+
+        // un override sur le caractère composé atteint aussi la forme NFD
+        let mut overridden = [nfc, nfd];
+        for e in &mut overridden {
+            let accented = e.registry.id_or_insert("LetterAccented");
+            e.apply_overrides_by_char(&HashMap::from([('é', accented), ('É', accented)]));
+        }
+        assert_eq!(categories(&overridden[0]), categories(&overridden[1]));
+        let names: Vec<&str> = overridden[1]
+            .tokens()
+            .iter()
+            .map(|t| overridden[1].registry.name(t.cat_id))
+            .collect();
+        assert_eq!(names[2], "LetterAccented");
+    }
+
+    #[test]
+    fn emoji_sequences_form_a_single_cluster() {
+        // famille (ZWJ), drapeau (indicateurs régionaux), pouce et teinte
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let text = format!("{family}🇫🇷👍🏽a");
+        let e = enc(&text);
+        let clusters: Vec<&str> = e.graphemes.iter().map(|g| e.grapheme_text(g)).collect();
+        assert_eq!(clusters, [family, "🇫🇷", "👍🏽", "a"]);
+        assert_eq!(e.grapheme_metas(&e.graphemes[0]).len(), 5);
+        assert!(e.graphemes[..3]
+            .iter()
+            .all(|g| g.flags & crate::FLAG_MULTI_CODEPOINT != 0));
+        assert_eq!(e.graphemes[3].flags & crate::FLAG_MULTI_CODEPOINT, 0);
+        assert_eq!(e.check(), Ok(()));
+    }
+}
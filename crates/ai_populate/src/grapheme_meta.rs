@@ -0,0 +1,24 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/* ========= GraphemeMeta : grappe de graphèmes (UAX #29) au-dessus des CharMeta ========= */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphemeMeta {
+    pub byte_span: Range<usize>, // où elle se trouve dans le buffer UTF-8
+    pub meta_span: Range<usize>, // ses points de code dans Encoded::metas
+    pub nfc: String,             // forme composée (NFC), base de la catégorisation
+    pub cat_id: usize,           // catégorie de la grappe (modifiable)
+    pub flags: u8,               // ex: uppercase, plusieurs points de code
+}
+
+impl GraphemeMeta {
+    /// Le caractère unique de la forme NFC, s'il existe (‘E’ + U+0301 -> ‘É’)
+    pub fn composed(&self) -> Option<char> {
+        let mut chars = self.nfc.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Fallback du module image : formats sans signature, que la détection
+//! automatique ne reconnaît pas (TGA)
+use crate::orchestrator::{DataPacket, Module, ModuleError, ModuleResult, Pixels, Port};
+use image::{GenericImageView, ImageFormat};
+
+/// Formats essayés dans l'ordre
+const HEADERLESS_FORMATS: [ImageFormat; 1] = [ImageFormat::Tga];
+
+pub struct HeaderlessImageModule;
+
+impl Module for HeaderlessImageModule {
+    fn name(&self) -> &str {
+        "HeaderlessImageModule"
+    }
+    fn modality(&self) -> &str {
+        "image"
+    }
+    fn inputs(&self) -> Vec<Port> {
+        vec![Port::new::<Vec<u8>>("image")]
+    }
+    fn output(&self) -> Port {
+        Port::new::<Pixels>("pixels")
+    }
+    fn process(&self, input: &DataPacket) -> ModuleResult {
+        let img = input.payload.get::<Vec<u8>>()?;
+        for format in HEADERLESS_FORMATS {
+            if let Ok(imgbuf) = image::load_from_memory_with_format(img, format) {
+                let (width, height) = imgbuf.dimensions();
+                let mut meta = input.meta.clone();
+                meta.insert("image_format".into(), format!("{:?}", format));
+                meta.insert("image_size".into(), img.len().to_string());
+                return Ok(DataPacket {
+                    modality: "image".into(),
+                    payload: Pixels {
+                        width,
+                        height,
+                        color: format!("{:?}", imgbuf.color()),
+                        data: imgbuf.as_bytes().to_vec(),
+                    }
+                    .into(),
+                    meta,
+                    trace: Default::default(),
+                });
+            }
+        }
+        Err(ModuleError::failed(format!(
+            "aucun format parmi {:?}",
+            HEADERLESS_FORMATS
+        )))
+    }
+}
@@ -1,5 +1,5 @@
 //! Module image modulaire : décodage réel avec la crate image
-use crate::orchestrator::{DataPacket, Module};
+use crate::orchestrator::{DataPacket, Module, ModuleError, ModuleResult, Pixels, Port};
 use image::GenericImageView;
 
 pub struct ImageModule;
@@ -11,43 +11,42 @@ impl Module for ImageModule {
     fn modality(&self) -> &str {
         "image"
     }
-    fn process(&self, input: &DataPacket) -> DataPacket {
-        if let Some(img) = (input.payload.as_ref() as &dyn std::any::Any).downcast_ref::<Vec<u8>>()
-        {
-            let mut meta = input.meta.clone();
-            // Détection du format et décodage
-            let format = image::guess_format(img)
-                .map(|f| format!("{:?}", f))
-                .unwrap_or("Unknown".to_string());
-            let decoded = image::load_from_memory(img);
-            match decoded {
-                Ok(imgbuf) => {
-                    let (w, h) = imgbuf.dimensions();
-                    let color = format!("{:?}", imgbuf.color());
-                    meta.insert("image_format".into(), format);
-                    meta.insert("image_size".into(), img.len().to_string());
-                    meta.insert("width".into(), w.to_string());
-                    meta.insert("height".into(), h.to_string());
-                    meta.insert("color_type".into(), color);
-                    // On expose les pixels sous forme de Vec<u8> (flatten)
-                    let pixels: Vec<u8> = imgbuf.as_bytes().to_vec();
-                    DataPacket {
-                        modality: "image".into(),
-                        payload: Box::new(pixels),
-                        meta,
-                    }
-                }
-                Err(e) => {
-                    meta.insert("image_error".into(), format!("Erreur décodage: {}", e));
-                    DataPacket {
-                        modality: "image".into(),
-                        payload: Box::new(img.clone()),
-                        meta,
-                    }
-                }
+    fn inputs(&self) -> Vec<Port> {
+        vec![Port::new::<Vec<u8>>("image")]
+    }
+    fn output(&self) -> Port {
+        Port::new::<Pixels>("pixels")
+    }
+    fn process(&self, input: &DataPacket) -> ModuleResult {
+        let img = input.payload.get::<Vec<u8>>()?;
+        let mut meta = input.meta.clone();
+        // Détection du format et décodage
+        let format = image::guess_format(img)
+            .map(|f| format!("{:?}", f))
+            .unwrap_or("Unknown".to_string());
+        let decoded = image::load_from_memory(img);
+        match decoded {
+            Ok(imgbuf) => {
+                let (w, h) = imgbuf.dimensions();
+                let color = format!("{:?}", imgbuf.color());
+                meta.insert("image_format".into(), format);
+                meta.insert("image_size".into(), img.len().to_string());
+                // On expose les pixels à plat, avec leurs dimensions
+                let pixels = Pixels {
+                    width: w,
+                    height: h,
+                    color,
+                    data: imgbuf.as_bytes().to_vec(),
+                };
+                Ok(DataPacket {
+                    modality: "image".into(),
+                    payload: pixels.into(),
+                    meta,
+                    trace: Default::default(),
+                })
             }
-        } else {
-            input.clone()
+            // formats sans signature : voir HeaderlessImageModule
+            Err(e) => Err(ModuleError::failed(format!("Erreur décodage: {}", e))),
         }
     }
 }
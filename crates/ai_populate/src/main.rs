@@ -1,24 +1,43 @@
 mod category_registry;
 mod char_meta;
+mod config;
 mod encoded;
+mod grapheme_meta;
+mod headerless_image_module;
 mod image_module;
 mod orchestrator;
+mod plugin;
 mod print;
+mod token;
+mod token_kind;
 
-use std::any;
 use std::collections::HashMap;
+use std::env;
 use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
 use strsim::levenshtein;
 use unicode_categories::UnicodeCategories;
 use unicode_normalization::UnicodeNormalization;
 
 use crate::category_registry::CategoryRegistry;
+use crate::config::{AssociationRule, PipelineConfig, TextNlpParams};
 use crate::encoded::{encode, Encoded};
-use crate::image_module::ImageModule;
-use crate::orchestrator::{DataPacket, Module, Orchestrator};
-use crate::print::{print_metas, print_metas_overrides};
+use crate::orchestrator::{
+    DataPacket, Module, ModuleResult, Orchestrator, PipelineError, Pixels, Port,
+};
+use crate::plugin::PluginHost;
+use crate::print::{print_graphemes, print_metas, print_metas_overrides, print_tokens};
 // === Exemple de module textuel pour orchestrateur ===
-struct TextNlpModule;
+struct TextNlpModule {
+    params: TextNlpParams,
+}
+
+impl TextNlpModule {
+    fn new(params: TextNlpParams) -> Self {
+        Self { params }
+    }
+}
 
 impl Module for TextNlpModule {
     fn name(&self) -> &str {
@@ -27,34 +46,49 @@ impl Module for TextNlpModule {
     fn modality(&self) -> &str {
         "text"
     }
-    fn process(&self, input: &DataPacket) -> DataPacket {
-        // On suppose que le payload est une String
-        if let Some(text) = (input.payload.as_ref() as &dyn any::Any).downcast_ref::<String>() {
-            let mut enc = encode(text, CategoryRegistry::default());
-            // Override direct sur '!'
-            override_char_category(&mut enc, '!', "SpecialPunct");
-            // Overrides par table
-            override_chars_table(&mut enc, &['Ç', 'À', 'É'], "LetterAccented");
-            // Associations automatiques (casse et accents)
-            let (norm_to_group, group_id_to_type) = build_auto_associations(&enc);
-            let mut enc = enc;
-            apply_associations(&mut enc, &norm_to_group, &group_id_to_type);
-            // On renvoie le résultat dans le payload (encodé dans Box)
-            let mut meta = input.meta.clone();
-            meta.insert("reconstructed".into(), enc.decode_utf8());
-            DataPacket {
-                modality: "text".into(),
-                payload: Box::new(enc),
-                meta,
+    fn inputs(&self) -> Vec<Port> {
+        vec![Port::new::<String>("text")]
+    }
+    fn output(&self) -> Port {
+        Port::new::<Encoded>("encoded")
+    }
+    fn process(&self, input: &DataPacket) -> ModuleResult {
+        // Le payload doit être un Text (sinon : non compris)
+        let text = input.payload.get::<String>()?;
+        let mut enc = encode(text, CategoryRegistry::default());
+        // Overrides configurables : direct sur la première occurrence
+        // (ex: '!' -> "SpecialPunct") ou par table
+        for table in &self.params.overrides {
+            if table.first_only {
+                for &ch in &table.chars {
+                    override_char_category(&mut enc, ch, &table.category);
+                }
+            } else {
+                override_chars_table(&mut enc, &table.chars, &table.category);
             }
-        } else {
-            input.clone()
         }
+        // Associations automatiques (casse et accents)
+        let rule = &self.params.associations;
+        if rule.enabled {
+            let (norm_to_group, group_id_to_type) = build_auto_associations(&enc, rule);
+            apply_associations(&mut enc, &norm_to_group, &group_id_to_type, rule);
+        }
+        // On renvoie le résultat dans le payload (Encoded)
+        let mut meta = input.meta.clone();
+        meta.insert("reconstructed".into(), enc.decode_utf8());
+        Ok(DataPacket {
+            modality: "text".into(),
+            payload: enc.into(),
+            meta,
+            trace: Default::default(),
+        })
     }
 }
 
 /* ========= Métadonnées par caractère ========= */
 const FLAG_UPPERCASE: u8 = 0b0000_0001;
+/// Grappe formée de plusieurs points de code (base + diacritiques, ZWJ...)
+const FLAG_MULTI_CODEPOINT: u8 = 0b0000_0010;
 
 /* ========= Heuristique simple de catégorisation par défaut ========= */
 // Note: on reste volontairement simple (sans lib Unicode avancée)
@@ -83,6 +117,17 @@ fn uppercase_flag(ch: char) -> u8 {
     }
 }
 
+/// Flags d'une grappe : ceux de son caractère de base (forme NFC) et le
+/// nombre de points de code qui la composent
+fn grapheme_flags(base: char, codepoints: usize) -> u8 {
+    let multi = if codepoints > 1 {
+        FLAG_MULTI_CODEPOINT
+    } else {
+        0
+    };
+    uppercase_flag(base) | multi
+}
+
 /* ========= Démonstration ========= */
 /// Applique un override direct sur un caractère donné (ex: '!' -> "SpecialPunct")
 fn override_char_category(enc: &mut Encoded, ch: char, cat_name: &str) {
@@ -90,6 +135,9 @@ fn override_char_category(enc: &mut Encoded, ch: char, cat_name: &str) {
     if let Some((i, _)) = enc.metas.iter().enumerate().find(|(_, m)| m.ch == ch) {
         enc.set_category(i, cat_id);
     }
+    if let Some(i) = enc.graphemes.iter().position(|g| g.composed() == Some(ch)) {
+        enc.set_grapheme_category(i, cat_id);
+    }
 }
 
 /// Applique des overrides par table (ex: ['Ç', 'À', 'É'] -> "LetterAccented")
@@ -103,31 +151,47 @@ fn override_chars_table(enc: &mut Encoded, chars: &[char], cat_name: &str) -> Ha
     ov
 }
 
+/// Forme normalisée d'une grappe selon la règle (minuscule, sans accents)
+fn normalized_form(nfc: &str, rule: &AssociationRule) -> String {
+    let s: String = if rule.fold_case {
+        nfc.to_lowercase()
+    } else {
+        nfc.to_string()
+    };
+    if rule.strip_accents {
+        s.nfkd().filter(|c| !c.is_mark_nonspacing()).collect()
+    } else {
+        s
+    }
+}
+
 /// Construit automatiquement des associations intelligentes (casse et accents)
-fn build_auto_associations(enc: &Encoded) -> (HashMap<String, usize>, HashMap<usize, String>) {
+///
+/// On regroupe des grappes (forme NFC) et non des points de code : en NFD,
+/// ‘E’ + U+0301 est un seul membre ‘É’, pas un ‘E’ et un accent isolé.
+fn build_auto_associations(
+    enc: &Encoded,
+    rule: &AssociationRule,
+) -> (HashMap<String, usize>, HashMap<usize, String>) {
     let mut norm_to_group: HashMap<String, usize> = HashMap::new();
     let mut group_id_to_type = HashMap::new();
     let mut next_group = 0;
 
-    // Collecte de toutes les formes normalisées uniques (minuscule + sans accents)
+    // Collecte de toutes les formes normalisées uniques (selon la règle)
     let mut all_norms: Vec<String> = Vec::new();
-    for m in &enc.metas {
-        let norm =
-            m.ch.to_lowercase()
-                .nfkd()
-                .filter(|c| !c.is_mark_nonspacing())
-                .collect::<String>();
+    for g in &enc.graphemes {
+        let norm = normalized_form(&g.nfc, rule);
         if !all_norms.contains(&norm) {
             all_norms.push(norm);
         }
     }
 
-    // Clustering par similarité de forme (Levenshtein <= 1)
+    // Clustering par similarité de forme (Levenshtein <= max_distance)
     let mut norm_to_groupid: HashMap<String, usize> = HashMap::new();
     for norm in &all_norms {
         let mut found = false;
         for (g_norm, &gid) in &norm_to_groupid {
-            if levenshtein(norm, g_norm) <= 1 {
+            if levenshtein(norm, g_norm) <= rule.max_distance {
                 norm_to_groupid.insert(norm.clone(), gid);
                 found = true;
                 break;
@@ -135,18 +199,14 @@ fn build_auto_associations(enc: &Encoded) -> (HashMap<String, usize>, HashMap<us
         }
         if !found {
             norm_to_groupid.insert(norm.clone(), next_group);
-            group_id_to_type.insert(next_group, "auto+sim".to_string());
+            group_id_to_type.insert(next_group, rule.kind.clone());
             next_group += 1;
         }
     }
 
-    // Remplissage final pour chaque caractère
-    for m in &enc.metas {
-        let norm =
-            m.ch.to_lowercase()
-                .nfkd()
-                .filter(|c| !c.is_mark_nonspacing())
-                .collect::<String>();
+    // Remplissage final pour chaque grappe
+    for g in &enc.graphemes {
+        let norm = normalized_form(&g.nfc, rule);
         let gid = *norm_to_groupid.get(&norm).unwrap();
         norm_to_group.insert(norm, gid);
     }
@@ -154,20 +214,22 @@ fn build_auto_associations(enc: &Encoded) -> (HashMap<String, usize>, HashMap<us
 }
 
 /// Applique les associations de groupes sur les caractères et construit la map des associations
+///
+/// Tous les points de code d'une grappe reçoivent le groupe de sa forme NFC ;
+/// les membres restent des index de `metas`.
 fn apply_associations(
     enc: &mut Encoded,
     norm_to_group: &HashMap<String, usize>,
     group_id_to_type: &HashMap<usize, String>,
+    rule: &AssociationRule,
 ) {
-    // Associe chaque caractère à son groupe normalisé
-    for m in enc.metas.iter_mut() {
-        let norm =
-            m.ch.to_lowercase()
-                .nfkd()
-                .filter(|c| !c.is_mark_nonspacing())
-                .collect::<String>();
+    // Associe chaque grappe (et ses points de code) à son groupe normalisé
+    for g in &enc.graphemes {
+        let norm = normalized_form(&g.nfc, rule);
         if let Some(&gid) = norm_to_group.get(&norm) {
-            m.association_id = Some(gid);
+            for m in &mut enc.metas[g.meta_span.clone()] {
+                m.association_id = Some(gid);
+            }
         }
     }
     // Construit la map association_id -> (type, membres)
@@ -186,7 +248,57 @@ fn apply_associations(
     }
 }
 
-fn main() {
+/// Affiche les erreurs de validation ou d'exécution du pipeline
+fn report_pipeline_errors(errors: &[PipelineError]) {
+    for e in errors {
+        eprintln!("Erreur pipeline : {e}");
+    }
+}
+
+/// Décode une image via l'orchestrateur (avec fallback) et affiche le résultat
+fn run_image(
+    orchestrator: &Orchestrator,
+    label: &str,
+    bytes: Vec<u8>,
+) -> Result<(), Vec<PipelineError>> {
+    let packet = DataPacket {
+        modality: "image".into(),
+        payload: bytes.into(),
+        meta: Default::default(),
+        trace: Default::default(),
+    };
+    let output = orchestrator.run_with_fallback(packet)?;
+    let Some(result) = output.get("pixels") else {
+        return Ok(());
+    };
+    let unknown = "?".to_string();
+    match result.payload.get::<Pixels>() {
+        Ok(pixels) => println!(
+            "\n[Image {}] Format: {} | {}x{} | Color: {} | Pixels: {} octets | Chemin: {}",
+            label,
+            result.meta.get("image_format").unwrap_or(&unknown),
+            pixels.width,
+            pixels.height,
+            pixels.color,
+            pixels.data.len(),
+            result.meta.get("ImageModule.path").unwrap_or(&unknown)
+        ),
+        Err(e) => println!("\n[Image {}] Erreur : {}", label, e),
+    }
+    Ok(())
+}
+
+/// Prend en compte les plugins recompilés depuis leur chargement
+fn reload_plugins(plugins: &mut PluginHost, orchestrator: &mut Orchestrator) {
+    for (name, result) in plugins.reload_changed(orchestrator) {
+        match result {
+            Ok(()) => println!("Plugin rechargé : {name}"),
+            Err(e) => eprintln!("Plugin {name} conservé dans sa version précédente : {e}"),
+        }
+    }
+}
+
+fn main() -> ExitCode {
     let text =
         "aAbBcCdDeEfFgGhHiIjJkKlLmMnNoOpPqQrRsStTuUvVwWxXyYzZ Bonjour 123! Ça va ?".to_string();
 
@@ -194,79 +306,209 @@ fn main() {
     let png_bytes = fs::read("test_image.png").expect("Fichier test_image.png introuvable");
     let jpeg_bytes = fs::read("test_image.jpg").expect("Fichier test_image.jpg introuvable");
 
-    let mut orchestrator = Orchestrator::new();
-    orchestrator.add_module(Box::new(TextNlpModule));
-    orchestrator.add_module(Box::new(ImageModule));
+    // --- Pipeline déclaratif : fichier en argument ou AI_POPULATE_CONFIG ---
+    let config_path = env::args_os()
+        .nth(1)
+        .or_else(|| env::var_os("AI_POPULATE_CONFIG"));
+    let config = match config_path {
+        Some(path) => match PipelineConfig::load(Path::new(&path)) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Erreur de configuration : {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => PipelineConfig::default(),
+    };
+    let mut plugins = PluginHost::new();
+    let mut orchestrator = match config.build(&mut plugins) {
+        Ok(orchestrator) => orchestrator,
+        Err(e) => {
+            eprintln!("Erreur de configuration : {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let text_params = match config.text_nlp_params() {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Erreur de configuration : {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // --- Plugins chargés à chaud (AI_POPULATE_PLUGINS : chemins séparés par ':') ---
+    if let Some(paths) = env::var_os("AI_POPULATE_PLUGINS") {
+        for path in env::split_paths(&paths) {
+            if let Err(e) = plugins.load(&mut orchestrator, &path) {
+                eprintln!("Plugin ignoré : {e}");
+            }
+        }
+        println!("Plugins chargés   : {}", plugins.names().join(", "));
+    }
 
     // --- Pipeline texte ---
     let packet_text = DataPacket {
         modality: "text".into(),
-        payload: Box::new(text.clone()),
+        payload: text.clone().into(),
         meta: Default::default(),
+        trace: Default::default(),
+    };
+    let output_text = match orchestrator.run(packet_text) {
+        Ok(output) => output,
+        Err(errors) => {
+            report_pipeline_errors(&errors);
+            return ExitCode::FAILURE;
+        }
     };
-    let result_text = orchestrator.run(packet_text);
 
-    // Récupération du résultat Encoded
-    if let Some(enc) = (result_text.payload.as_ref() as &dyn any::Any).downcast_ref::<Encoded>() {
-        println!("Texte original    : {:?}", enc.decode_utf8());
-        println!("Bytes (len={})     : {:?}", enc.bytes.len(), enc.bytes);
-        print_metas(enc);
-        // Affichage des overrides et associations
-        let reconstructed = enc.decode_utf8();
-        println!("\nReconstruction identique ? {}", reconstructed == text);
-        // Pour print_metas_overrides, on doit régénérer la table ov (ici simplifié)
-        let ov = override_chars_table(&mut enc.clone(), &['Ç', 'À', 'É'], "LetterAccented");
-        print_metas_overrides(enc, &ov);
-    } else {
-        println!("Erreur : le module n'a pas renvoyé un Encoded valide.");
+    println!("Modules exécutés  : {}", output_text.executed.join(", "));
+    let mut slots: Vec<&String> = output_text.packets.keys().collect();
+    slots.sort();
+    for slot in slots
+        .into_iter()
+        .filter(|s| *s != "text" && *s != "encoded")
+    {
+        println!(
+            "Donnée '{}'       : {}",
+            slot, output_text.packets[slot].payload
+        );
     }
 
-    // --- Pipeline image PNG ---
-    let packet_png = DataPacket {
-        modality: "image".into(),
-        payload: Box::new(png_bytes.clone()),
-        meta: Default::default(),
+    // Récupération du résultat Encoded
+    let Some(result_text) = output_text.get("encoded") else {
+        return ExitCode::SUCCESS;
     };
-    let result_png = orchestrator.run(packet_png);
-    if let Some(pixels) = (result_png.payload.as_ref() as &dyn any::Any).downcast_ref::<Vec<u8>>() {
-        println!(
-            "\n[Image PNG] Format: {} | {}x{} | Color: {} | Pixels: {} octets",
-            result_png
-                .meta
-                .get("image_format")
-                .unwrap_or(&"?".to_string()),
-            result_png.meta.get("width").unwrap_or(&"?".to_string()),
-            result_png.meta.get("height").unwrap_or(&"?".to_string()),
-            result_png
-                .meta
-                .get("color_type")
-                .unwrap_or(&"?".to_string()),
-            pixels.len()
-        );
+    println!("Payload           : {}", result_text.payload);
+    match result_text.payload.get::<Encoded>() {
+        Ok(enc) => {
+            println!("Texte original    : {:?}", enc.decode_utf8());
+            println!("Bytes (len={})     : {:?}", enc.bytes.len(), enc.bytes);
+            print_metas(enc);
+            print_graphemes(enc);
+            print_tokens(enc);
+            // Affichage des overrides et associations
+            let reconstructed = enc.decode_utf8();
+            println!("\nReconstruction identique ? {}", reconstructed == text);
+            // Pour print_metas_overrides, on régénère la table ov depuis la configuration
+            let mut scratch = enc.clone();
+            let mut ov = HashMap::new();
+            for table in text_params.overrides.iter().filter(|t| !t.first_only) {
+                ov.extend(override_chars_table(
+                    &mut scratch,
+                    &table.chars,
+                    &table.category,
+                ));
+            }
+            print_metas_overrides(enc, &ov);
+        }
+        Err(e) => println!("Erreur : le module n'a pas renvoyé un Encoded valide ({e})."),
     }
 
-    // --- Pipeline image JPEG ---
-    let packet_jpeg = DataPacket {
-        modality: "image".into(),
-        payload: Box::new(jpeg_bytes.clone()),
+    // Le même texte en NFD (diacritiques décomposés) se catégorise comme en NFC
+    let packet_nfd = DataPacket {
+        modality: "text".into(),
+        payload: text.nfd().collect::<String>().into(),
         meta: Default::default(),
+        trace: Default::default(),
     };
-    let result_jpeg = orchestrator.run(packet_jpeg);
-    if let Some(pixels) = (result_jpeg.payload.as_ref() as &dyn any::Any).downcast_ref::<Vec<u8>>()
-    {
+    if let Ok(output_nfd) = orchestrator.run(packet_nfd) {
+        let categories = |packet: Option<&DataPacket>| {
+            packet
+                .and_then(|p| p.payload.get::<Encoded>().ok())
+                .map(|e| {
+                    let names = |ids: Vec<usize>| -> Vec<String> {
+                        ids.iter()
+                            .map(|&id| e.registry.name(id).to_string())
+                            .collect()
+                    };
+                    (
+                        names(e.graphemes.iter().map(|g| g.cat_id).collect()),
+                        names(e.tokens().iter().map(|t| t.cat_id).collect()),
+                    )
+                })
+        };
         println!(
-            "\n[Image JPEG] Format: {} | {}x{} | Color: {} | Pixels: {} octets",
-            result_jpeg
-                .meta
-                .get("image_format")
-                .unwrap_or(&"?".to_string()),
-            result_jpeg.meta.get("width").unwrap_or(&"?".to_string()),
-            result_jpeg.meta.get("height").unwrap_or(&"?".to_string()),
-            result_jpeg
-                .meta
-                .get("color_type")
-                .unwrap_or(&"?".to_string()),
-            pixels.len()
+            "Catégories NFD identiques à NFC ? {}",
+            categories(output_nfd.get("encoded")) == categories(Some(result_text))
         );
     }
+
+    // Le paquet est persistable tel quel (JSON)
+    match result_text.to_json() {
+        Ok(json) => {
+            let restored = DataPacket::from_json(&json)
+                .map(|p| p.payload.get::<Encoded>().map(|e| e.decode_utf8()) == Ok(text.clone()));
+            println!(
+                "\nJSON : {} octets | Relecture identique ? {}",
+                json.len(),
+                restored.unwrap_or(false)
+            );
+        }
+        Err(e) => println!("\nErreur de sérialisation : {e}"),
+    }
+
+    // Provenance : chaque transformation est attribuée à son module
+    let producers: Vec<&str> = result_text
+        .trace
+        .touching("reconstructed")
+        .iter()
+        .map(|e| e.module.as_str())
+        .collect();
+    println!("'reconstructed' produit par : {}", producers.join(", "));
+    match output_text.trace().to_json() {
+        Ok(json) => println!("Trace :\n{json}"),
+        Err(e) => println!("Erreur de sérialisation de la trace : {e}"),
+    }
+
+    // --- Pipelines image (PNG puis JPEG) ---
+    let mut status = ExitCode::SUCCESS;
+    for (label, bytes) in [("PNG", png_bytes), ("JPEG", jpeg_bytes)] {
+        reload_plugins(&mut plugins, &mut orchestrator);
+        if let Err(errors) = run_image(&orchestrator, label, bytes) {
+            report_pipeline_errors(&errors);
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    plugins.unload_all(&mut orchestrator);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn associate(text: &str) -> Encoded {
+        let mut enc = encode(text, CategoryRegistry::default());
+        let rule = AssociationRule {
+            max_distance: 0,
+            ..AssociationRule::default()
+        };
+        let (norm_to_group, group_id_to_type) = build_auto_associations(&enc, &rule);
+        apply_associations(&mut enc, &norm_to_group, &group_id_to_type, &rule);
+        enc
+    }
+
+    fn grapheme_groups(enc: &Encoded) -> Vec<Option<usize>> {
+        enc.graphemes
+            .iter()
+            .map(|g| enc.metas[g.meta_span.start].association_id)
+            .collect()
+    }
+
+    #[test]
+    fn nfd_text_is_associated_like_its_nfc_form() {
+        let nfc = associate("Été e");
+        let nfd = associate("E\u{301}te\u{301} e");
+        nfd.check().unwrap();
+        // e, t, espace : l'accent combinant ne forme pas de groupe à part
+        assert_eq!(nfd.associations.len(), 3);
+        assert_eq!(grapheme_groups(&nfd), grapheme_groups(&nfc));
+        // ‘E’ + U+0301 : les deux points de code dans le groupe de ‘e’
+        let e = nfd.metas[6].association_id;
+        assert_eq!(nfd.metas[0].association_id, e);
+        assert_eq!(nfd.metas[1].association_id, e);
+        assert_eq!(nfd.associations[&e.unwrap()].1, [0, 1, 3, 4, 6]);
+        assert!(nfd.metas.iter().all(|m| m.association_id.is_some()));
+    }
 }
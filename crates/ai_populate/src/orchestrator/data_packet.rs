@@ -2,20 +2,96 @@
 
 use std::collections::HashMap;
 
-use crate::orchestrator::any_clone::AnyClone;
+use serde::{de, Deserialize, Serialize};
 
+use crate::orchestrator::{Payload, Trace};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataPacket {
     pub modality: String,              // ex: "text", "image", "audio"
-    pub payload: Box<dyn AnyClone>,    // contenu typé dynamiquement et clonable
+    pub payload: Payload,              // contenu typé (registre de payloads)
     pub meta: HashMap<String, String>, // métadonnées (optionnel)
+    /// Provenance : renseignée par l'orchestrateur après chaque module
+    #[serde(default)]
+    pub trace: Trace,
 }
 
-impl Clone for DataPacket {
-    fn clone(&self) -> Self {
+impl DataPacket {
+    /// Sérialisation pour journaliser ou persister un paquet
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Relecture d'un paquet ; un payload incohérent est une erreur de
+    /// données, comme un JSON mal formé
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let packet: Self = serde_json::from_str(json)?;
+        packet.payload.check().map_err(de::Error::custom)?;
+        Ok(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::category_registry::CategoryRegistry;
+    use crate::encoded::{encode, Encoded};
+    use crate::orchestrator::{payload_digest, Audio, Pixels};
+
+    fn packet(payload: Payload) -> DataPacket {
         DataPacket {
-            modality: self.modality.clone(),
-            payload: self.payload.clone(),
-            meta: self.meta.clone(),
+            modality: "text".to_string(),
+            payload,
+            meta: HashMap::from([("source".to_string(), "test".to_string())]),
+            trace: Trace::default(),
         }
     }
+
+    #[test]
+    fn every_payload_kind_round_trips_through_json() {
+        let payloads = [
+            Payload::Text("Pluton".to_string()),
+            Payload::Bytes(vec![0, 1, 255]),
+            Payload::Encoded(encode("Éa\u{301} !", CategoryRegistry::default())),
+            Payload::Pixels(Pixels {
+                width: 1,
+                height: 2,
+                color: "Rgb8".to_string(),
+                data: vec![1, 2, 3, 4, 5, 6],
+            }),
+            Payload::Audio(Audio {
+                sample_rate: 8000,
+                channels: 1,
+                samples: vec![0.0, -0.5, 0.25],
+            }),
+        ];
+        for payload in payloads {
+            let original = packet(payload);
+            let restored = DataPacket::from_json(&original.to_json().unwrap()).unwrap();
+            assert_eq!(restored.payload.kind(), original.payload.kind());
+            assert_eq!(
+                payload_digest(&restored.payload),
+                payload_digest(&original.payload)
+            );
+            assert_eq!(restored.meta, original.meta);
+        }
+    }
+
+    #[test]
+    fn from_json_rejects_a_corrupt_encoding() {
+        let mut encoded = encode("abc", CategoryRegistry::default());
+        encoded.metas[2].byte_span = 2..9;
+        let json = packet(Payload::Encoded(encoded)).to_json().unwrap();
+        let error = DataPacket::from_json(&json).unwrap_err();
+        assert!(error.to_string().contains("incohérent"), "{error}");
+
+        let restored = DataPacket::from_json(
+            &packet(Payload::Encoded(encode("abc", CategoryRegistry::default())))
+                .to_json()
+                .unwrap(),
+        )
+        .unwrap();
+        let encoded: &Encoded = restored.payload.get().unwrap();
+        assert_eq!(encoded.decode_utf8(), "abc");
+    }
 }
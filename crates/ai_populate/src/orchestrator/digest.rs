@@ -0,0 +1,117 @@
+//! Empreinte stable d'un payload (FNV-1a 64 bits) : deux étapes qui
+//! produisent la même empreinte ont produit le même contenu
+
+use std::hash::Hasher;
+
+use crate::orchestrator::Payload;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Empreinte hexadécimale du contenu, catégories et associations comprises
+/// pour un `Encoded` (un changement de catégorisation change l'empreinte)
+pub fn payload_digest(payload: &Payload) -> String {
+    let mut h = Fnv(FNV_OFFSET);
+    h.write(payload.kind().to_string().as_bytes());
+    match payload {
+        Payload::Text(s) => h.write(s.as_bytes()),
+        Payload::Bytes(b) => h.write(b),
+        Payload::Encoded(e) => {
+            h.write(&e.bytes);
+            for m in &e.metas {
+                h.write(e.registry.name(m.cat_id).as_bytes());
+                h.write_u8(m.flags);
+                h.write_u64(m.association_id.map_or(u64::MAX, |a| a as u64));
+            }
+            for g in &e.graphemes {
+                h.write_u64(g.meta_span.end as u64);
+                h.write(e.registry.name(g.cat_id).as_bytes());
+                h.write_u8(g.flags);
+            }
+            let mut groups: Vec<_> = e.associations.iter().collect();
+            groups.sort_by_key(|(id, _)| **id);
+            for (id, (kind, members)) in groups {
+                h.write_u64(*id as u64);
+                h.write(kind.as_bytes());
+                for &i in members {
+                    h.write_u64(i as u64);
+                }
+            }
+        }
+        Payload::Pixels(p) => {
+            h.write_u32(p.width);
+            h.write_u32(p.height);
+            h.write(p.color.as_bytes());
+            h.write(&p.data);
+        }
+        Payload::Audio(a) => {
+            h.write_u32(a.sample_rate);
+            h.write_u16(a.channels);
+            for s in &a.samples {
+                h.write_u32(s.to_bits());
+            }
+        }
+    }
+    format!("{:016x}", h.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::category_registry::CategoryRegistry;
+    use crate::encoded::encode;
+
+    #[test]
+    fn digest_is_stable_and_content_sensitive() {
+        // FNV-1a figé : une empreinte enregistrée reste comparable
+        assert_eq!(
+            payload_digest(&Payload::Text("Pluton".to_string())),
+            "e090a367f871da9e"
+        );
+        let text = |s: &str| payload_digest(&Payload::Text(s.to_string()));
+        assert_eq!(text("Pluton"), text("Pluton"));
+        assert_ne!(text("Pluton"), text("pluton"));
+        assert_ne!(
+            payload_digest(&Payload::Bytes(b"Pluton".to_vec())),
+            text("Pluton")
+        );
+    }
+
+    #[test]
+    fn encoded_digest_ignores_association_order_but_not_categories() {
+        let base = encode("Pluton Charon", CategoryRegistry::default());
+        let groups = [(0, vec![0, 1]), (1, vec![7, 8])];
+        let (mut forward, mut backward) = (base.clone(), base.clone());
+        for (id, members) in groups.iter().cloned() {
+            forward
+                .associations
+                .insert(id, ("mot".to_string(), members));
+        }
+        for (id, members) in groups.iter().rev().cloned() {
+            backward
+                .associations
+                .insert(id, ("mot".to_string(), members));
+        }
+        let digest = |e| payload_digest(&Payload::Encoded(e));
+        assert_eq!(digest(forward.clone()), digest(backward));
+
+        let mut recategorized = forward.clone();
+        recategorized.set_grapheme_category(0, 4);
+        assert_ne!(digest(recategorized), digest(forward));
+    }
+}
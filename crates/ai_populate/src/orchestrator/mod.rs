@@ -1,9 +1,34 @@
+pub mod audio;
 pub mod data_packet;
+pub mod digest;
 pub mod module;
-pub mod any_clone;
+pub mod module_error;
+#[allow(clippy::module_inception)]
 pub mod orchestrator;
+pub mod payload;
+pub mod payload_error;
+pub mod payload_kind;
+pub mod pipeline;
+pub mod pipeline_error;
+pub mod pipeline_output;
+pub mod pixels;
+pub mod port;
+pub mod trace;
+pub mod trace_entry;
 
+pub use audio::Audio;
 pub use data_packet::DataPacket;
+pub use digest::payload_digest;
 pub use module::Module;
-pub use any_clone::AnyClone;
-pub use orchestrator::Orchestrator;
\ No newline at end of file
+pub use module_error::{ModuleError, ModuleResult};
+pub use orchestrator::Orchestrator;
+pub use payload::{Payload, PayloadType};
+pub use payload_error::{PayloadError, PayloadResult};
+pub use payload_kind::PayloadKind;
+pub use pipeline::Pipeline;
+pub use pipeline_error::PipelineError;
+pub use pipeline_output::PipelineOutput;
+pub use pixels::Pixels;
+pub use port::Port;
+pub use trace::Trace;
+pub use trace_entry::TraceEntry;
@@ -1,15 +1,35 @@
 //! Définition du trait commun à tous les modules de traitement IA
-use crate::orchestrator::data_packet::DataPacket;
+use crate::orchestrator::{DataPacket, ModuleError, ModuleResult, Port};
 
-pub trait Module: Send {
+/// Un module déclare ce qu'il consomme (`inputs`) et ce qu'il produit
+/// (`output`) : l'orchestrateur en déduit l'ordre d'exécution, sans
+/// dépendre de l'ordre d'ajout. `Sync` : des branches indépendantes
+/// s'exécutent en parallèle.
+pub trait Module: Send + Sync {
     /// Nom unique du module
     fn name(&self) -> &str;
     /// Modalité prise en charge (ex: "text", "image", ...)
     fn modality(&self) -> &str;
-    /// Traitement principal
-    fn process(&self, input: &DataPacket) -> DataPacket;
-    /// Fallback optionnel en cas d'échec ou d'incompréhension
-    fn fallback(&self, input: &DataPacket) -> Option<DataPacket> {
+    /// Données consommées (au moins une), la première étant l'entrée
+    /// principale
+    fn inputs(&self) -> Vec<Port>;
+    /// Donnée produite
+    fn output(&self) -> Port;
+    /// Modules (par nom) à exécuter avant celui-ci, en plus des dépendances
+    /// de données
+    fn depends_on(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Traitement principal ; une erreur déclenche le fallback
+    fn process(&self, input: &DataPacket) -> ModuleResult;
+    /// Traitement avec toutes les entrées, dans l'ordre de `inputs`
+    /// (par défaut : `process` sur l'entrée principale)
+    fn process_all(&self, inputs: &[&DataPacket]) -> ModuleResult {
+        self.process(inputs[0])
+    }
+    /// Fallback propre au module en cas d'échec ou d'incompréhension, tenté
+    /// avant les modules de fallback enregistrés dans l'orchestrateur
+    fn fallback(&self, _input: &DataPacket, _error: &ModuleError) -> Option<DataPacket> {
         None
     }
 }
@@ -0,0 +1,40 @@
+//! Échec d'un module : entrée incomprise ou traitement en erreur
+
+use std::fmt;
+
+use crate::orchestrator::DataPacket;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleError {
+    /// L'entrée n'est pas dans un format que le module sait traiter
+    NotUnderstood { reason: String },
+    /// L'entrée est comprise mais le traitement a échoué
+    Failed { reason: String },
+}
+
+impl ModuleError {
+    pub fn not_understood(reason: impl Into<String>) -> Self {
+        ModuleError::NotUnderstood {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn failed(reason: impl Into<String>) -> Self {
+        ModuleError::Failed {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::NotUnderstood { reason } => write!(f, "non compris : {reason}"),
+            ModuleError::Failed { reason } => write!(f, "échec : {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
+
+pub type ModuleResult = Result<DataPacket, ModuleError>;
@@ -1,48 +1,948 @@
 //! Orchestrateur principal : gestion du pipeline de modules IA
-use crate::orchestrator::{data_packet::DataPacket, module::Module};
+//!
+//! Les modules forment un graphe orienté acyclique : un module dépend du
+//! producteur de chacune de ses entrées et des modules cités par
+//! `depends_on`. Le graphe est validé avant toute exécution (cycles,
+//! entrées non satisfaites, types incompatibles) puis exécuté niveau par
+//! niveau, les branches indépendantes en parallèle.
+//!
+//! En cas d'erreur d'un module, `run_with_fallback` tente son fallback
+//! propre puis, dans l'ordre, les modules de fallback enregistrés pour lui ;
+//! le chemin suivi est noté dans les métadonnées du paquet produit
+//! (`<module>.path`, `<module>.errors`).
+//!
+//! Chaque paquet produit porte la trace de provenance de ses entrées,
+//! complétée d'une étape pour le module qui l'a produit.
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Instant;
+
+use crate::orchestrator::{
+    payload_digest, DataPacket, Module, ModuleError, PayloadKind, Pipeline, PipelineError,
+    PipelineOutput, Port, Trace, TraceEntry,
+};
+
+/// Nom donné au paquet d'entrée dans les erreurs
+const SOURCE: &str = "<source>";
 
 pub struct Orchestrator {
     modules: Vec<Box<dyn Module>>,
+    /// Modules de remplacement par module, tentés dans l'ordre
+    fallbacks: HashMap<String, Vec<Box<dyn Module>>>,
 }
 
 impl Orchestrator {
     pub fn new() -> Self {
         Self {
             modules: Vec::new(),
+            fallbacks: HashMap::new(),
         }
     }
 
-    /// Ajoute un module au pipeline
+    /// Ajoute un module au pipeline (l'ordre d'ajout est sans importance)
     pub fn add_module(&mut self, module: Box<dyn Module>) {
         self.modules.push(module);
     }
 
-    /// Exécute le pipeline sur un DataPacket
-    pub fn run(&self, mut packet: DataPacket) -> DataPacket {
-        for module in &self.modules {
-            if module.modality() == packet.modality {
-                let result = module.process(&packet);
-                packet = result;
+    /// Retire un module (ses fallbacks restent enregistrés, pour une
+    /// nouvelle version du module)
+    pub fn remove_module(&mut self, name: &str) -> Option<Box<dyn Module>> {
+        let i = self.index_of(name)?;
+        Some(self.modules.remove(i))
+    }
+
+    /// Remplace le module de même nom, ou l'ajoute ; renvoie l'ancien
+    pub fn replace_module(&mut self, module: Box<dyn Module>) -> Option<Box<dyn Module>> {
+        match self.index_of(module.name()) {
+            Some(i) => Some(std::mem::replace(&mut self.modules[i], module)),
+            None => {
+                self.modules.push(module);
+                None
             }
         }
-        packet
     }
 
-    /// Exécute le pipeline avec gestion de fallback
-    pub fn run_with_fallback(&self, mut packet: DataPacket) -> DataPacket {
-        for module in &self.modules {
-            if module.modality() == packet.modality {
-                let result = module.process(&packet);
-                // Si le module ne modifie pas le packet, tente fallback
-                if std::ptr::eq(&result, &packet) {
-                    if let Some(fb) = module.fallback(&packet) {
-                        packet = fb;
+    /// Ajoute un module de fallback à la fin de la chaîne de `module` : il
+    /// reçoit les mêmes données et doit produire le même type
+    pub fn add_fallback(&mut self, module: &str, fallback: Box<dyn Module>) {
+        self.fallbacks
+            .entry(module.to_string())
+            .or_default()
+            .push(fallback);
+    }
+
+    pub fn has_module(&self, name: &str) -> bool {
+        self.index_of(name).is_some()
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name() == name)
+    }
+
+    /// Producteur de chaque donnée
+    fn producers(&self) -> HashMap<String, usize> {
+        self.modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.output().slot, i))
+            .collect()
+    }
+
+    /// Prédécesseurs de chaque module : producteurs de ses entrées et
+    /// dépendances explicites
+    fn predecessors(&self) -> Vec<Vec<usize>> {
+        let producers = self.producers();
+        self.modules
+            .iter()
+            .map(|m| {
+                let mut preds: Vec<usize> = m
+                    .inputs()
+                    .iter()
+                    .filter_map(|p| producers.get(&p.slot).copied())
+                    .chain(m.depends_on().iter().filter_map(|d| self.index_of(d)))
+                    .collect();
+                preds.sort_unstable();
+                preds.dedup();
+                preds
+            })
+            .collect()
+    }
+
+    /// Vérifications indépendantes de l'entrée : noms uniques, au moins une
+    /// entrée par module, dépendances connues, un seul producteur par
+    /// donnée, absence de cycle
+    pub fn validate(&self) -> Result<(), Vec<PipelineError>> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        for m in &self.modules {
+            if !names.insert(m.name()) {
+                errors.push(PipelineError::DuplicateModule {
+                    name: m.name().to_string(),
+                });
+            }
+        }
+        let mut targets: Vec<&String> = self.fallbacks.keys().collect();
+        targets.sort();
+        let fallbacks = targets.iter().flat_map(|t| &self.fallbacks[*t]);
+        for m in self.modules.iter().chain(fallbacks) {
+            if m.inputs().is_empty() {
+                errors.push(PipelineError::NoInput {
+                    module: m.name().to_string(),
+                });
+            }
+        }
+        for m in &self.modules {
+            for dep in m.depends_on() {
+                if self.index_of(&dep).is_none() {
+                    errors.push(PipelineError::UnknownDependency {
+                        module: m.name().to_string(),
+                        dependency: dep,
+                    });
+                }
+            }
+        }
+        let mut by_slot: HashMap<String, Vec<String>> = HashMap::new();
+        for m in &self.modules {
+            by_slot
+                .entry(m.output().slot)
+                .or_default()
+                .push(m.name().to_string());
+        }
+        let mut duplicated: Vec<(String, Vec<String>)> = by_slot
+            .into_iter()
+            .filter(|(_, producers)| producers.len() > 1)
+            .collect();
+        duplicated.sort();
+        for (slot, producers) in duplicated {
+            errors.push(PipelineError::DuplicateProducer { slot, producers });
+        }
+        for target in targets {
+            let chain = &self.fallbacks[target];
+            let Some(i) = self.index_of(target) else {
+                for fb in chain {
+                    errors.push(PipelineError::UnknownFallbackTarget {
+                        module: target.clone(),
+                        fallback: fb.name().to_string(),
+                    });
+                }
+                continue;
+            };
+            let primary = &self.modules[i];
+            for fb in chain {
+                errors.extend(fallback_mismatches(primary.as_ref(), fb.as_ref()));
+            }
+        }
+        if let Some(cycle) = self.find_cycle() {
+            errors.push(PipelineError::Cycle {
+                modules: cycle
+                    .into_iter()
+                    .map(|i| self.modules[i].name().to_string())
+                    .collect(),
+            });
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parcours en profondeur : renvoie le premier cycle rencontré
+    fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Open,
+            Done,
+        }
+        fn visit(
+            i: usize,
+            preds: &[Vec<usize>],
+            marks: &mut [Mark],
+            path: &mut Vec<usize>,
+        ) -> Option<Vec<usize>> {
+            marks[i] = Mark::Open;
+            path.push(i);
+            for &p in &preds[i] {
+                match marks[p] {
+                    Mark::Open => {
+                        // le chemin remonte les dépendances : on le remet dans
+                        // le sens d'exécution
+                        let start = path.iter().position(|&j| j == p).unwrap();
+                        let mut cycle = path[start..].to_vec();
+                        cycle.reverse();
+                        return Some(cycle);
+                    }
+                    Mark::New => {
+                        if let Some(cycle) = visit(p, preds, marks, path) {
+                            return Some(cycle);
+                        }
+                    }
+                    Mark::Done => {}
+                }
+            }
+            path.pop();
+            marks[i] = Mark::Done;
+            None
+        }
+
+        let preds = self.predecessors();
+        let mut marks = vec![Mark::New; self.modules.len()];
+        for i in 0..self.modules.len() {
+            if marks[i] == Mark::New {
+                if let Some(cycle) = visit(i, &preds, &mut marks, &mut Vec::new()) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    /// Construit le plan d'exécution pour une entrée donnée.
+    ///
+    /// Un module est actif dès que toutes ses entrées sont disponibles
+    /// (l'entrée elle-même ou la sortie d'un module actif). Un module inactif
+    /// dont une partie des entrées est disponible, ou dont la modalité est
+    /// celle de l'entrée, est une erreur ; les autres concernent une autre
+    /// modalité et sont ignorés.
+    pub fn plan(&self, source: &Port) -> Result<Pipeline, Vec<PipelineError>> {
+        self.validate()?;
+        let producers = self.producers();
+        let mut errors = Vec::new();
+        if let Some(&i) = producers.get(&source.slot) {
+            errors.push(PipelineError::DuplicateProducer {
+                slot: source.slot.clone(),
+                producers: vec![SOURCE.to_string(), self.modules[i].name().to_string()],
+            });
+        }
+
+        // modules actifs, jusqu'au point fixe
+        let mut available: HashSet<String> = HashSet::from([source.slot.clone()]);
+        let mut active = vec![false; self.modules.len()];
+        loop {
+            let mut changed = false;
+            for (i, m) in self.modules.iter().enumerate() {
+                if !active[i] && m.inputs().iter().all(|p| available.contains(&p.slot)) {
+                    active[i] = true;
+                    available.insert(m.output().slot);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        for (i, m) in self.modules.iter().enumerate() {
+            let inputs = m.inputs();
+            if !active[i] {
+                if m.modality() == source.slot || inputs.iter().any(|p| available.contains(&p.slot))
+                {
+                    for p in inputs.iter().filter(|p| !available.contains(&p.slot)) {
+                        errors.push(PipelineError::UnsatisfiedInput {
+                            module: m.name().to_string(),
+                            slot: p.slot.clone(),
+                        });
+                    }
+                }
+                continue;
+            }
+            for p in &inputs {
+                let (producer, found) = match producers.get(&p.slot) {
+                    Some(&j) => (self.modules[j].name().to_string(), self.modules[j].output()),
+                    None => (SOURCE.to_string(), source.clone()),
+                };
+                if found.kind != p.kind {
+                    errors.push(PipelineError::TypeMismatch {
+                        slot: p.slot.clone(),
+                        producer,
+                        consumer: m.name().to_string(),
+                        expected: p.kind,
+                        found: found.kind,
+                    });
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // niveau = longueur du plus long chemin depuis l'entrée (graphe acyclique)
+        let preds = self.predecessors();
+        let mut level: Vec<Option<usize>> = vec![None; self.modules.len()];
+        let mut remaining: Vec<usize> = (0..self.modules.len()).filter(|&i| active[i]).collect();
+        while !remaining.is_empty() {
+            remaining.retain(|&i| {
+                let mut lv = 0;
+                for &p in preds[i].iter().filter(|&&p| active[p]) {
+                    match level[p] {
+                        Some(l) => lv = lv.max(l + 1),
+                        None => return true,
+                    }
+                }
+                level[i] = Some(lv);
+                false
+            });
+        }
+        let depth = level.iter().flatten().map(|l| l + 1).max().unwrap_or(0);
+        let mut levels = vec![Vec::new(); depth];
+        for (i, l) in level.iter().enumerate() {
+            if let Some(l) = l {
+                levels[*l].push(i);
+            }
+        }
+        Ok(Pipeline { levels })
+    }
+
+    /// Exécute le pipeline sur un DataPacket ; l'entrée est la donnée nommée
+    /// d'après sa modalité. Sans fallback, l'erreur d'un module arrête
+    /// l'exécution après son niveau.
+    pub fn run(&self, packet: DataPacket) -> Result<PipelineOutput, Vec<PipelineError>> {
+        self.execute(packet, false)
+    }
+
+    /// Exécute le pipeline avec gestion de fallback : l'exécution ne
+    /// s'arrête que si toute la chaîne d'un module échoue
+    pub fn run_with_fallback(
+        &self,
+        packet: DataPacket,
+    ) -> Result<PipelineOutput, Vec<PipelineError>> {
+        self.execute(packet, true)
+    }
+
+    fn execute(
+        &self,
+        packet: DataPacket,
+        with_fallback: bool,
+    ) -> Result<PipelineOutput, Vec<PipelineError>> {
+        let source = Port::of_payload(&packet.modality, &packet.payload);
+        let pipeline = self.plan(&source)?;
+        let mut packets = HashMap::from([(source.slot, packet)]);
+        let mut executed = Vec::new();
+        for level in &pipeline.levels {
+            let results: Vec<Result<DataPacket, PipelineError>> = if level.len() == 1 {
+                let i = level[0];
+                let invoke = AssertUnwindSafe(|| self.invoke(i, &packets, with_fallback));
+                vec![panic::catch_unwind(invoke).unwrap_or_else(|p| Err(self.panicked(i, p)))]
+            } else {
+                let packets = &packets;
+                thread::scope(|s| {
+                    let handles: Vec<_> = level
+                        .iter()
+                        .map(|&i| s.spawn(move || self.invoke(i, packets, with_fallback)))
+                        .collect();
+                    level
+                        .iter()
+                        .zip(handles)
+                        .map(|(&i, h)| h.join().unwrap_or_else(|p| Err(self.panicked(i, p))))
+                        .collect()
+                })
+            };
+            let mut errors = Vec::new();
+            for (&i, result) in level.iter().zip(results) {
+                let m = &self.modules[i];
+                let result = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        errors.push(e);
                         continue;
                     }
+                };
+                let output = m.output();
+                if !output.accepts(&result.payload) {
+                    errors.push(PipelineError::UnexpectedPayload {
+                        module: m.name().to_string(),
+                        slot: output.slot.clone(),
+                        expected: output.kind,
+                    });
+                }
+                executed.push(m.name().to_string());
+                packets.insert(output.slot, result);
+            }
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+        Ok(PipelineOutput { packets, executed })
+    }
+
+    /// Une panique est un échec du module, sans fallback : l'état du module
+    /// n'est plus sûr
+    fn panicked(&self, i: usize, panic: Box<dyn Any + Send>) -> PipelineError {
+        let reason = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        let name = self.modules[i].name().to_string();
+        PipelineError::ModuleFailed {
+            module: name.clone(),
+            attempts: vec![(name, ModuleError::failed(format!("panique : {reason}")))],
+        }
+    }
+
+    fn invoke(
+        &self,
+        i: usize,
+        packets: &HashMap<String, DataPacket>,
+        with_fallback: bool,
+    ) -> Result<DataPacket, PipelineError> {
+        let module = &self.modules[i];
+        let inputs_of = |m: &dyn Module| -> Vec<&DataPacket> {
+            m.inputs().iter().map(|p| &packets[&p.slot]).collect()
+        };
+        let inputs = inputs_of(module.as_ref());
+        let started = Instant::now();
+        let mut attempts: Vec<(String, ModuleError)> = Vec::new();
+        let mut outcome = match module.process_all(&inputs) {
+            Ok(packet) => Some((packet, "process".to_string())),
+            Err(e) => {
+                attempts.push((module.name().to_string(), e));
+                None
+            }
+        };
+        if with_fallback && outcome.is_none() {
+            let (_, error) = &attempts[0];
+            outcome = module
+                .fallback(inputs[0], error)
+                .map(|packet| (packet, "fallback".to_string()));
+            for fb in self.fallbacks.get(module.name()).into_iter().flatten() {
+                if outcome.is_some() {
+                    break;
+                }
+                match fb.process_all(&inputs_of(fb.as_ref())) {
+                    Ok(packet) => outcome = Some((packet, format!("fallback:{}", fb.name()))),
+                    Err(e) => attempts.push((fb.name().to_string(), e)),
                 }
-                packet = result;
             }
         }
+        let Some((mut packet, path)) = outcome else {
+            return Err(PipelineError::ModuleFailed {
+                module: module.name().to_string(),
+                attempts,
+            });
+        };
         packet
+            .meta
+            .insert(format!("{}.path", module.name()), path.clone());
+        if !attempts.is_empty() {
+            let errors: Vec<String> = attempts
+                .iter()
+                .map(|(name, e)| format!("{name} : {e}"))
+                .collect();
+            packet
+                .meta
+                .insert(format!("{}.errors", module.name()), errors.join(" ; "));
+        }
+        let primary = inputs[0];
+        let changed = |k: &&String| primary.meta.get(*k).is_some_and(|v| v != &packet.meta[*k]);
+        let entry = TraceEntry {
+            module: module.name().to_string(),
+            path,
+            modality_in: primary.modality.clone(),
+            modality_out: packet.modality.clone(),
+            duration_us: started.elapsed().as_micros() as u64,
+            meta_added: sorted(
+                packet
+                    .meta
+                    .keys()
+                    .filter(|k| !primary.meta.contains_key(*k)),
+            ),
+            meta_changed: sorted(packet.meta.keys().filter(changed)),
+            meta_removed: sorted(
+                primary
+                    .meta
+                    .keys()
+                    .filter(|k| !packet.meta.contains_key(*k)),
+            ),
+            payload_kind: packet.payload.kind(),
+            payload_digest: payload_digest(&packet.payload),
+        };
+        // provenance : traces des entrées (ancêtres communs une seule fois)
+        packet.trace = Trace::default();
+        for input in &inputs {
+            packet.trace.merge(&input.trace);
+        }
+        packet.trace.push(entry);
+        Ok(packet)
+    }
+}
+
+fn sorted<'a>(keys: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut keys: Vec<String> = keys.cloned().collect();
+    keys.sort();
+    keys
+}
+
+/// Un fallback doit produire le type du module et ne consommer que des
+/// données que le module reçoit, avec le même type
+fn fallback_mismatches(primary: &dyn Module, fallback: &dyn Module) -> Vec<PipelineError> {
+    let mismatch = |slot: &str, expected: Option<PayloadKind>, found: PayloadKind| {
+        PipelineError::FallbackMismatch {
+            module: primary.name().to_string(),
+            fallback: fallback.name().to_string(),
+            slot: slot.to_string(),
+            expected,
+            found,
+        }
+    };
+    let mut errors = Vec::new();
+    let (expected, found) = (primary.output(), fallback.output());
+    if expected.kind != found.kind {
+        errors.push(mismatch(&found.slot, Some(expected.kind), found.kind));
+    }
+    let inputs = primary.inputs();
+    for p in fallback.inputs() {
+        match inputs.iter().find(|q| q.slot == p.slot) {
+            Some(q) if q.kind == p.kind => {}
+            Some(q) => errors.push(mismatch(&p.slot, Some(q.kind), p.kind)),
+            None => errors.push(mismatch(&p.slot, None, p.kind)),
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::orchestrator::{ModuleResult, Payload};
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mode {
+        Ok,
+        Fail,
+        Panic,
+    }
+
+    /// Module de test : concatène ses entrées texte et ajoute son nom
+    struct Stage {
+        name: String,
+        inputs: Vec<Port>,
+        output: Port,
+        mode: Mode,
+        /// Fallback propre : renvoie l'entrée principale
+        rescue: bool,
+        /// Modules en cours d'exécution et maximum observé
+        running: Option<Arc<(AtomicUsize, AtomicUsize)>>,
+    }
+
+    fn stage(name: &str, inputs: &[&str], output: &str) -> Stage {
+        Stage {
+            name: name.to_string(),
+            inputs: inputs.iter().map(|s| Port::new::<String>(s)).collect(),
+            output: Port::new::<String>(output),
+            mode: Mode::Ok,
+            rescue: false,
+            running: None,
+        }
+    }
+
+    impl Stage {
+        fn with(self, mode: Mode) -> Box<dyn Module> {
+            Box::new(Stage { mode, ..self })
+        }
+    }
+
+    impl Module for Stage {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn modality(&self) -> &str {
+            "text"
+        }
+        fn inputs(&self) -> Vec<Port> {
+            self.inputs.clone()
+        }
+        fn output(&self) -> Port {
+            self.output.clone()
+        }
+        fn process(&self, input: &DataPacket) -> ModuleResult {
+            self.process_all(&[input])
+        }
+        fn process_all(&self, inputs: &[&DataPacket]) -> ModuleResult {
+            if let Some(running) = &self.running {
+                let now = running.0.fetch_add(1, Ordering::SeqCst) + 1;
+                running.1.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(50));
+                running.0.fetch_sub(1, Ordering::SeqCst);
+            }
+            match self.mode {
+                Mode::Ok => {}
+                Mode::Fail => return Err(ModuleError::failed(format!("{} en panne", self.name))),
+                Mode::Panic => panic!("{} a paniqué", self.name),
+            }
+            let texts = inputs
+                .iter()
+                .map(|p| p.payload.get::<String>().map(String::as_str))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ModuleError::not_understood(e.to_string()))?;
+            Ok(DataPacket {
+                modality: "text".to_string(),
+                payload: Payload::Text(format!("{}>{}", texts.join("+"), self.name)),
+                meta: inputs[0].meta.clone(),
+                trace: Trace::default(),
+            })
+        }
+        fn fallback(&self, input: &DataPacket, _error: &ModuleError) -> Option<DataPacket> {
+            self.rescue.then(|| input.clone())
+        }
+    }
+
+    fn text(s: &str) -> DataPacket {
+        DataPacket {
+            modality: "text".to_string(),
+            payload: Payload::Text(s.to_string()),
+            meta: HashMap::new(),
+            trace: Trace::default(),
+        }
+    }
+
+    fn orchestrator(modules: Vec<Box<dyn Module>>) -> Orchestrator {
+        let mut o = Orchestrator::new();
+        for m in modules {
+            o.add_module(m);
+        }
+        o
+    }
+
+    #[test]
+    fn validate_reports_cycles_and_modules_without_inputs() {
+        let mut o = orchestrator(vec![
+            stage("a", &["y"], "x").with(Mode::Ok),
+            stage("b", &["x"], "y").with(Mode::Ok),
+            stage("c", &[], "z").with(Mode::Ok),
+        ]);
+        o.add_fallback("a", stage("a2", &[], "x").with(Mode::Ok));
+        let errors = o.validate().unwrap_err();
+        let no_input = |m: &str| PipelineError::NoInput {
+            module: m.to_string(),
+        };
+        assert!(errors.contains(&no_input("c")));
+        assert!(errors.contains(&no_input("a2")));
+        let cycle = errors.iter().find_map(|e| match e {
+            PipelineError::Cycle { modules } => Some(modules.clone()),
+            _ => None,
+        });
+        let mut cycle = cycle.expect("cycle non détecté");
+        cycle.sort();
+        assert_eq!(cycle, ["a", "b"]);
+        // sans entrée, le module est refusé avant d'être exécuté
+        assert!(o.run(text("x")).is_err());
+    }
+
+    #[test]
+    fn plan_reports_unsatisfied_inputs_and_type_mismatches() {
+        let raw = Stage {
+            inputs: vec![Port::new::<Vec<u8>>("text")],
+            ..stage("raw", &[], "raw")
+        };
+        let o = orchestrator(vec![
+            stage("upper", &["text"], "upper").with(Mode::Ok),
+            stage("join", &["upper", "missing"], "joined").with(Mode::Ok),
+            raw.with(Mode::Ok),
+        ]);
+        let errors = o.plan(&Port::new::<String>("text")).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                PipelineError::UnsatisfiedInput {
+                    module: "join".to_string(),
+                    slot: "missing".to_string(),
+                },
+                PipelineError::TypeMismatch {
+                    slot: "text".to_string(),
+                    producer: SOURCE.to_string(),
+                    consumer: "raw".to_string(),
+                    expected: PayloadKind::Bytes,
+                    found: PayloadKind::Text,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn independent_branches_run_in_parallel() {
+        let running = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+        let counted = |name: &str, inputs: &[&str]| -> Box<dyn Module> {
+            Box::new(Stage {
+                running: Some(running.clone()),
+                ..stage(name, inputs, name)
+            })
+        };
+        // l'ordre d'ajout ne compte pas
+        let o = orchestrator(vec![
+            counted("c", &["a", "b"]),
+            counted("a", &["text"]),
+            counted("b", &["text"]),
+        ]);
+        let plan = o.plan(&Port::new::<String>("text")).unwrap();
+        let levels: Vec<Vec<&str>> = plan
+            .levels
+            .iter()
+            .map(|l| l.iter().map(|&i| o.modules[i].name()).collect())
+            .collect();
+        assert_eq!(levels, [vec!["a", "b"], vec!["c"]]);
+
+        let out = o.run(text("x")).unwrap();
+        assert_eq!(out.executed, ["a", "b", "c"]);
+        let c = out.get("c").unwrap().payload.get::<String>().unwrap();
+        assert_eq!(c, "x>a+x>b>c");
+        assert_eq!(running.1.load(Ordering::SeqCst), 2);
+        // la trace de `c` réunit ses deux branches
+        let steps: Vec<&str> = out
+            .get("c")
+            .unwrap()
+            .trace
+            .entries
+            .iter()
+            .map(|e| e.module.as_str())
+            .collect();
+        assert_eq!(steps, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn a_failing_or_panicking_module_fails_the_run() {
+        let failed = |errors: Vec<PipelineError>| {
+            errors
+                .into_iter()
+                .filter_map(|e| match e {
+                    PipelineError::ModuleFailed { module, .. } => Some(module),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        for mode in [Mode::Fail, Mode::Panic] {
+            let alone = orchestrator(vec![stage("a", &["text"], "a").with(mode)]);
+            assert_eq!(failed(alone.run(text("x")).unwrap_err()), ["a"]);
+        }
+
+        let parallel = orchestrator(vec![
+            stage("a", &["text"], "a").with(Mode::Panic),
+            stage("b", &["text"], "b").with(Mode::Ok),
+        ]);
+        assert_eq!(
+            failed(parallel.run_with_fallback(text("x")).unwrap_err()),
+            ["a"]
+        );
+    }
+
+    #[test]
+    fn fallbacks_are_tried_in_order_and_recorded() {
+        let mut o = orchestrator(vec![
+            stage("upper", &["text"], "upper").with(Mode::Fail),
+            stage("next", &["upper"], "next").with(Mode::Ok),
+        ]);
+        o.add_fallback("upper", stage("first", &["text"], "upper").with(Mode::Fail));
+        o.add_fallback("upper", stage("second", &["text"], "upper").with(Mode::Ok));
+        o.add_fallback("upper", stage("third", &["text"], "upper").with(Mode::Ok));
+        assert!(o.run(text("x")).is_err());
+
+        let out = o.run_with_fallback(text("x")).unwrap();
+        assert_eq!(out.executed, ["upper", "next"]);
+        let upper = out.get("upper").unwrap();
+        assert_eq!(upper.payload.get::<String>().unwrap(), "x>second");
+        assert_eq!(upper.meta["upper.path"], "fallback:second");
+        assert_eq!(
+            upper.meta["upper.errors"],
+            "upper : échec : upper en panne ; first : échec : first en panne"
+        );
+        assert_eq!(upper.trace.entries[0].path, "fallback:second");
+        // la méta suit le paquet dans les modules suivants
+        let next = out.get("next").unwrap();
+        assert_eq!(next.meta["next.path"], "process");
+        assert!(!next.meta.contains_key("next.errors"));
+        assert_eq!(next.meta["upper.path"], "fallback:second");
+    }
+
+    #[test]
+    fn the_module_fallback_comes_before_registered_ones() {
+        let rescued = Stage {
+            rescue: true,
+            ..stage("upper", &["text"], "upper")
+        };
+        let mut o = orchestrator(vec![rescued.with(Mode::Fail)]);
+        o.add_fallback("upper", stage("other", &["text"], "upper").with(Mode::Ok));
+        let out = o.run_with_fallback(text("x")).unwrap();
+        let upper = out.get("upper").unwrap();
+        assert_eq!(upper.payload.get::<String>().unwrap(), "x");
+        assert_eq!(upper.meta["upper.path"], "fallback");
+        assert_eq!(upper.meta["upper.errors"], "upper : échec : upper en panne");
+    }
+
+    #[test]
+    fn an_exhausted_chain_lists_every_attempt() {
+        let mut o = orchestrator(vec![stage("upper", &["text"], "upper").with(Mode::Fail)]);
+        o.add_fallback("upper", stage("first", &["text"], "upper").with(Mode::Fail));
+        let errors = o.run_with_fallback(text("x")).unwrap_err();
+        let attempts = |names: &[&str]| {
+            names
+                .iter()
+                .map(|n| (n.to_string(), ModuleError::failed(format!("{n} en panne"))))
+                .collect()
+        };
+        assert_eq!(
+            errors,
+            [PipelineError::ModuleFailed {
+                module: "upper".to_string(),
+                attempts: attempts(&["upper", "first"]),
+            }]
+        );
+    }
+
+    #[test]
+    fn fallbacks_must_fit_the_module() {
+        let mut o = orchestrator(vec![stage("upper", &["text"], "upper").with(Mode::Ok)]);
+        let bytes = Stage {
+            output: Port::new::<Vec<u8>>("upper"),
+            ..stage("bytes", &["text"], "upper")
+        };
+        o.add_fallback("upper", bytes.with(Mode::Ok));
+        o.add_fallback(
+            "upper",
+            stage("wide", &["text", "other"], "upper").with(Mode::Ok),
+        );
+        o.add_fallback("ghost", stage("lost", &["text"], "ghost").with(Mode::Ok));
+        let errors = o.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                PipelineError::UnknownFallbackTarget {
+                    module: "ghost".to_string(),
+                    fallback: "lost".to_string(),
+                },
+                PipelineError::FallbackMismatch {
+                    module: "upper".to_string(),
+                    fallback: "bytes".to_string(),
+                    slot: "upper".to_string(),
+                    expected: Some(PayloadKind::Text),
+                    found: PayloadKind::Bytes,
+                },
+                PipelineError::FallbackMismatch {
+                    module: "upper".to_string(),
+                    fallback: "wide".to_string(),
+                    slot: "other".to_string(),
+                    expected: None,
+                    found: PayloadKind::Text,
+                },
+            ]
+        );
+    }
+
+    /// Module de test : ajoute, modifie et retire des métadonnées
+    struct Tagger;
+
+    impl Module for Tagger {
+        fn name(&self) -> &str {
+            "tag"
+        }
+        fn modality(&self) -> &str {
+            "text"
+        }
+        fn inputs(&self) -> Vec<Port> {
+            vec![Port::new::<String>("text")]
+        }
+        fn output(&self) -> Port {
+            Port::new::<String>("tagged")
+        }
+        fn process(&self, input: &DataPacket) -> ModuleResult {
+            let mut packet = input.clone();
+            packet.payload = Payload::Text(input.payload.get::<String>()?.to_uppercase());
+            packet.meta.insert("lang".to_string(), "fr".to_string());
+            packet.meta.insert("source".to_string(), "tag".to_string());
+            packet.meta.remove("draft");
+            Ok(packet)
+        }
+    }
+
+    #[test]
+    fn trace_records_meta_changes_and_provenance() {
+        let o = orchestrator(vec![
+            stage("next", &["tagged"], "next").with(Mode::Ok),
+            Box::new(Tagger),
+        ]);
+        let mut input = text("x");
+        input.meta = HashMap::from([
+            ("source".to_string(), "user".to_string()),
+            ("draft".to_string(), "1".to_string()),
+        ]);
+        let out = o.run(input).unwrap();
+
+        let tagged = out.get("tagged").unwrap();
+        let [step] = tagged.trace.entries.as_slice() else {
+            panic!("une étape attendue : {:?}", tagged.trace);
+        };
+        assert_eq!(step.module, "tag");
+        assert_eq!(step.path, "process");
+        assert_eq!(step.meta_added, ["lang", "tag.path"]);
+        assert_eq!(step.meta_changed, ["source"]);
+        assert_eq!(step.meta_removed, ["draft"]);
+        assert_eq!(step.payload_kind, PayloadKind::Text);
+        assert_eq!(step.payload_digest, payload_digest(&tagged.payload));
+
+        // le paquet suivant hérite de la trace de son entrée
+        let next = out.get("next").unwrap();
+        let modules: Vec<&str> = next
+            .trace
+            .entries
+            .iter()
+            .map(|e| e.module.as_str())
+            .collect();
+        assert_eq!(modules, ["tag", "next"]);
+        assert_eq!(next.trace.entries[1].meta_added, ["next.path"]);
+        assert!(next.trace.entries[1].meta_changed.is_empty());
+        let touching: Vec<&str> = next
+            .trace
+            .touching("source")
+            .iter()
+            .map(|e| e.module.as_str())
+            .collect();
+        assert_eq!(touching, ["tag"]);
+        assert_eq!(out.trace().entries, next.trace.entries);
     }
 }
@@ -0,0 +1,171 @@
+//! Contenu d'un DataPacket : un type parmi ceux du registre (`PayloadKind`),
+//! inspectable et sérialisable sans connaître le module qui l'a produit
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoded::Encoded;
+use crate::orchestrator::{Audio, PayloadError, PayloadKind, PayloadResult, Pixels};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value")]
+pub enum Payload {
+    Text(String),
+    Bytes(Vec<u8>),
+    Encoded(Encoded),
+    Pixels(Pixels),
+    Audio(Audio),
+}
+
+impl Payload {
+    pub fn kind(&self) -> PayloadKind {
+        match self {
+            Payload::Text(_) => PayloadKind::Text,
+            Payload::Bytes(_) => PayloadKind::Bytes,
+            Payload::Encoded(_) => PayloadKind::Encoded,
+            Payload::Pixels(_) => PayloadKind::Pixels,
+            Payload::Audio(_) => PayloadKind::Audio,
+        }
+    }
+
+    /// Accès vérifié : erreur si le payload n'est pas de type `T`
+    pub fn get<T: PayloadType>(&self) -> PayloadResult<&T> {
+        T::from_payload(self).ok_or(PayloadError::WrongKind {
+            expected: T::KIND,
+            found: self.kind(),
+        })
+    }
+
+    /// Vérifie un payload désérialisé (seul `Encoded` a des invariants
+    /// entre ses champs)
+    pub fn check(&self) -> PayloadResult<()> {
+        match self {
+            Payload::Encoded(e) => e.check().map_err(|reason| PayloadError::Corrupt {
+                kind: self.kind(),
+                reason,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Payload::Text(s) => write!(f, "Text ({} caractères)", s.chars().count()),
+            Payload::Bytes(b) => write!(f, "Bytes ({} octets)", b.len()),
+            Payload::Encoded(e) => write!(
+                f,
+                "Encoded ({} caractères, {} associations)",
+                e.metas.len(),
+                e.associations.len()
+            ),
+            Payload::Pixels(p) => write!(
+                f,
+                "Pixels ({}x{}, {}, {} octets)",
+                p.width,
+                p.height,
+                p.color,
+                p.data.len()
+            ),
+            Payload::Audio(a) => write!(
+                f,
+                "Audio ({} Hz, {} canaux, {} échantillons)",
+                a.sample_rate,
+                a.channels,
+                a.samples.len()
+            ),
+        }
+    }
+}
+
+/// Type Rust enregistré comme payload : correspondance avec sa variante
+pub trait PayloadType: Sized {
+    const KIND: PayloadKind;
+    fn from_payload(payload: &Payload) -> Option<&Self>;
+    fn into_payload(self) -> Payload;
+}
+
+macro_rules! payload_type {
+    ($ty:ty, $variant:ident) => {
+        impl PayloadType for $ty {
+            const KIND: PayloadKind = PayloadKind::$variant;
+            fn from_payload(payload: &Payload) -> Option<&Self> {
+                match payload {
+                    Payload::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+            fn into_payload(self) -> Payload {
+                Payload::$variant(self)
+            }
+        }
+
+        impl From<$ty> for Payload {
+            fn from(v: $ty) -> Self {
+                v.into_payload()
+            }
+        }
+    };
+}
+
+payload_type!(String, Text);
+payload_type!(Vec<u8>, Bytes);
+payload_type!(Encoded, Encoded);
+payload_type!(Pixels, Pixels);
+payload_type!(Audio, Audio);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::category_registry::CategoryRegistry;
+    use crate::encoded::encode;
+    use crate::orchestrator::ModuleError;
+
+    #[test]
+    fn get_checks_the_kind() {
+        let payload = Payload::from("Pluton".to_string());
+        assert_eq!(payload.get::<String>().map(String::as_str), Ok("Pluton"));
+        let error = payload.get::<Vec<u8>>().unwrap_err();
+        assert_eq!(
+            error,
+            PayloadError::WrongKind {
+                expected: PayloadKind::Bytes,
+                found: PayloadKind::Text,
+            }
+        );
+        assert!(matches!(
+            ModuleError::from(error),
+            ModuleError::NotUnderstood { .. }
+        ));
+    }
+
+    #[test]
+    fn check_rejects_inconsistent_encodings() {
+        let valid = encode("Éa\u{301} 👍🏽", CategoryRegistry::default());
+        assert_eq!(Payload::from(valid.clone()).check(), Ok(()));
+
+        let mut bad_utf8 = valid.clone();
+        bad_utf8.bytes[0] = 0xff;
+        let mut short = valid.clone();
+        short.bytes.pop();
+        let mut shifted = valid.clone();
+        shifted.metas[1].byte_span = 1..2;
+        let mut overlapping = valid.clone();
+        overlapping.graphemes[1].meta_span.start = 0;
+        let mut association = valid;
+        association
+            .associations
+            .insert(0, ("mot".to_string(), vec![0, 99]));
+        for encoded in [bad_utf8, short, shifted, overlapping, association] {
+            assert!(matches!(
+                Payload::from(encoded).check(),
+                Err(PayloadError::Corrupt {
+                    kind: PayloadKind::Encoded,
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Accès à un payload sous un type qui n'est pas le sien, ou payload
+//! incohérent reçu de l'extérieur
+
+use std::fmt;
+
+use crate::orchestrator::{ModuleError, PayloadKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadError {
+    WrongKind {
+        expected: PayloadKind,
+        found: PayloadKind,
+    },
+    /// Payload désérialisé dont le contenu viole ses invariants
+    Corrupt { kind: PayloadKind, reason: String },
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadError::WrongKind { expected, found } => {
+                write!(f, "payload {found}, attendu {expected}")
+            }
+            PayloadError::Corrupt { kind, reason } => {
+                write!(f, "payload {kind} incohérent : {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PayloadError {}
+
+/// Un module qui reçoit un payload d'un autre type ne le comprend pas
+impl From<PayloadError> for ModuleError {
+    fn from(e: PayloadError) -> Self {
+        ModuleError::not_understood(e.to_string())
+    }
+}
+
+pub type PayloadResult<T> = Result<T, PayloadError>;
@@ -0,0 +1,27 @@
+//! Types de payload connus de l'orchestrateur
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PayloadKind {
+    Text,
+    Bytes,
+    Encoded,
+    Pixels,
+    Audio,
+}
+
+impl fmt::Display for PayloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PayloadKind::Text => "Text",
+            PayloadKind::Bytes => "Bytes",
+            PayloadKind::Encoded => "Encoded",
+            PayloadKind::Pixels => "Pixels",
+            PayloadKind::Audio => "Audio",
+        };
+        f.write_str(name)
+    }
+}
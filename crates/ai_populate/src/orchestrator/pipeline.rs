@@ -0,0 +1,9 @@
+//! Plan d'exécution validé : les modules actifs, groupés par niveaux
+
+/// Les modules d'un même niveau ne dépendent pas les uns des autres et
+/// s'exécutent en parallèle ; chaque niveau attend le précédent.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    /// Indices des modules de l'orchestrateur, par niveau
+    pub levels: Vec<Vec<usize>>,
+}
@@ -0,0 +1,130 @@
+//! Erreurs de construction ou d'exécution du pipeline
+
+use std::fmt;
+
+use crate::orchestrator::{ModuleError, PayloadKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineError {
+    /// Deux modules portent le même nom
+    DuplicateModule { name: String },
+    /// `depends_on` cite un module absent
+    UnknownDependency { module: String, dependency: String },
+    /// Plusieurs producteurs pour une même donnée ("<source>" : le paquet d'entrée)
+    DuplicateProducer {
+        slot: String,
+        producers: Vec<String>,
+    },
+    /// Le producteur d'une donnée ne fournit pas le type attendu
+    TypeMismatch {
+        slot: String,
+        producer: String,
+        consumer: String,
+        expected: PayloadKind,
+        found: PayloadKind,
+    },
+    /// Un module (ou un fallback) ne déclare aucune entrée : il n'aurait
+    /// pas d'entrée principale
+    NoInput { module: String },
+    /// Une entrée d'un module actif n'est produite par personne
+    UnsatisfiedInput { module: String, slot: String },
+    /// Dépendances circulaires (dans l'ordre du cycle)
+    Cycle { modules: Vec<String> },
+    /// Fallback enregistré pour un module absent
+    UnknownFallbackTarget { module: String, fallback: String },
+    /// Un module de fallback ne peut pas remplacer le module : sortie d'un
+    /// autre type, ou entrée que le module ne reçoit pas
+    FallbackMismatch {
+        module: String,
+        fallback: String,
+        slot: String,
+        /// None : le module ne reçoit pas cette donnée
+        expected: Option<PayloadKind>,
+        found: PayloadKind,
+    },
+    /// Le module et tous ses fallbacks ont échoué (tentatives dans l'ordre)
+    ModuleFailed {
+        module: String,
+        attempts: Vec<(String, ModuleError)>,
+    },
+    /// À l'exécution, un module a renvoyé un payload d'un autre type que déclaré
+    UnexpectedPayload {
+        module: String,
+        slot: String,
+        expected: PayloadKind,
+    },
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineError::DuplicateModule { name } => {
+                write!(f, "module '{name}' ajouté deux fois")
+            }
+            PipelineError::UnknownDependency { module, dependency } => {
+                write!(f, "'{module}' dépend de '{dependency}', qui n'existe pas")
+            }
+            PipelineError::DuplicateProducer { slot, producers } => write!(
+                f,
+                "'{slot}' est produit par plusieurs modules : {}",
+                producers.join(", ")
+            ),
+            PipelineError::TypeMismatch {
+                slot,
+                producer,
+                consumer,
+                expected,
+                found,
+            } => write!(
+                f,
+                "'{consumer}' attend '{slot}' de type {expected}, '{producer}' fournit {found}"
+            ),
+            PipelineError::NoInput { module } => {
+                write!(f, "'{module}' ne déclare aucune entrée")
+            }
+            PipelineError::UnsatisfiedInput { module, slot } => {
+                write!(f, "'{module}' attend '{slot}', que rien ne produit")
+            }
+            PipelineError::Cycle { modules } => {
+                write!(f, "cycle : {} -> {}", modules.join(" -> "), modules[0])
+            }
+            PipelineError::UnknownFallbackTarget { module, fallback } => write!(
+                f,
+                "fallback '{fallback}' enregistré pour '{module}', qui n'existe pas"
+            ),
+            PipelineError::FallbackMismatch {
+                module,
+                fallback,
+                slot,
+                expected,
+                found,
+            } => match expected {
+                Some(expected) => write!(
+                    f,
+                    "'{fallback}' ne peut pas remplacer '{module}' : '{slot}' de type {found}, attendu {expected}"
+                ),
+                None => write!(
+                    f,
+                    "'{fallback}' ne peut pas remplacer '{module}' : '{slot}' n'est pas une entrée de '{module}'"
+                ),
+            },
+            PipelineError::ModuleFailed { module, attempts } => {
+                write!(f, "'{module}' a échoué")?;
+                for (name, error) in attempts {
+                    write!(f, " ; {name} : {error}")?;
+                }
+                Ok(())
+            }
+            PipelineError::UnexpectedPayload {
+                module,
+                slot,
+                expected,
+            } => write!(
+                f,
+                "'{module}' a produit '{slot}' d'un autre type que {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
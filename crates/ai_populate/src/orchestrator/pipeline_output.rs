@@ -0,0 +1,36 @@
+//! Résultat d'une exécution : toutes les données produites, par nom
+
+use std::collections::HashMap;
+
+use crate::orchestrator::{DataPacket, Trace};
+
+#[derive(Debug)]
+pub struct PipelineOutput {
+    /// Paquets par donnée (l'entrée comprise)
+    pub packets: HashMap<String, DataPacket>,
+    /// Modules exécutés, dans l'ordre des niveaux
+    pub executed: Vec<String>,
+}
+
+impl PipelineOutput {
+    pub fn get(&self, slot: &str) -> Option<&DataPacket> {
+        self.packets.get(slot)
+    }
+
+    /// Trace de toute l'exécution : étapes de tous les paquets produits,
+    /// dans l'ordre d'exécution
+    pub fn trace(&self) -> Trace {
+        let mut trace = Trace::default();
+        for name in &self.executed {
+            let step = self
+                .packets
+                .values()
+                .flat_map(|p| &p.trace.entries)
+                .find(|e| &e.module == name);
+            if let Some(step) = step {
+                trace.push(step.clone());
+            }
+        }
+        trace
+    }
+}
@@ -0,0 +1,12 @@
+//! Image décodée : pixels à plat, ligne par ligne
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pixels {
+    pub width: u32,
+    pub height: u32,
+    /// Disposition des canaux (ex: "Rgb8", "Rgba16", "L8")
+    pub color: String,
+    pub data: Vec<u8>,
+}
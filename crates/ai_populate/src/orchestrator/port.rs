@@ -0,0 +1,43 @@
+//! Entrées et sorties typées des modules : une donnée nommée (`slot`) et
+//! le type de payload attendu
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::orchestrator::{Payload, PayloadKind, PayloadType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Port {
+    /// Nom de la donnée dans le pipeline (ex: "text", "encoded", "pixels")
+    pub slot: String,
+    pub kind: PayloadKind,
+}
+
+impl Port {
+    pub fn new<T: PayloadType>(slot: &str) -> Self {
+        Self {
+            slot: slot.to_string(),
+            kind: T::KIND,
+        }
+    }
+
+    /// Port décrivant un payload existant
+    pub fn of_payload(slot: &str, payload: &Payload) -> Self {
+        Self {
+            slot: slot.to_string(),
+            kind: payload.kind(),
+        }
+    }
+
+    /// Le payload est-il du type attendu par ce port ?
+    pub fn accepts(&self, payload: &Payload) -> bool {
+        payload.kind() == self.kind
+    }
+}
+
+impl fmt::Display for Port {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.slot, self.kind)
+    }
+}
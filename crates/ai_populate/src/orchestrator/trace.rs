@@ -0,0 +1,97 @@
+//! Trace de provenance d'un paquet : les étapes qui l'ont produit, dans
+//! l'ordre d'exécution
+
+use serde::{Deserialize, Serialize};
+
+use crate::orchestrator::TraceEntry;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    pub entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    /// Ajoute les étapes de `other` absentes de cette trace (une étape par
+    /// module et par exécution : les ancêtres communs ne sont pas dupliqués)
+    pub fn merge(&mut self, other: &Trace) {
+        for e in &other.entries {
+            if !self.entries.iter().any(|x| x.module == e.module) {
+                self.entries.push(e.clone());
+            }
+        }
+    }
+
+    pub fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Étapes ayant ajouté ou modifié la clé de métadonnée `key`
+    pub fn touching(&self, key: &str) -> Vec<&TraceEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.meta_added.iter().chain(&e.meta_changed).any(|k| k == key))
+            .collect()
+    }
+
+    /// Trace au format JSON (indenté)
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::PayloadKind;
+
+    fn step(module: &str, added: &[&str], changed: &[&str]) -> TraceEntry {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect();
+        TraceEntry {
+            module: module.to_string(),
+            path: "process".to_string(),
+            modality_in: "text".to_string(),
+            modality_out: "text".to_string(),
+            duration_us: 0,
+            meta_added: keys(added),
+            meta_changed: keys(changed),
+            meta_removed: vec![],
+            payload_kind: PayloadKind::Text,
+            payload_digest: String::new(),
+        }
+    }
+
+    fn trace(steps: Vec<TraceEntry>) -> Trace {
+        Trace { entries: steps }
+    }
+
+    #[test]
+    fn merge_keeps_common_ancestors_once() {
+        let source = step("source", &["lang"], &[]);
+        let mut left = trace(vec![source.clone(), step("left", &[], &[])]);
+        let right = trace(vec![source, step("right", &[], &["lang"])]);
+        left.merge(&right);
+        let modules: Vec<&str> = left.entries.iter().map(|e| e.module.as_str()).collect();
+        assert_eq!(modules, ["source", "left", "right"]);
+        let before = left.clone();
+        left.merge(&right);
+        assert_eq!(left, before);
+    }
+
+    #[test]
+    fn touching_lists_additions_and_changes() {
+        let t = trace(vec![
+            step("a", &["lang"], &[]),
+            step("b", &["other"], &[]),
+            step("c", &[], &["lang"]),
+        ]);
+        let modules: Vec<&str> = t
+            .touching("lang")
+            .iter()
+            .map(|e| e.module.as_str())
+            .collect();
+        assert_eq!(modules, ["a", "c"]);
+        assert!(t.touching("missing").is_empty());
+        let json = t.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<Trace>(&json).unwrap(), t);
+    }
+}
@@ -0,0 +1,25 @@
+//! Une étape de la trace : ce qu'un module a reçu et ce qu'il a produit
+
+use serde::{Deserialize, Serialize};
+
+use crate::orchestrator::PayloadKind;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Module du pipeline (même si un fallback a produit le résultat)
+    pub module: String,
+    /// Chemin suivi : "process", "fallback" ou "fallback:<module>"
+    pub path: String,
+    pub modality_in: String,
+    pub modality_out: String,
+    /// Durée du module, fallbacks compris (microsecondes)
+    pub duration_us: u64,
+    /// Clés de `meta` ajoutées, modifiées ou retirées par rapport à
+    /// l'entrée principale
+    pub meta_added: Vec<String>,
+    pub meta_changed: Vec<String>,
+    pub meta_removed: Vec<String>,
+    pub payload_kind: PayloadKind,
+    /// Empreinte du payload produit (voir `payload_digest`)
+    pub payload_digest: String,
+}
@@ -0,0 +1,9 @@
+pub mod plugin_error;
+pub mod plugin_host;
+pub mod plugin_manifest;
+pub mod plugin_module;
+
+pub use plugin_error::{PluginError, PluginResult};
+pub use plugin_host::PluginHost;
+pub use plugin_manifest::PluginManifest;
+pub use plugin_module::PluginModule;
@@ -0,0 +1,73 @@
+//! Erreurs de chargement, déchargement ou rechargement des plugins
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::orchestrator::PipelineError;
+
+#[derive(Debug)]
+pub enum PluginError {
+    /// Copie ou lecture du fichier impossible
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// La bibliothèque dynamique ne s'ouvre pas, ou n'exporte pas le symbole
+    Load { path: PathBuf, reason: String },
+    /// Plugin compilé pour une autre version de l'interface
+    AbiVersion {
+        path: PathBuf,
+        found: u32,
+        expected: u32,
+    },
+    /// Manifeste illisible
+    Manifest { path: PathBuf, reason: String },
+    /// Le pipeline ne valide plus avec ce plugin (il n'a pas été installé)
+    Rejected {
+        name: String,
+        errors: Vec<PipelineError>,
+    },
+    /// La nouvelle version d'un plugin rechargé porte un autre nom
+    Renamed { name: String, found: String },
+    /// Aucun plugin chargé sous ce nom
+    UnknownPlugin { name: String },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::Io { path, error } => write!(f, "{} : {error}", path.display()),
+            PluginError::Load { path, reason } => {
+                write!(f, "chargement de {} : {reason}", path.display())
+            }
+            PluginError::AbiVersion {
+                path,
+                found,
+                expected,
+            } => write!(
+                f,
+                "{} : interface v{found}, l'hôte attend v{expected}",
+                path.display()
+            ),
+            PluginError::Manifest { path, reason } => {
+                write!(f, "manifeste de {} : {reason}", path.display())
+            }
+            PluginError::Rejected { name, errors } => {
+                write!(f, "plugin '{name}' refusé")?;
+                for e in errors {
+                    write!(f, " ; {e}")?;
+                }
+                Ok(())
+            }
+            PluginError::Renamed { name, found } => write!(
+                f,
+                "'{name}' rechargé sous le nom '{found}' : le décharger puis charger le nouveau"
+            ),
+            PluginError::UnknownPlugin { name } => write!(f, "aucun plugin '{name}' chargé"),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+pub type PluginResult<T> = Result<T, PluginError>;
@@ -0,0 +1,378 @@
+//! Chargement, déchargement et rechargement à chaud des plugins, sans
+//! reconstruire l'orchestrateur
+//!
+//! La bibliothèque est copiée sous un nom unique avant d'être ouverte :
+//! le chargeur dynamique réutiliserait sinon la version déjà en mémoire, et
+//! le fichier d'origine peut être recompilé pendant que le plugin tourne.
+//! Les copies vont dans un répertoire créé par l'hôte, réservé à
+//! l'utilisateur : un autre compte ne peut pas y substituer sa bibliothèque.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::orchestrator::{Orchestrator, PipelineError};
+use crate::plugin::{PluginError, PluginModule, PluginResult};
+
+struct LoadedPlugin {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+pub struct PluginHost {
+    /// Plugins installés, par nom de module
+    plugins: HashMap<String, LoadedPlugin>,
+    /// Répertoire des copies, créé au premier chargement
+    dir: Option<PathBuf>,
+    /// Compteur des copies (noms uniques)
+    loads: usize,
+}
+
+impl Drop for PluginHost {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+impl PluginHost {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Noms des plugins chargés
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.plugins.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Crée le répertoire des copies : `create` échoue s'il existe déjà
+    /// (lien symbolique compris), on tente alors un autre nom
+    fn copy_dir(&mut self) -> PluginResult<PathBuf> {
+        if let Some(dir) = &self.dir {
+            return Ok(dir.clone());
+        }
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos());
+        let mut attempt = 0;
+        loop {
+            let dir =
+                env::temp_dir().join(format!("ai_populate_{}_{nanos}_{attempt}", process::id()));
+            let mut builder = fs::DirBuilder::new();
+            #[cfg(unix)]
+            std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+            match builder.create(&dir) {
+                Ok(()) => {
+                    self.dir = Some(dir.clone());
+                    return Ok(dir);
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => {
+                    attempt += 1;
+                }
+                Err(error) => return Err(PluginError::Io { path: dir, error }),
+            }
+        }
+    }
+
+    fn open(&mut self, path: &Path) -> PluginResult<(PluginModule, Option<SystemTime>)> {
+        let io = |error| PluginError::Io {
+            path: path.to_path_buf(),
+            error,
+        };
+        let modified = fs::metadata(path).map_err(io)?.modified().ok();
+        let dir = self.copy_dir()?;
+        self.loads += 1;
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let copy = dir.join(format!("{}_{file}", self.loads));
+        // `create_new` : jamais d'écriture à travers un fichier existant
+        let copied = File::open(path).and_then(|mut source| {
+            let mut target = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&copy)?;
+            io::copy(&mut source, &mut target)
+        });
+        if let Err(error) = copied {
+            let _ = fs::remove_file(&copy);
+            return Err(io(error));
+        }
+        let module = PluginModule::open(&copy, path);
+        // la bibliothèque ouverte reste en mémoire : la copie n'est plus utile
+        let _ = fs::remove_file(&copy);
+        Ok((module?, modified))
+    }
+
+    /// Charge un plugin et l'ajoute à l'orchestrateur ; refusé (et non
+    /// installé) si le pipeline ne valide plus
+    pub fn load(&mut self, orchestrator: &mut Orchestrator, path: &Path) -> PluginResult<String> {
+        let (module, modified) = self.open(path)?;
+        let name = module.manifest().name.clone();
+        // un nom déjà pris (plugin ou module intégré) est refusé avant
+        // l'installation : le retrait en cas d'échec ne vise que ce plugin
+        if self.plugins.contains_key(&name) || orchestrator.has_module(&name) {
+            return Err(PluginError::Rejected {
+                name: name.clone(),
+                errors: vec![PipelineError::DuplicateModule { name }],
+            });
+        }
+        orchestrator.add_module(Box::new(module));
+        if let Err(errors) = orchestrator.validate() {
+            orchestrator.remove_module(&name);
+            return Err(PluginError::Rejected { name, errors });
+        }
+        self.plugins.insert(
+            name.clone(),
+            LoadedPlugin {
+                path: path.to_path_buf(),
+                modified,
+            },
+        );
+        Ok(name)
+    }
+
+    /// Retire le plugin de l'orchestrateur et décharge sa bibliothèque
+    pub fn unload(&mut self, orchestrator: &mut Orchestrator, name: &str) -> PluginResult<()> {
+        if self.plugins.remove(name).is_none() {
+            return Err(PluginError::UnknownPlugin {
+                name: name.to_string(),
+            });
+        }
+        drop(orchestrator.remove_module(name));
+        Ok(())
+    }
+
+    pub fn unload_all(&mut self, orchestrator: &mut Orchestrator) {
+        let names: Vec<String> = self.plugins.keys().cloned().collect();
+        for name in names {
+            let _ = self.unload(orchestrator, &name);
+        }
+    }
+
+    /// Recharge le plugin depuis son fichier. La nouvelle version remplace
+    /// l'ancienne à la même place ; si elle ne s'ouvre pas ou si le pipeline
+    /// ne valide plus, l'ancienne reste en service.
+    pub fn reload(&mut self, orchestrator: &mut Orchestrator, name: &str) -> PluginResult<()> {
+        let path = match self.plugins.get(name) {
+            Some(p) => p.path.clone(),
+            None => {
+                return Err(PluginError::UnknownPlugin {
+                    name: name.to_string(),
+                })
+            }
+        };
+        let (module, modified) = self.open(&path)?;
+        let found = module.manifest().name.clone();
+        if found != name {
+            return Err(PluginError::Renamed {
+                name: name.to_string(),
+                found,
+            });
+        }
+        let old = orchestrator.replace_module(Box::new(module));
+        if let Err(errors) = orchestrator.validate() {
+            if let Some(old) = old {
+                orchestrator.replace_module(old);
+            }
+            return Err(PluginError::Rejected {
+                name: found,
+                errors,
+            });
+        }
+        self.plugins.insert(found, LoadedPlugin { path, modified });
+        Ok(())
+    }
+
+    /// Recharge les plugins dont le fichier a changé depuis leur chargement
+    pub fn reload_changed(
+        &mut self,
+        orchestrator: &mut Orchestrator,
+    ) -> Vec<(String, PluginResult<()>)> {
+        let changed: Vec<String> = self
+            .plugins
+            .iter()
+            .filter(|(_, p)| {
+                let now = fs::metadata(&p.path).and_then(|m| m.modified()).ok();
+                now.is_some() && now != p.modified
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed
+            .into_iter()
+            .map(|name| {
+                let result = self.reload(orchestrator, &name);
+                (name, result)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+    use std::process::Command;
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::orchestrator::{DataPacket, Module, ModuleResult, Payload, Port, Trace};
+
+    /// Bibliothèque du plugin d'exemple, compilée une fois pour tous les tests
+    fn example() -> &'static Path {
+        static LIBRARY: OnceLock<PathBuf> = OnceLock::new();
+        LIBRARY.get_or_init(|| {
+            // target/<profil>/deps/<test> -> target/<profil>/examples
+            let exe = env::current_exe().unwrap();
+            let profile = exe.parent().unwrap().parent().unwrap();
+            let mut build = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+            build.args([
+                "build",
+                "-q",
+                "-p",
+                "ai_populate_plugin",
+                "--example",
+                "upper_text",
+            ]);
+            if profile.ends_with("release") {
+                build.arg("--release");
+            }
+            assert!(build.status().unwrap().success(), "compilation du plugin");
+            profile
+                .join("examples")
+                .join(format!("{DLL_PREFIX}upper_text{DLL_SUFFIX}"))
+        })
+    }
+
+    /// Module intégré qui produit la même donnée que le plugin
+    struct Builtin(&'static str);
+
+    impl Module for Builtin {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn modality(&self) -> &str {
+            "text"
+        }
+        fn inputs(&self) -> Vec<Port> {
+            vec![Port::new::<String>("text")]
+        }
+        fn output(&self) -> Port {
+            Port::new::<String>("text_upper")
+        }
+        fn process(&self, input: &DataPacket) -> ModuleResult {
+            Ok(DataPacket {
+                payload: Payload::Text(format!("intégré : {}", input.payload.get::<String>()?)),
+                ..input.clone()
+            })
+        }
+    }
+
+    fn text(s: &str) -> DataPacket {
+        DataPacket {
+            modality: "text".to_string(),
+            payload: Payload::Text(s.to_string()),
+            meta: HashMap::new(),
+            trace: Trace::default(),
+        }
+    }
+
+    fn upper(o: &Orchestrator, s: &str) -> String {
+        let out = o.run(text(s)).unwrap();
+        out.get("text_upper")
+            .unwrap()
+            .payload
+            .get::<String>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn load_runs_and_unloads_the_plugin() {
+        let (mut host, mut o) = (PluginHost::new(), Orchestrator::new());
+        let name = host.load(&mut o, example()).unwrap();
+        assert_eq!(name, "UpperTextPlugin");
+        assert_eq!(host.names(), [name.as_str()]);
+        assert_eq!(upper(&o, "Pluton"), "PLUTON");
+
+        host.unload(&mut o, &name).unwrap();
+        assert!(host.names().is_empty());
+        assert!(!o.has_module(&name));
+        assert!(matches!(
+            host.unload(&mut o, &name),
+            Err(PluginError::UnknownPlugin { .. })
+        ));
+    }
+
+    #[test]
+    fn a_taken_name_is_refused_and_leaves_the_module_in_place() {
+        let (mut host, mut o) = (PluginHost::new(), Orchestrator::new());
+        o.add_module(Box::new(Builtin("UpperTextPlugin")));
+        let refused = host.load(&mut o, example()).unwrap_err();
+        assert!(matches!(
+            refused,
+            PluginError::Rejected { ref errors, .. }
+                if errors == &[PipelineError::DuplicateModule { name: "UpperTextPlugin".into() }]
+        ));
+        assert!(host.names().is_empty());
+        assert_eq!(upper(&o, "Pluton"), "intégré : Pluton");
+
+        let (mut host, mut o) = (PluginHost::new(), Orchestrator::new());
+        host.load(&mut o, example()).unwrap();
+        assert!(host.load(&mut o, example()).is_err());
+        assert_eq!(upper(&o, "Pluton"), "PLUTON");
+    }
+
+    #[test]
+    fn reload_keeps_the_old_version_when_the_pipeline_breaks() {
+        let (mut host, mut o) = (PluginHost::new(), Orchestrator::new());
+        let name = host.load(&mut o, example()).unwrap();
+        host.reload(&mut o, &name).unwrap();
+        assert!(host.reload_changed(&mut o).is_empty());
+        assert_eq!(upper(&o, "Pluton"), "PLUTON");
+
+        // un second producteur de `text_upper` : la nouvelle version est refusée
+        o.add_module(Box::new(Builtin("Concurrent")));
+        let Err(PluginError::Rejected { errors, .. }) = host.reload(&mut o, &name) else {
+            panic!("rechargement accepté malgré le conflit");
+        };
+        assert!(matches!(errors[0], PipelineError::DuplicateProducer { .. }));
+        assert!(o.has_module(&name));
+        assert_eq!(host.names(), [name.as_str()]);
+        o.remove_module("Concurrent");
+        assert_eq!(upper(&o, "Pluton"), "PLUTON");
+
+        assert!(matches!(
+            host.reload(&mut o, "Absent"),
+            Err(PluginError::UnknownPlugin { .. })
+        ));
+    }
+
+    #[test]
+    fn copies_live_in_a_private_directory() {
+        let (mut host, mut o) = (PluginHost::new(), Orchestrator::new());
+        assert!(matches!(
+            host.load(&mut o, Path::new("absent.so")),
+            Err(PluginError::Io { .. })
+        ));
+        host.load(&mut o, example()).unwrap();
+        let dir = host.dir.clone().unwrap();
+        // la copie est retirée dès l'ouverture
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        host.unload_all(&mut o);
+        drop(host);
+        assert!(!dir.exists());
+    }
+}
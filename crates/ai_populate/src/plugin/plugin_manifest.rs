@@ -0,0 +1,15 @@
+//! Description d'un plugin, lue à son chargement
+
+use serde::Deserialize;
+
+use crate::orchestrator::Port;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub modality: String,
+    pub inputs: Vec<Port>,
+    pub output: Port,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
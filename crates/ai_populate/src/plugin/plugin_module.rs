@@ -0,0 +1,129 @@
+//! Module de l'orchestrateur implémenté par une bibliothèque dynamique
+
+use std::path::Path;
+
+use ai_populate_plugin::{
+    EntryPoint, PluginBuffer, PluginVTable, ABI_VERSION, ENTRY_POINT, STATUS_NOT_UNDERSTOOD,
+    STATUS_OK,
+};
+use libloading::Library;
+
+use crate::orchestrator::{DataPacket, Module, ModuleError, ModuleResult, Port};
+use crate::plugin::{PluginError, PluginManifest, PluginResult};
+
+pub struct PluginModule {
+    manifest: PluginManifest,
+    /// Table statique du plugin, valide tant que la bibliothèque est chargée
+    vtable: *const PluginVTable,
+    /// Déchargée (dlclose) quand le module est abandonné
+    _library: Library,
+}
+
+// La table de fonctions est immuable et l'interface exige que `process`
+// soit réentrant : le module peut être partagé entre les branches du pipeline.
+unsafe impl Send for PluginModule {}
+unsafe impl Sync for PluginModule {}
+
+impl PluginModule {
+    /// Ouvre la bibliothèque, vérifie la version de l'interface et lit le
+    /// manifeste. `origin` ne sert qu'aux messages d'erreur.
+    pub fn open(path: &Path, origin: &Path) -> PluginResult<Self> {
+        let load_error = |e: libloading::Error| PluginError::Load {
+            path: origin.to_path_buf(),
+            reason: e.to_string(),
+        };
+        // SAFETY : charger un plugin exécute son code d'initialisation ; on
+        // ne charge que des chemins fournis explicitement par l'utilisateur.
+        let library = unsafe { Library::new(path) }.map_err(load_error)?;
+        let vtable = unsafe {
+            let entry = library.get::<EntryPoint>(ENTRY_POINT).map_err(load_error)?;
+            entry()
+        };
+        if vtable.is_null() {
+            return Err(PluginError::Load {
+                path: origin.to_path_buf(),
+                reason: "le point d'entrée a renvoyé une table nulle".to_string(),
+            });
+        }
+        let abi_version = unsafe { (*vtable).abi_version };
+        if abi_version != ABI_VERSION {
+            return Err(PluginError::AbiVersion {
+                path: origin.to_path_buf(),
+                found: abi_version,
+                expected: ABI_VERSION,
+            });
+        }
+        let manifest = unsafe {
+            let buffer = ((*vtable).manifest)();
+            take_buffer(&*vtable, buffer)
+        };
+        let manifest: PluginManifest =
+            serde_json::from_slice(&manifest).map_err(|e| PluginError::Manifest {
+                path: origin.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+        Ok(Self {
+            manifest,
+            vtable,
+            _library: library,
+        })
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    fn vtable(&self) -> &PluginVTable {
+        unsafe { &*self.vtable }
+    }
+}
+
+/// Copie un tampon du plugin puis le lui rend
+unsafe fn take_buffer(vtable: &PluginVTable, buffer: PluginBuffer) -> Vec<u8> {
+    let bytes = buffer.as_slice().to_vec();
+    (vtable.free)(buffer);
+    bytes
+}
+
+impl Module for PluginModule {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+    fn modality(&self) -> &str {
+        &self.manifest.modality
+    }
+    fn inputs(&self) -> Vec<Port> {
+        self.manifest.inputs.clone()
+    }
+    fn output(&self) -> Port {
+        self.manifest.output.clone()
+    }
+    fn depends_on(&self) -> Vec<String> {
+        self.manifest.depends_on.clone()
+    }
+    fn process(&self, input: &DataPacket) -> ModuleResult {
+        self.process_all(&[input])
+    }
+    fn process_all(&self, inputs: &[&DataPacket]) -> ModuleResult {
+        let request = serde_json::to_vec(inputs).map_err(|e| ModuleError::failed(e.to_string()))?;
+        let vtable = self.vtable();
+        let mut output = PluginBuffer::empty();
+        let status = (vtable.process)(request.as_ptr(), request.len(), &mut output);
+        // `process` écrit toujours `output` (résultat ou message d'erreur)
+        let body = unsafe { take_buffer(vtable, output) };
+        match status {
+            STATUS_OK => {
+                let invalid = |e: &dyn std::fmt::Display| {
+                    ModuleError::failed(format!("paquet renvoyé par le plugin invalide : {e}"))
+                };
+                let packet: DataPacket = serde_json::from_slice(&body).map_err(|e| invalid(&e))?;
+                packet.payload.check().map_err(|e| invalid(&e))?;
+                Ok(packet)
+            }
+            STATUS_NOT_UNDERSTOOD => {
+                Err(ModuleError::not_understood(String::from_utf8_lossy(&body)))
+            }
+            _ => Err(ModuleError::failed(String::from_utf8_lossy(&body))),
+        }
+    }
+}
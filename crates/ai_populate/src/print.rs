@@ -16,6 +16,39 @@ pub fn print_metas(enc: &Encoded) {
     }
 }
 
+pub fn print_graphemes(enc: &Encoded) {
+    println!("\n--- GRAPHÈMES (UAX #29) ---");
+    for (i, g) in enc.graphemes.iter().enumerate() {
+        println!(
+            "#{:02} {:?}  span={:?}  metas={:?} {:?}  cat={}  flags={:08b}",
+            i,
+            enc.grapheme_text(g),
+            g.byte_span,
+            g.meta_span,
+            enc.grapheme_metas(g)
+                .iter()
+                .map(|m| m.ch)
+                .collect::<Vec<_>>(),
+            enc.registry.name(g.cat_id),
+            g.flags
+        );
+    }
+}
+
+pub fn print_tokens(enc: &Encoded) {
+    println!("\n--- TOKENS (UAX #29) ---");
+    for (i, t) in enc.tokens().iter().enumerate() {
+        println!(
+            "#{:02} {:?}  span={:?}  kind={}  cat={}",
+            i,
+            enc.token_text(t),
+            t.byte_span,
+            t.kind,
+            enc.registry.name(t.cat_id)
+        );
+    }
+}
+
 pub fn print_metas_overrides(enc: &Encoded, ov: &HashMap<char, usize>) {
     println!("\n--- ASSOCIATIONS SYNTHÉTIQUES (groupes & overrides) ---");
     // Afficher chaque groupe d'association une seule fois
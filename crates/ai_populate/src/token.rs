@@ -0,0 +1,15 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::token_kind::TokenKind;
+
+/* ========= Token : segment de mots (UAX #29) au-dessus des CharMeta ========= */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    pub byte_span: Range<usize>,     // où il se trouve dans le buffer UTF-8
+    pub meta_span: Range<usize>,     // ses caractères dans Encoded::metas
+    pub grapheme_span: Range<usize>, // ses grappes dans Encoded::graphemes
+    pub kind: TokenKind,
+    pub cat_id: usize, // catégorie agrégée des grappes : majoritaire (la première en cas d'égalité)
+}
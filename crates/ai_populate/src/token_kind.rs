@@ -0,0 +1,55 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use unicode_categories::UnicodeCategories;
+
+/* ========= Nature d'un token (segment UAX #29) ========= */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenKind {
+    Word,
+    Number,
+    Punct,
+    Symbol,
+    Whitespace,
+    Other,
+}
+
+impl TokenKind {
+    /// Nature d'un segment : un mot dès qu'il contient une lettre
+    /// ("l'eau", "abc123"), un nombre s'il commence par un chiffre ("3.14")
+    pub fn of(segment: &str) -> Self {
+        if segment.chars().all(char::is_whitespace) {
+            TokenKind::Whitespace
+        } else if segment.chars().any(|c| c.is_letter()) {
+            TokenKind::Word
+        } else if segment.chars().any(|c| c.is_number()) {
+            TokenKind::Number
+        } else if segment.chars().any(|c| c.is_punctuation()) {
+            TokenKind::Punct
+        } else if segment.chars().any(|c| c.is_symbol()) {
+            TokenKind::Symbol
+        } else {
+            TokenKind::Other
+        }
+    }
+
+    /// Les segments consécutifs de ces natures forment un seul token
+    /// ("?!", "...") ; UAX #29 les sépare caractère par caractère
+    pub fn merges_runs(self) -> bool {
+        matches!(self, TokenKind::Punct | TokenKind::Whitespace)
+    }
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TokenKind::Word => "Word",
+            TokenKind::Number => "Number",
+            TokenKind::Punct => "Punct",
+            TokenKind::Symbol => "Symbol",
+            TokenKind::Whitespace => "Whitespace",
+            TokenKind::Other => "Other",
+        };
+        f.write_str(name)
+    }
+}
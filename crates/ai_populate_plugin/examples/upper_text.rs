@@ -0,0 +1,40 @@
+//! Plugin d'exemple : met le texte d'entrée en majuscules.
+//!
+//! `cargo build -p ai_populate_plugin --example upper_text`, puis
+//! `AI_POPULATE_PLUGINS=target/debug/examples/libupper_text.so`.
+
+use ai_populate_plugin::{export_plugin, Plugin, PluginFailure};
+use serde_json::{json, Value};
+
+struct UpperText;
+
+impl Plugin for UpperText {
+    fn manifest() -> String {
+        json!({
+            "name": "UpperTextPlugin",
+            "modality": "text",
+            "inputs": [{ "slot": "text", "kind": "Text" }],
+            "output": { "slot": "text_upper", "kind": "Text" },
+        })
+        .to_string()
+    }
+
+    fn process(inputs: &str) -> Result<String, PluginFailure> {
+        let inputs: Vec<Value> = serde_json::from_str(inputs)
+            .map_err(|e| PluginFailure::NotUnderstood(e.to_string()))?;
+        let packet = inputs
+            .first()
+            .ok_or_else(|| PluginFailure::NotUnderstood("aucune entrée".into()))?;
+        let text = packet["payload"]["value"]
+            .as_str()
+            .ok_or_else(|| PluginFailure::NotUnderstood("payload attendu : Text".into()))?;
+        Ok(json!({
+            "modality": "text",
+            "payload": { "kind": "Text", "value": text.to_uppercase() },
+            "meta": packet["meta"],
+        })
+        .to_string())
+    }
+}
+
+export_plugin!(UpperText);
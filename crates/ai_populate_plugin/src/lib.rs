@@ -0,0 +1,150 @@
+//! Interface binaire (C-ABI) des modules ai_populate chargés à chaud.
+//!
+//! Un plugin est une bibliothèque dynamique (`cdylib`) qui exporte le
+//! symbole `ai_populate_plugin`, renvoyant une table de fonctions
+//! (`PluginVTable`). Les échanges passent par des tampons d'octets au format
+//! JSON, indépendants de la version de Rust ou du langage du plugin :
+//!
+//! - `manifest` : `{"name", "modality", "inputs": [{"slot", "kind"}],
+//!   "output": {"slot", "kind"}, "depends_on": [..]}` ;
+//! - `process` : reçoit le tableau JSON des paquets d'entrée (dans l'ordre de
+//!   `inputs`) et produit un paquet JSON, ou un message d'erreur.
+//!
+//! Chaque tampon est libéré par le côté qui l'a alloué : l'hôte rend au
+//! plugin ceux qu'il a reçus via `free`.
+//!
+//! Côté plugin, implémenter `Plugin` puis `export_plugin!(MonPlugin);`.
+
+use std::panic;
+use std::slice;
+
+/// Version de l'interface ; l'hôte refuse les plugins d'une autre version
+pub const ABI_VERSION: u32 = 1;
+
+/// Symbole exporté par chaque plugin
+pub const ENTRY_POINT: &[u8] = b"ai_populate_plugin\0";
+
+/// Codes de retour de `process`
+pub const STATUS_OK: i32 = 0;
+pub const STATUS_NOT_UNDERSTOOD: i32 = 1;
+pub const STATUS_FAILED: i32 = 2;
+
+/// Tampon d'octets alloué par le plugin
+#[repr(C)]
+pub struct PluginBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl PluginBuffer {
+    pub fn from_vec(v: Vec<u8>) -> Self {
+        let mut v = std::mem::ManuallyDrop::new(v);
+        PluginBuffer {
+            ptr: v.as_mut_ptr(),
+            len: v.len(),
+            cap: v.capacity(),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    /// # Safety
+    /// Le tampon doit provenir de `from_vec` et ne pas avoir été libéré.
+    pub unsafe fn as_slice(&self) -> &[u8] {
+        slice::from_raw_parts(self.ptr, self.len)
+    }
+
+    /// # Safety
+    /// À n'appeler que dans le module qui a alloué le tampon (via `free`).
+    pub unsafe fn into_vec(self) -> Vec<u8> {
+        Vec::from_raw_parts(self.ptr, self.len, self.cap)
+    }
+}
+
+#[repr(C)]
+pub struct PluginVTable {
+    pub abi_version: u32,
+    pub manifest: extern "C" fn() -> PluginBuffer,
+    pub process: extern "C" fn(input: *const u8, len: usize, output: *mut PluginBuffer) -> i32,
+    pub free: extern "C" fn(buffer: PluginBuffer),
+}
+
+/// Signature du symbole `ai_populate_plugin`
+pub type EntryPoint = unsafe extern "C" fn() -> *const PluginVTable;
+
+/// Échec d'un plugin (traduit en `ModuleError` par l'hôte)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginFailure {
+    NotUnderstood(String),
+    Failed(String),
+}
+
+/// Module écrit côté plugin, sans état
+pub trait Plugin {
+    /// Manifeste JSON (voir la documentation du crate)
+    fn manifest() -> String;
+    /// Paquets d'entrée (tableau JSON) -> paquet de sortie (JSON)
+    fn process(inputs: &str) -> Result<String, PluginFailure>;
+}
+
+/// Implémentation de `PluginVTable::process` pour `P` : décode l'entrée,
+/// isole les paniques (qui ne doivent pas traverser la frontière C) et
+/// écrit le résultat ou le message d'erreur dans `output`.
+///
+/// # Safety
+/// `input` doit pointer sur `len` octets lisibles et `output` être valide
+/// en écriture.
+pub unsafe fn process_with<P: Plugin>(
+    input: *const u8,
+    len: usize,
+    output: *mut PluginBuffer,
+) -> i32 {
+    let bytes = slice::from_raw_parts(input, len);
+    let result = match std::str::from_utf8(bytes) {
+        Ok(text) => panic::catch_unwind(|| P::process(text))
+            .unwrap_or_else(|_| Err(PluginFailure::Failed("panique dans le plugin".into()))),
+        Err(e) => Err(PluginFailure::NotUnderstood(format!(
+            "entrée non UTF-8 : {e}"
+        ))),
+    };
+    let (status, body) = match result {
+        Ok(json) => (STATUS_OK, json),
+        Err(PluginFailure::NotUnderstood(msg)) => (STATUS_NOT_UNDERSTOOD, msg),
+        Err(PluginFailure::Failed(msg)) => (STATUS_FAILED, msg),
+    };
+    output.write(PluginBuffer::from_vec(body.into_bytes()));
+    status
+}
+
+/// Exporte `$plugin` (qui implémente `Plugin`) sous le symbole attendu
+#[macro_export]
+macro_rules! export_plugin {
+    ($plugin:ty) => {
+        #[no_mangle]
+        pub extern "C" fn ai_populate_plugin() -> *const $crate::PluginVTable {
+            extern "C" fn manifest() -> $crate::PluginBuffer {
+                $crate::PluginBuffer::from_vec(<$plugin as $crate::Plugin>::manifest().into_bytes())
+            }
+            extern "C" fn process(
+                input: *const u8,
+                len: usize,
+                output: *mut $crate::PluginBuffer,
+            ) -> i32 {
+                unsafe { $crate::process_with::<$plugin>(input, len, output) }
+            }
+            extern "C" fn free(buffer: $crate::PluginBuffer) {
+                drop(unsafe { buffer.into_vec() });
+            }
+            static VTABLE: $crate::PluginVTable = $crate::PluginVTable {
+                abi_version: $crate::ABI_VERSION,
+                manifest,
+                process,
+                free,
+            };
+            &VTABLE
+        }
+    };
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::edge::Edge;
+
+/// Index d'adjacence des faits courants (`Graph::edges`) : positions des
+/// arêtes par nœud source, par nœud cible et par relation, dans l'ordre
+/// d'insertion, et position de chaque triplet `(source, relation, cible)`.
+///
+/// L'index est tenu à jour sur place : un ajout enregistre sa position, un
+/// retrait (`swap_remove` dans `edges`) oublie la position retirée et
+/// renumérote la dernière arête, venue la remplacer. Seules les réécritures
+/// complètes (nouvelles règles, fusions) le reconstruisent.
+#[derive(Default)]
+pub struct Adjacency {
+    out: Vec<Vec<usize>>,
+    inc: Vec<Vec<usize>>,
+    by_rel: HashMap<String, Vec<usize>>,
+    // relation -> (source, cible) -> position (la plus ancienne en cas de doublon)
+    triples: HashMap<String, HashMap<(usize, usize), usize>>,
+}
+
+impl Adjacency {
+    pub fn build(edges: &[Edge]) -> Self {
+        let mut index = Self::default();
+        for (pos, e) in edges.iter().enumerate() {
+            index.insert(pos, e);
+        }
+        index
+    }
+
+    /// Enregistre l'arête `e`, rangée à la position `pos`.
+    pub fn insert(&mut self, pos: usize, e: &Edge) {
+        let n = e.from.max(e.to) + 1;
+        if self.out.len() < n {
+            self.out.resize_with(n, Vec::new);
+            self.inc.resize_with(n, Vec::new);
+        }
+        self.out[e.from].push(pos);
+        self.inc[e.to].push(pos);
+        self.by_rel.entry(e.rel.clone()).or_default().push(pos);
+        self.triples
+            .entry(e.rel.clone())
+            .or_default()
+            .entry((e.from, e.to))
+            .or_insert(pos);
+    }
+
+    /// Oublie l'arête à la position `pos`, encore présente dans `edges`.
+    pub fn remove(&mut self, pos: usize, edges: &[Edge]) {
+        let e = &edges[pos];
+        forget(&mut self.out[e.from], pos);
+        forget(&mut self.inc[e.to], pos);
+        if let Some(list) = self.by_rel.get_mut(&e.rel) {
+            forget(list, pos);
+        }
+        let Some(triples) = self.triples.get_mut(&e.rel) else {
+            return;
+        };
+        if triples.get(&(e.from, e.to)) == Some(&pos) {
+            // un doublon éventuel (fusion de nœuds) prend le relais
+            let twin = self.out[e.from]
+                .iter()
+                .copied()
+                .find(|&p| edges[p].rel == e.rel && edges[p].to == e.to);
+            match twin {
+                Some(p) => triples.insert((e.from, e.to), p),
+                None => triples.remove(&(e.from, e.to)),
+            };
+        }
+    }
+
+    /// L'arête `e` passe de la position `old` à la position `new`.
+    pub fn relocate(&mut self, old: usize, new: usize, e: &Edge) {
+        renumber(&mut self.out[e.from], old, new);
+        renumber(&mut self.inc[e.to], old, new);
+        if let Some(list) = self.by_rel.get_mut(&e.rel) {
+            renumber(list, old, new);
+        }
+        if let Some(p) = self
+            .triples
+            .get_mut(&e.rel)
+            .and_then(|t| t.get_mut(&(e.from, e.to)))
+        {
+            if *p == old {
+                *p = new;
+            }
+        }
+    }
+
+    pub fn outgoing(&self, node: usize) -> &[usize] {
+        self.out.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn incoming(&self, node: usize) -> &[usize] {
+        self.inc.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn with_rel(&self, rel: &str) -> &[usize] {
+        self.by_rel.get(rel).map_or(&[], Vec::as_slice)
+    }
+
+    /// Position d'une arête courante `from rel to`, en O(1).
+    pub fn position(&self, from: usize, rel: &str, to: usize) -> Option<usize> {
+        self.triples.get(rel)?.get(&(from, to)).copied()
+    }
+}
+
+/// Retire `pos` d'une liste en gardant l'ordre d'insertion des autres.
+fn forget(list: &mut Vec<usize>, pos: usize) {
+    if let Some(i) = list.iter().position(|&p| p == pos) {
+        list.remove(i);
+    }
+}
+
+fn renumber(list: &mut [usize], old: usize, new: usize) {
+    if let Some(p) = list.iter_mut().find(|p| **p == old) {
+        *p = new;
+    }
+}
@@ -0,0 +1,377 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::ann::hnsw_params::HnswParams;
+use crate::embedding::cosine;
+
+/// Candidat (distance, id) ordonné par distance.
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    dist: f32,
+    id: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist
+            .total_cmp(&other.dist)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+/// Index ANN « Hierarchical Navigable Small World » sur distance cosinus.
+///
+/// Les ids sont ceux du `Graph` (denses, attribués dans l'ordre d'insertion).
+/// L'index garde sa propre copie des vecteurs.
+pub struct Hnsw {
+    params: HnswParams,
+    vectors: Vec<Vec<f32>>,
+    // links[id][couche] = voisins
+    links: Vec<Vec<Vec<usize>>>,
+    entry: Option<usize>,
+    max_level: usize,
+    rng: u64,
+}
+
+impl Hnsw {
+    pub fn new(params: HnswParams) -> Self {
+        let rng = params.seed | 1;
+        Self {
+            params,
+            vectors: vec![],
+            links: vec![],
+            entry: None,
+            max_level: 0,
+            rng,
+        }
+    }
+
+    pub fn params(&self) -> &HnswParams {
+        &self.params
+    }
+
+    /// Ajuste le compromis rappel / latence des requêtes.
+    pub fn set_ef_search(&mut self, ef: usize) {
+        self.params.ef_search = ef.max(1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    /// Insère le vecteur du nœud `id` (qui doit valoir `self.len()`).
+    pub fn insert(&mut self, id: usize, v: &[f32]) {
+        assert_eq!(id, self.vectors.len(), "ids HNSW attribués dans l'ordre");
+        let level = self.random_level();
+        self.vectors.push(v.to_vec());
+        self.links.push(vec![vec![]; level + 1]);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(id);
+            self.max_level = level;
+            return;
+        };
+        self.connect(id, entry);
+        if level > self.max_level {
+            self.entry = Some(id);
+            self.max_level = level;
+        }
+    }
+
+    /// Remplace le vecteur du nœud `id` et recalcule ses voisins.
+    ///
+    /// Les liens entrants obsolètes sont conservés : ils ne faussent pas les
+    /// distances (toujours recalculées) et préservent la connexité du graphe.
+    pub fn update(&mut self, id: usize, v: &[f32]) {
+        self.vectors[id] = v.to_vec();
+        if self.vectors.len() == 1 {
+            return;
+        }
+        let entry = self.entry.expect("index non vide");
+        self.connect(id, entry);
+    }
+
+    /// Les `k` plus proches voisins approchés de `q`, par similarité décroissante.
+    pub fn search(&self, q: &[f32], k: usize) -> Vec<(usize, f32)> {
+        let Some(entry) = self.entry else {
+            return vec![];
+        };
+        let mut ep = entry;
+        for layer in (1..=self.max_level).rev() {
+            ep = self.search_layer(q, &[ep], 1, layer)[0].id;
+        }
+        let ef = self.params.ef_search.max(k);
+        self.search_layer(q, &[ep], ef, 0)
+            .into_iter()
+            .take(k)
+            .map(|c| (c.id, 1.0 - c.dist))
+            .collect()
+    }
+
+    fn dist(&self, q: &[f32], id: usize) -> f32 {
+        1.0 - cosine(q, &self.vectors[id])
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.params.m * 2
+        } else {
+            self.params.m
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        // xorshift64*
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let r = self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        let u = ((r >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let ml = 1.0 / (self.params.m.max(2) as f64).ln();
+        (-u.ln() * ml).floor() as usize
+    }
+
+    /// (Re)calcule les voisins de `id` sur toutes ses couches, à partir de `entry`.
+    fn connect(&mut self, id: usize, entry: usize) {
+        let q = self.vectors[id].clone();
+        let level = self.links[id].len() - 1;
+
+        let mut ep = entry;
+        for layer in (level + 1..=self.max_level).rev() {
+            ep = self.search_layer(&q, &[ep], 1, layer)[0].id;
+        }
+
+        let mut eps = vec![ep];
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let cands: Vec<Candidate> = self
+                .search_layer(&q, &eps, self.params.ef_construction, layer)
+                .into_iter()
+                .filter(|c| c.id != id)
+                .collect();
+            if cands.is_empty() {
+                continue;
+            }
+            let neigh = self.select_neighbors(&cands, self.max_links(layer));
+            for &n in &neigh {
+                if !self.links[n][layer].contains(&id) {
+                    self.links[n][layer].push(id);
+                }
+                if self.links[n][layer].len() > self.max_links(layer) {
+                    self.prune(n, layer);
+                }
+            }
+            self.links[id][layer] = neigh;
+            eps = cands.iter().map(|c| c.id).collect();
+        }
+    }
+
+    fn prune(&mut self, n: usize, layer: usize) {
+        let v = self.vectors[n].clone();
+        let mut cands: Vec<Candidate> = self.links[n][layer]
+            .iter()
+            .map(|&id| Candidate {
+                dist: self.dist(&v, id),
+                id,
+            })
+            .collect();
+        cands.sort();
+        self.links[n][layer] = self.select_neighbors(&cands, self.max_links(layer));
+    }
+
+    /// Heuristique de diversité de l'article HNSW : un candidat n'est gardé
+    /// que s'il est plus proche de la requête que des voisins déjà retenus ;
+    /// on complète ensuite avec les candidats écartés. `cands` est trié.
+    fn select_neighbors(&self, cands: &[Candidate], m: usize) -> Vec<usize> {
+        let mut kept: Vec<usize> = Vec::with_capacity(m);
+        let mut pruned: Vec<usize> = vec![];
+        for c in cands {
+            if kept.len() >= m {
+                break;
+            }
+            let diverse = kept
+                .iter()
+                .all(|&r| c.dist < self.dist(&self.vectors[c.id], r));
+            if diverse {
+                kept.push(c.id);
+            } else {
+                pruned.push(c.id);
+            }
+        }
+        for id in pruned {
+            if kept.len() >= m {
+                break;
+            }
+            kept.push(id);
+        }
+        kept
+    }
+
+    /// Recherche gloutonne en faisceau sur une couche ; résultat trié par distance.
+    fn search_layer(&self, q: &[f32], eps: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &ep in eps {
+            if visited.insert(ep) {
+                let c = Candidate {
+                    dist: self.dist(q, ep),
+                    id: ep,
+                };
+                candidates.push(Reverse(c));
+                results.push(c);
+            }
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(c)) = candidates.pop() {
+            let worst = results.peek().map(|w| w.dist).unwrap_or(f32::INFINITY);
+            if c.dist > worst && results.len() >= ef {
+                break;
+            }
+            let Some(neigh) = self.links[c.id].get(layer) else {
+                continue;
+            };
+            for &n in neigh {
+                if !visited.insert(n) {
+                    continue;
+                }
+                let d = self.dist(q, n);
+                let worst = results.peek().map(|w| w.dist).unwrap_or(f32::INFINITY);
+                if results.len() < ef || d < worst {
+                    let nc = Candidate { dist: d, id: n };
+                    candidates.push(Reverse(nc));
+                    results.push(nc);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::l2_normalize;
+    use crate::graph::Graph;
+
+    fn random_vectors(n: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut s = seed;
+        let mut next = || {
+            s = s
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((s >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+        };
+        (0..n)
+            .map(|_| {
+                let mut v: Vec<f32> = (0..dim).map(|_| next()).collect();
+                l2_normalize(&mut v);
+                v
+            })
+            .collect()
+    }
+
+    fn exact(vectors: &[Vec<f32>], q: &[f32], k: usize) -> Vec<usize> {
+        let mut sims: Vec<(usize, f32)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, cosine(v, q)))
+            .collect();
+        sims.sort_by(|a, b| b.1.total_cmp(&a.1));
+        sims.into_iter().take(k).map(|(i, _)| i).collect()
+    }
+
+    fn recall(index: &Hnsw, vectors: &[Vec<f32>], queries: &[Vec<f32>], k: usize) -> f32 {
+        let mut hits = 0;
+        for q in queries {
+            let truth: HashSet<usize> = exact(vectors, q, k).into_iter().collect();
+            hits += index
+                .search(q, k)
+                .iter()
+                .filter(|(id, _)| truth.contains(id))
+                .count();
+        }
+        hits as f32 / (queries.len() * k) as f32
+    }
+
+    #[test]
+    fn recall_against_exact_scan() {
+        let k = 10;
+        let mut vectors = random_vectors(1500, 16, 42);
+        let queries = random_vectors(100, 16, 7);
+
+        let params = HnswParams {
+            ef_construction: 100,
+            ..HnswParams::default()
+        };
+        let mut index = Hnsw::new(params);
+        for (i, v) in vectors.iter().enumerate() {
+            index.insert(i, v);
+        }
+        let r = recall(&index, &vectors, &queries, k);
+        assert!(r >= 0.9, "rappel HNSW trop faible : {r}");
+
+        // un ef_search minuscule dégrade le rappel, sans le rendre nul
+        index.set_ef_search(1);
+        let low = recall(&index, &vectors, &queries, k);
+        assert!(low <= r);
+        index.set_ef_search(HnswParams::default().ef_search);
+
+        // les mises à jour d'embedding restent cohérentes avec le scan exact
+        let moved = random_vectors(150, 16, 99);
+        for (i, v) in moved.into_iter().enumerate() {
+            let id = i * 10;
+            index.update(id, &v);
+            vectors[id] = v;
+        }
+        let r = recall(&index, &vectors, &queries, k);
+        assert!(r >= 0.9, "rappel HNSW après mises à jour : {r}");
+    }
+
+    #[test]
+    fn graph_knn_stays_in_sync() {
+        let mut g = Graph::new();
+        for i in 0..200 {
+            g.add_node(&format!("entité_{i}"));
+        }
+        g.enable_ann(HnswParams::default());
+        for i in 200..400 {
+            g.add_node(&format!("entité_{i}"));
+        }
+        for i in (0..400).step_by(7) {
+            g.set_node_embedding_ema(&format!("entité_{i}"), &format!("planète naine {i}"), 0.9);
+        }
+
+        // l'index doit suivre le scan exact après insertions et mises à jour
+        let k = 10;
+        let mut hits = 0;
+        let queries: Vec<usize> = (0..400).step_by(13).collect();
+        for &id in &queries {
+            let q = g.nodes[id].emb.clone();
+            let truth: HashSet<usize> = g.k_nn_exact(&q, k).into_iter().map(|(i, _)| i).collect();
+            let approx = g.k_nn(&q, k);
+            assert_eq!(approx.len(), k);
+            hits += approx.iter().filter(|(i, _)| truth.contains(i)).count();
+        }
+        let r = hits as f32 / (queries.len() * k) as f32;
+        assert!(r >= 0.9, "rappel du graphe après mises à jour : {r}");
+    }
+}
@@ -0,0 +1,23 @@
+/// Paramètres de l'index HNSW (compromis rappel / latence).
+#[derive(Clone, Debug)]
+pub struct HnswParams {
+    /// Nombre max de voisins par nœud sur les couches hautes (2*m sur la couche 0).
+    pub m: usize,
+    /// Taille de la liste de candidats à la construction (plus grand = meilleur graphe, insertion plus lente).
+    pub ef_construction: usize,
+    /// Taille de la liste de candidats à la recherche (plus grand = meilleur rappel, requête plus lente).
+    pub ef_search: usize,
+    /// Graine du tirage des niveaux (construction déterministe).
+    pub seed: u64,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+            seed: 0x5eed_1234,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod hnsw;
+pub mod hnsw_params;
+
+pub use hnsw::Hnsw;
+pub use hnsw_params::HnswParams;
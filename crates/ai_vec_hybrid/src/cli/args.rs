@@ -0,0 +1,155 @@
+use std::env;
+use std::path::PathBuf;
+
+use ai_vec_hybrid::time::{self, Timestamp};
+
+use crate::cli::cli_error::{usage, CliResult};
+
+pub const USAGE: &str = "\
+usage : ai_vec_hybrid [options] <commande> [arguments]
+
+options :
+  --store <dir>       répertoire de stockage (défaut : $AI_VEC_STORE ou crates/ai_vec_hybrid/data)
+  --embedder <spec>   hashing[:dim] | tfidf[:dim] | vectors:<fichier> (défaut : $AI_VEC_EMBEDDER ou hashing)
+                      tfidf : IDF appris au premier chargement, conservé dans le stockage
+  --rules <fichier>   règles d'inférence à appliquer (défaut : $AI_VEC_RULES)
+  --schema <fichier>  schéma des relations à faire respecter (défaut : $AI_VEC_SCHEMA)
+  --json              sortie JSON (pour les scripts)
+
+commandes :
+  load <fichier>... [--format f] [--source s]      importe des faits (.json .nt .ttl .csv .jsonld)
+  search <texte> [-k n] [--hybrid] [--as-of date]  recherche k-NN textuelle
+  names <texte> [-k n] [--prefix] [--distance d]   noms de nœuds proches (complétion, fautes de frappe)
+  facts <nœud> [--out|--in] [--as-of date]         faits sortants et entrants
+  correct <sujet> <rel> <ancien> <nouveau> [--rel r] [--at date]
+                                                   remplace un fait (l'ancien reste dans l'historique)
+  reembed <nœud> <texte> [--alpha a]               mise à jour EMA de l'embedding
+  feedback <texte> [--relevant a,b] [--irrelevant c] [--max-step d] [--max-drift d]
+                                                   retour de pertinence sur une recherche
+  history <nœud>                                   versions de l'embedding d'un nœud
+  rollback <nœud> <version>                        revient à une version de l'embedding
+  stats                                            statistiques du graphe et du stockage
+  query [fichier]                                  requêtes (REPL sans fichier)
+  export <fichier> [--format f]                    exporte les faits de base
+  resolve [--apply]                                doublons probables (et fusion)
+  conflicts [--resolve]                            conflits sur les relations fonctionnelles
+  check                                            vérifie le graphe contre le schéma
+  train [--model transe|distmult] [--dim n] [--epochs n] [--save f] [--blend w]
+                                                   apprend des embeddings sur les faits
+  predict <sujet> <rel> ? | ? <rel> <objet> [-k n] [--load f]
+                                                   prédiction de liens (sans --load : entraîne
+                                                   avec les options de train)
+  serve [--addr hôte:port]                         serveur HTTP/JSON (défaut : 127.0.0.1:7878)
+  demo                                             démonstration Pluton";
+
+/// Options globales, valables pour toutes les commandes.
+#[derive(Clone)]
+pub struct GlobalOpts {
+    pub store: PathBuf,
+    pub embedder: String,
+    pub rules: Option<PathBuf>,
+    pub schema: Option<PathBuf>,
+    pub json: bool,
+}
+
+/// Ligne de commande découpée : options globales, commande, et arguments
+/// restants de la commande (lus avec `opt`, `flag` puis `positional`).
+pub struct Args {
+    pub global: GlobalOpts,
+    pub command: Option<String>,
+    rest: Vec<String>,
+}
+
+impl Args {
+    /// Les options globales sont acceptées à n'importe quelle position.
+    pub fn parse(argv: impl IntoIterator<Item = String>) -> CliResult<Args> {
+        let mut global = GlobalOpts {
+            store: env::var_os("AI_VEC_STORE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("crates/ai_vec_hybrid/data")),
+            embedder: env::var("AI_VEC_EMBEDDER").unwrap_or_else(|_| "hashing".into()),
+            rules: env::var_os("AI_VEC_RULES").map(PathBuf::from),
+            schema: env::var_os("AI_VEC_SCHEMA").map(PathBuf::from),
+            json: false,
+        };
+        let mut rest = vec![];
+        let mut it = argv.into_iter();
+        while let Some(arg) = it.next() {
+            let mut value = |name: &str| match it.next() {
+                Some(v) => Ok(v),
+                None => usage(format!("{name} attend une valeur")),
+            };
+            match arg.as_str() {
+                "--store" => global.store = PathBuf::from(value("--store")?),
+                "--embedder" => global.embedder = value("--embedder")?,
+                "--rules" => global.rules = Some(PathBuf::from(value("--rules")?)),
+                "--schema" => global.schema = Some(PathBuf::from(value("--schema")?)),
+                "--json" => global.json = true,
+                "-h" | "--help" => rest.insert(0, "help".into()),
+                _ => rest.push(arg),
+            }
+        }
+        let command = (!rest.is_empty()).then(|| rest.remove(0));
+        Ok(Args {
+            global,
+            command,
+            rest,
+        })
+    }
+
+    /// Retire `name <valeur>` des arguments.
+    pub fn opt(&mut self, name: &str) -> CliResult<Option<String>> {
+        let Some(pos) = self.rest.iter().position(|a| a == name) else {
+            return Ok(None);
+        };
+        if pos + 1 >= self.rest.len() {
+            return usage(format!("{name} attend une valeur"));
+        }
+        let v = self.rest.remove(pos + 1);
+        self.rest.remove(pos);
+        Ok(Some(v))
+    }
+
+    pub fn opt_parse<T: std::str::FromStr>(&mut self, name: &str) -> CliResult<Option<T>> {
+        match self.opt(name)? {
+            Some(v) => match v.parse() {
+                Ok(x) => Ok(Some(x)),
+                Err(_) => usage(format!("{name} : valeur invalide '{v}'")),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Date `AAAA-MM-JJ`.
+    pub fn opt_date(&mut self, name: &str) -> CliResult<Option<Timestamp>> {
+        match self.opt(name)? {
+            Some(v) => match time::parse_date(&v) {
+                Some(t) => Ok(Some(t)),
+                None => usage(format!("{name} : date invalide '{v}' (AAAA-MM-JJ)")),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Retire le drapeau `name` ; vrai s'il était présent.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let before = self.rest.len();
+        self.rest.retain(|a| a != name);
+        self.rest.len() != before
+    }
+
+    /// Arguments positionnels restants ; une option non consommée est une erreur.
+    pub fn positional(&mut self, min: usize, max: usize) -> CliResult<Vec<String>> {
+        if let Some(o) = self.rest.iter().find(|a| a.starts_with("--") || *a == "-k") {
+            return usage(format!("option inconnue '{o}'"));
+        }
+        let n = self.rest.len();
+        if n < min || n > max {
+            let cmd = self.command.as_deref().unwrap_or_default();
+            return usage(format!(
+                "{cmd} : nombre d'arguments incorrect (voir --help)"
+            ));
+        }
+        Ok(std::mem::take(&mut self.rest))
+    }
+}
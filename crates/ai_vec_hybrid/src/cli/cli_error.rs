@@ -0,0 +1,38 @@
+use ai_vec_hybrid::feedback::FeedbackError;
+use ai_vec_hybrid::formats::FormatError;
+use ai_vec_hybrid::kge::KgeError;
+use ai_vec_hybrid::query::QueryError;
+use ai_vec_hybrid::resolve::MergeError;
+use ai_vec_hybrid::schema::SchemaError;
+use ai_vec_hybrid::store::StoreError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CliError {
+    /// Ligne de commande invalide (code de sortie 2).
+    #[error("{0}")]
+    Usage(String),
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error("{path} : {source}")]
+    Format { path: String, source: FormatError },
+    #[error(transparent)]
+    Merge(#[from] MergeError),
+    #[error(transparent)]
+    Kge(#[from] KgeError),
+    #[error(transparent)]
+    Feedback(#[from] FeedbackError),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error("requête invalide, {0}")]
+    Query(#[from] QueryError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    NotFound(String),
+}
+
+pub type CliResult<T> = Result<T, CliError>;
+
+pub fn usage<T>(message: impl Into<String>) -> CliResult<T> {
+    Err(CliError::Usage(message.into()))
+}
@@ -0,0 +1,913 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use ai_vec_hybrid::edge::Edge;
+use ai_vec_hybrid::embedders::{self, Embedder, TfIdfEmbedder};
+use ai_vec_hybrid::embedding::cosine;
+use ai_vec_hybrid::fact_row::FactRow;
+use ai_vec_hybrid::feedback::FeedbackParams;
+use ai_vec_hybrid::formats::{read_facts, FactIter, Format, Namespace};
+use ai_vec_hybrid::graph::Graph;
+use ai_vec_hybrid::hybrid::HybridQuery;
+use ai_vec_hybrid::kge::{KgeEmbeddings, KgeParams};
+use ai_vec_hybrid::resolve::ResolveParams;
+use ai_vec_hybrid::rules::RuleSet;
+use ai_vec_hybrid::schema::GraphSchema;
+use ai_vec_hybrid::server::GraphServer;
+use ai_vec_hybrid::store::GraphStore;
+use ai_vec_hybrid::time;
+
+use crate::cli::args::{Args, GlobalOpts, USAGE};
+use crate::cli::cli_error::{usage, CliError, CliResult};
+use crate::cli::demo;
+use crate::cli::reports::*;
+
+pub fn run(mut args: Args) -> CliResult<()> {
+    match args.command.clone().as_deref() {
+        None | Some("help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some("load") => load(args),
+        Some("search") => search(args),
+        Some("names") => names(args),
+        Some("facts") => facts(args),
+        Some("correct") => correct(args),
+        Some("reembed") => reembed(args),
+        Some("feedback") => feedback(args),
+        Some("history") => history(args),
+        Some("rollback") => rollback(args),
+        Some("stats") => stats(args),
+        Some("query") => query(args),
+        Some("export") => export(args),
+        Some("resolve") => resolve(args),
+        Some("conflicts") => conflicts(args),
+        Some("check") => check(args),
+        Some("train") => train(args),
+        Some("predict") => predict(args),
+        Some("serve") => serve(args),
+        Some("demo") => {
+            args.positional(0, 0)?;
+            demo::run(&args.global)
+        }
+        Some(other) => usage(format!("commande inconnue '{other}'\n\n{USAGE}")),
+    }
+}
+
+/// Sortie texte ou JSON selon `--json`.
+fn emit<T: Serialize + Display>(report: &T, json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(report).expect("rapport sérialisable")
+        );
+    } else {
+        println!("{report}");
+    }
+}
+
+/// Fichier du modèle `tfidf`, dans le répertoire de stockage.
+const TFIDF_MODEL: &str = "tfidf.json";
+
+/// Faits insérés entre deux écritures du WAL pendant `load`.
+const LOAD_BATCH: usize = 1000;
+
+/// Embedder de `--embedder`. L'IDF de `tfidf` est appris une seule fois, sur
+/// les noms déjà stockés puis `corpus`, et conservé dans le stockage : le
+/// rejeu du WAL, les requêtes et les chargements suivants pondèrent ainsi
+/// les termes de la même façon. `corpus` n'est pas lu si le modèle existe.
+fn embedder(
+    global: &GlobalOpts,
+    corpus: impl IntoIterator<Item = String>,
+) -> CliResult<Box<dyn Embedder>> {
+    let bad_spec = |e: io::Error| CliError::Usage(format!("--embedder : {e}"));
+    let kind = global.embedder.split(':').next().unwrap_or_default();
+    if kind != "tfidf" {
+        return embedders::from_spec(&global.embedder, []).map_err(bad_spec);
+    }
+    let dim =
+        embedders::spec_dim(&global.embedder, TfIdfEmbedder::DEFAULT_DIM).map_err(bad_spec)?;
+    let path = global.store.join(TFIDF_MODEL);
+    if path.exists() {
+        let model = TfIdfEmbedder::load(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{} : {e}", path.display())))?;
+        if global.embedder.contains(':') && model.dim() != dim {
+            return usage(format!(
+                "--embedder {} : le modèle de {} a la dimension {}",
+                global.embedder,
+                path.display(),
+                model.dim()
+            ));
+        }
+        return Ok(Box::new(model));
+    }
+    let names = GraphStore::node_names(&global.store)?
+        .into_iter()
+        .chain(corpus);
+    let model = TfIdfEmbedder::fit(names, dim);
+    // un modèle appris sur rien ne pondère rien : on attend des faits
+    if model.vocabulary_size() > 0 {
+        model.save(&path)?;
+    }
+    Ok(Box::new(model))
+}
+
+/// Ouvre le stockage avec l'embedder, les règles et le schéma demandés.
+/// `corpus` : noms sur le point d'être chargés, pour apprendre l'IDF de
+/// `tfidf` si le stockage n'a pas encore de modèle.
+pub fn open_store(
+    global: &GlobalOpts,
+    corpus: impl IntoIterator<Item = String>,
+) -> CliResult<(GraphStore, Graph)> {
+    let embedder = embedder(global, corpus)?;
+    let (store, mut g) = GraphStore::open_with(&global.store, Graph::with_embedder(embedder))?;
+    if let Some(path) = &global.rules {
+        let rules = RuleSet::load(path)
+            .map_err(|e| CliError::Usage(format!("--rules {} : {e}", path.display())))?;
+        g.set_rules(rules);
+    }
+    if let Some(path) = &global.schema {
+        let schema = GraphSchema::load(path)
+            .map_err(|e| CliError::Usage(format!("--schema {} : {e}", path.display())))?;
+        g.set_schema(schema);
+    }
+    Ok((store, g))
+}
+
+/// Nom canonique d'un nœud (nom exact, alias ou forme normalisée).
+fn node_name(g: &Graph, name: &str) -> CliResult<String> {
+    if let Some(id) = g.resolve_name(name) {
+        return Ok(g.nodes[id].name.clone());
+    }
+    let close: Vec<String> = g
+        .lookup_names(name, 3)
+        .into_iter()
+        .map(|m| m.node)
+        .collect();
+    Err(CliError::NotFound(if close.is_empty() {
+        format!("nœud inconnu '{name}'")
+    } else {
+        format!("nœud inconnu '{name}' (proches : {})", close.join(", "))
+    }))
+}
+
+fn parse_format(args: &mut Args) -> CliResult<Option<Format>> {
+    match args.opt("--format")? {
+        Some(f) => f.parse().map(Some).map_err(CliError::Usage),
+        None => Ok(None),
+    }
+}
+
+fn load(mut args: Args) -> CliResult<()> {
+    let format = parse_format(&mut args)?;
+    let source = args.opt("--source")?;
+    let json = args.global.json;
+    let paths = args.positional(1, usize::MAX)?;
+
+    let mut inputs = vec![];
+    for path in &paths {
+        let Some(fmt) = format.or_else(|| Format::from_path(Path::new(path))) else {
+            return usage(format!("{path} : format inconnu, préciser --format"));
+        };
+        inputs.push((path.as_str(), fmt));
+    }
+    let read = |path: &str, fmt: Format| -> CliResult<FactIter<'static>> {
+        let input = BufReader::new(File::open(path).map_err(|e| CliError::Format {
+            path: path.to_string(),
+            source: e.into(),
+        })?);
+        Ok(read_facts(fmt, input, &Namespace::default()))
+    };
+
+    // l'IDF de tfidf, s'il reste à apprendre, l'est sur une première lecture
+    // en flux ; les erreurs sont signalées par la seconde
+    let corpus = inputs.iter().flat_map(|&(path, fmt)| {
+        read(path, fmt)
+            .into_iter()
+            .flatten()
+            .map_while(Result::ok)
+            .flat_map(|f| [f.subj, f.obj])
+    });
+    let (mut store, mut g) = open_store(&args.global, corpus)?;
+
+    // faits insérés au fil de la lecture, journalisés par lots ; ceux lus
+    // avant une erreur sont gardés, mais la commande échoue
+    let mut files = vec![];
+    let mut failure = None;
+    for &(path, fmt) in &inputs {
+        let mut n = 0;
+        let mut error = None;
+        for r in read(path, fmt)? {
+            match r {
+                Ok(mut f) => {
+                    if f.provenance.source.is_none() {
+                        f.provenance.source = Some(source.clone().unwrap_or_else(|| path.into()));
+                    }
+                    g.add_fact(&f);
+                    n += 1;
+                    if n % LOAD_BATCH == 0 {
+                        store.commit(&mut g)?;
+                    }
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+        store.commit(&mut g)?;
+        files.push(LoadedFile {
+            path: path.to_string(),
+            format: fmt.to_string(),
+            facts: n,
+            error: error.as_ref().map(ToString::to_string),
+        });
+        if let Some(source) = error {
+            failure.get_or_insert(CliError::Format {
+                path: path.to_string(),
+                source,
+            });
+        }
+    }
+
+    // validation d'ensemble : l'ordre des faits d'un fichier ne compte pas
+    g.take_violations();
+    let schema = g.schema().map(|_| g.check_schema());
+    emit(
+        &LoadReport {
+            files,
+            nodes: g.nodes.iter().filter(|n| n.merged_into.is_none()).count(),
+            facts: g.edges.iter().filter(|e| !e.derived).count(),
+            schema,
+        },
+        json,
+    );
+    failure.map_or(Ok(()), Err)
+}
+
+fn search(mut args: Args) -> CliResult<()> {
+    let k = args.opt_parse::<usize>("-k")?.unwrap_or(5);
+    let hybrid = args.flag("--hybrid");
+    let as_of = args.opt_date("--as-of")?;
+    let json = args.global.json;
+    let text = args.positional(1, usize::MAX)?.join(" ");
+    let (_store, g) = open_store(&args.global, [])?;
+
+    if hybrid {
+        let hq = HybridQuery {
+            limit: k,
+            ..HybridQuery::default()
+        };
+        let hits = g.hybrid_search(&text, &hq);
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&hits).expect("sérialisable")
+            );
+        } else {
+            for hit in &hits {
+                print!("{:>8.3}  {}  via {}", hit.score, hit.node, hit);
+                if hit.confidence() < 1.0 {
+                    print!("  (confiance {:.2})", hit.confidence());
+                }
+                println!();
+            }
+        }
+        return Ok(());
+    }
+    let hits = match as_of {
+        Some(t) => g.search_text_as_of(&text, k, t),
+        None => g.search_text(&text, k),
+    };
+    let report = SearchReport {
+        query: text,
+        hits: hits
+            .into_iter()
+            .map(|(name, score)| SearchHit {
+                sources: g.sources_of(&name).into_iter().map(String::from).collect(),
+                name,
+                score,
+            })
+            .collect(),
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+fn names(mut args: Args) -> CliResult<()> {
+    let k = args.opt_parse::<usize>("-k")?.unwrap_or(10);
+    let prefix = args.flag("--prefix");
+    let distance = args.opt_parse::<usize>("--distance")?;
+    let json = args.global.json;
+    let text = args.positional(1, usize::MAX)?.join(" ");
+    let (_store, g) = open_store(&args.global, [])?;
+    let matches = match (prefix, distance) {
+        (true, _) => g.complete(&text, k),
+        (false, Some(d)) => g.fuzzy_names(&text, d, k),
+        (false, None) => g.lookup_names(&text, k),
+    };
+    emit(&NamesReport { text, matches }, json);
+    Ok(())
+}
+
+fn facts(mut args: Args) -> CliResult<()> {
+    let (only_out, only_in) = (args.flag("--out"), args.flag("--in"));
+    let as_of = args.opt_date("--as-of")?;
+    let json = args.global.json;
+    let name = args.positional(1, 1)?.remove(0);
+    let (_store, g) = open_store(&args.global, [])?;
+    let node = node_name(&g, &name)?;
+
+    let rows = |edges: Vec<&Edge>| -> Vec<FactRow> {
+        edges.into_iter().map(|e| FactRow::new(&g, e)).collect()
+    };
+    let (outgoing, incoming) = match as_of {
+        Some(t) => (g.outgoing_as_of(&node, t), g.incoming_as_of(&node, t)),
+        None => (g.outgoing(&node), g.incoming(&node)),
+    };
+    let report = FactsReport {
+        aliases: g
+            .aliases(g.name2id[&node])
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        as_of: as_of.map(time::format_date),
+        outgoing: (!only_in).then(|| rows(outgoing)),
+        incoming: (!only_out).then(|| rows(incoming)),
+        node,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+fn correct(mut args: Args) -> CliResult<()> {
+    let new_rel = args.opt("--rel")?;
+    let at = args.opt_date("--at")?.unwrap_or_else(time::now);
+    let json = args.global.json;
+    let [subj, rel, old, new]: [String; 4] = args
+        .positional(4, 4)?
+        .try_into()
+        .expect("4 arguments vérifiés");
+    let new_rel = new_rel.unwrap_or_else(|| rel.clone());
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let subj = node_name(&g, &subj)?;
+    let old = node_name(&g, &old)?;
+    let exists = g
+        .outgoing(&subj)
+        .iter()
+        .any(|e| !e.derived && e.rel == rel && g.nodes[e.to].name == old);
+    if !exists {
+        return Err(CliError::NotFound(format!(
+            "fait introuvable : {subj} --{rel}--> {old}"
+        )));
+    }
+    g.update_fact_checked(&subj, &rel, &old, &new_rel, &new, at)?;
+    store.commit(&mut g)?;
+
+    let report = CorrectReport {
+        at: time::format_date(at),
+        closed: stored_fact(&g, &subj, &rel, &old, |e| e.valid_to == Some(at))?,
+        added: stored_fact(&g, &subj, &new_rel, &new, |e| e.valid_to.is_none())?,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+/// Fait de base `subj --rel--> obj` tel qu'enregistré dans le graphe : parmi
+/// les faits courants, puis dans l'historique (version la plus récente
+/// d'abord), la première version non retirée acceptée par `keep`.
+fn stored_fact(
+    g: &Graph,
+    subj: &str,
+    rel: &str,
+    obj: &str,
+    keep: impl Fn(&Edge) -> bool,
+) -> CliResult<FactRow> {
+    let missing = || CliError::NotFound(format!("fait introuvable : {subj} --{rel}--> {obj}"));
+    let (Some(from), Some(to)) = (g.resolve_name(subj), g.resolve_name(obj)) else {
+        return Err(missing());
+    };
+    g.out_edges(from)
+        .chain(g.history.iter().rev())
+        .find(|e| {
+            e.from == from
+                && e.rel == rel
+                && e.to == to
+                && !e.derived
+                && e.retracted_at.is_none()
+                && keep(e)
+        })
+        .map(|e| FactRow::new(g, e))
+        .ok_or_else(missing)
+}
+
+fn reembed(mut args: Args) -> CliResult<()> {
+    let alpha = args.opt_parse::<f32>("--alpha")?.unwrap_or(0.3);
+    if !(0.0..=1.0).contains(&alpha) {
+        return usage("--alpha doit être dans [0, 1]");
+    }
+    let json = args.global.json;
+    let mut pos = args.positional(2, usize::MAX)?;
+    let name = pos.remove(0);
+    let text = pos.join(" ");
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let node = node_name(&g, &name)?;
+
+    let id = g.name2id[&node];
+    let before = g.nodes[id].emb.clone();
+    g.set_node_embedding_ema(&node, &text, alpha);
+    store.commit(&mut g)?;
+    let report = ReembedReport {
+        similarity_to_previous: cosine(&before, &g.nodes[id].emb),
+        node,
+        text,
+        alpha,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+/// Noms canoniques d'une liste `a,b,c`.
+fn node_list(g: &Graph, list: Option<String>) -> CliResult<Vec<String>> {
+    list.iter()
+        .flat_map(|l| l.split(','))
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|n| node_name(g, n))
+        .collect()
+}
+
+fn feedback(mut args: Args) -> CliResult<()> {
+    let relevant = args.opt("--relevant")?;
+    let irrelevant = args.opt("--irrelevant")?;
+    let mut params = FeedbackParams::default();
+    if let Some(d) = args.opt_parse("--max-step")? {
+        params.max_step = d;
+    }
+    if let Some(d) = args.opt_parse("--max-drift")? {
+        params.max_drift = d;
+    }
+    let json = args.global.json;
+    let query = args.positional(1, usize::MAX)?.join(" ");
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let relevant = node_list(&g, relevant)?;
+    let irrelevant = node_list(&g, irrelevant)?;
+    if relevant.is_empty() && irrelevant.is_empty() {
+        return usage("--relevant ou --irrelevant attendu");
+    }
+
+    let relevant: Vec<&str> = relevant.iter().map(String::as_str).collect();
+    let irrelevant: Vec<&str> = irrelevant.iter().map(String::as_str).collect();
+    let updates = g.apply_feedback(&query, &relevant, &irrelevant, &params)?;
+    store.commit(&mut g)?;
+    emit(&FeedbackReport { query, updates }, json);
+    Ok(())
+}
+
+fn history(mut args: Args) -> CliResult<()> {
+    let json = args.global.json;
+    let [name]: [String; 1] = args
+        .positional(1, 1)?
+        .try_into()
+        .expect("1 argument vérifié");
+    let (_store, g) = open_store(&args.global, [])?;
+    let node = node_name(&g, &name)?;
+    let report = HistoryReport {
+        version: g.nodes[g.name2id[&node]].emb_history.len(),
+        updates: g.embedding_history(&node).into_iter().cloned().collect(),
+        node,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+fn rollback(mut args: Args) -> CliResult<()> {
+    let json = args.global.json;
+    let [name, version]: [String; 2] = args
+        .positional(2, 2)?
+        .try_into()
+        .expect("2 arguments vérifiés");
+    let Ok(version) = version.parse::<usize>() else {
+        return usage(format!("version invalide '{version}'"));
+    };
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let node = node_name(&g, &name)?;
+    let update = g.rollback_embedding(&node, version)?;
+    store.commit(&mut g)?;
+    emit(&update, json);
+    Ok(())
+}
+
+fn stats(mut args: Args) -> CliResult<()> {
+    let json = args.global.json;
+    args.positional(0, 0)?;
+    let (store, g) = open_store(&args.global, [])?;
+    let mut relations = BTreeMap::new();
+    for e in g.edges.iter().filter(|e| !e.derived) {
+        *relations.entry(e.rel.clone()).or_insert(0) += 1;
+    }
+    let derived = g.derived_edges().len();
+    let report = StatsReport {
+        store: store.dir().display().to_string(),
+        seq: store.seq(),
+        embedder: g.embedder().name().to_string(),
+        dim: g.embedder().dim(),
+        nodes: g.nodes.iter().filter(|n| n.merged_into.is_none()).count(),
+        merged_nodes: g.nodes.iter().filter(|n| n.merged_into.is_some()).count(),
+        facts: g.edges.len() - derived,
+        derived_facts: derived,
+        history: g.history.len(),
+        rules: g.rules().rules.len(),
+        conflicts: g.conflicts().len(),
+        relations,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+fn query(mut args: Args) -> CliResult<()> {
+    let json = args.global.json;
+    let pos = args.positional(0, 1)?;
+    let (_store, g) = open_store(&args.global, [])?;
+    match pos.first() {
+        Some(path) => run_queries(&g, BufReader::new(File::open(path)?), false, json),
+        None => run_queries(&g, io::stdin().lock(), !json, json),
+    }
+    Ok(())
+}
+
+/// Exécute les requêtes lues sur `input` ; une requête se termine par une
+/// ligne vide ou par un `;` en fin de ligne.
+fn run_queries(g: &Graph, input: impl BufRead, interactive: bool, json: bool) {
+    let prompt = |buf: &str| {
+        if interactive {
+            print!("{}", if buf.is_empty() { "?> " } else { ".. " });
+            let _ = io::stdout().flush();
+        }
+    };
+    let mut buf = String::new();
+    let run = |buf: &mut String| {
+        let only_comments = buf
+            .lines()
+            .all(|l| l.trim().is_empty() || l.trim_start().starts_with('#'));
+        if !only_comments {
+            match (g.query_str(buf), json) {
+                (Ok(answer), true) => println!(
+                    "{}",
+                    serde_json::to_string(&answer).expect("réponse sérialisable")
+                ),
+                (Ok(answer), false) => println!("{answer}\n"),
+                (Err(e), true) => {
+                    println!("{}", serde_json::json!({ "error": e.to_string() }))
+                }
+                (Err(e), false) => println!("erreur de requête, {e}\n"),
+            }
+        }
+        buf.clear();
+    };
+    prompt(&buf);
+    for line in input.lines() {
+        let Ok(line) = line else { break };
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            run(&mut buf);
+        } else if let Some(q) = trimmed.strip_suffix(';') {
+            buf.push_str(q);
+            run(&mut buf);
+        } else {
+            buf.push_str(trimmed);
+            buf.push('\n');
+        }
+        prompt(&buf);
+    }
+    run(&mut buf);
+}
+
+fn export(mut args: Args) -> CliResult<()> {
+    let format = parse_format(&mut args)?;
+    let json = args.global.json;
+    let path = args.positional(1, 1)?.remove(0);
+    let (_store, g) = open_store(&args.global, [])?;
+    let n = g
+        .export_path(&path, format)
+        .map_err(|source| CliError::Format {
+            path: path.clone(),
+            source,
+        })?;
+    if json {
+        println!("{}", serde_json::json!({ "path": path, "facts": n }));
+    } else {
+        println!("{n} faits exportés vers {path}");
+    }
+    Ok(())
+}
+
+fn resolve(mut args: Args) -> CliResult<()> {
+    let apply = args.flag("--apply");
+    let json = args.global.json;
+    args.positional(0, 0)?;
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let proposals = g.propose_merges(&ResolveParams::default());
+    let applied = if apply {
+        let done = g.apply_merges(&proposals);
+        store.commit(&mut g)?;
+        Some(done)
+    } else {
+        None
+    };
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "proposals": proposals,
+                "applied": applied,
+            }))
+            .expect("sérialisable")
+        );
+        return Ok(());
+    }
+    if proposals.is_empty() {
+        println!("(aucun doublon probable)");
+    }
+    for p in &proposals {
+        println!("  {p}");
+    }
+    if let Some(done) = applied {
+        println!("{} fusions appliquées", done.len());
+    }
+    Ok(())
+}
+
+fn serve(mut args: Args) -> CliResult<()> {
+    let addr = args
+        .opt("--addr")?
+        .unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    args.positional(0, 0)?;
+    let (store, g) = open_store(&args.global, [])?;
+    let server = GraphServer::bind(addr.as_str(), g, Some(store))?;
+    eprintln!("écoute sur http://{}", server.local_addr()?);
+    server.serve()?;
+    Ok(())
+}
+
+fn conflicts(mut args: Args) -> CliResult<()> {
+    let apply = args.flag("--resolve");
+    let json = args.global.json;
+    args.positional(0, 0)?;
+    let (mut store, mut g) = open_store(&args.global, [])?;
+    let conflicts = g
+        .conflicts()
+        .iter()
+        .map(|c| ConflictEntry {
+            subj: g.nodes[c.subj].name.clone(),
+            rel: c.rel.to_string(),
+            facts: c.edges.iter().map(|e| FactRow::new(&g, e)).collect(),
+        })
+        .collect();
+    let retracted = if apply {
+        let n = g.resolve_conflicts();
+        store.commit(&mut g)?;
+        Some(n)
+    } else {
+        None
+    };
+    emit(
+        &ConflictsReport {
+            conflicts,
+            retracted,
+        },
+        json,
+    );
+    Ok(())
+}
+
+fn check(mut args: Args) -> CliResult<()> {
+    let json = args.global.json;
+    args.positional(0, 0)?;
+    if args.global.schema.is_none() {
+        return usage("check : préciser --schema (ou $AI_VEC_SCHEMA)");
+    }
+    let (_store, g) = open_store(&args.global, [])?;
+    emit(&g.check_schema(), json);
+    Ok(())
+}
+
+fn kge_params(args: &mut Args) -> CliResult<KgeParams> {
+    let mut params = KgeParams::default();
+    if let Some(m) = args.opt("--model")? {
+        params.model = m.parse().map_err(CliError::Usage)?;
+    }
+    params.dim = args.opt_parse("--dim")?;
+    if let Some(n) = args.opt_parse("--epochs")? {
+        params.epochs = n;
+    }
+    if let Some(lr) = args.opt_parse("--lr")? {
+        params.learning_rate = lr;
+    }
+    Ok(params)
+}
+
+fn train(mut args: Args) -> CliResult<()> {
+    let params = kge_params(&mut args)?;
+    let save = args.opt("--save")?;
+    let blend = args.opt_parse::<f32>("--blend")?;
+    if blend.is_some_and(|w| !(0.0..=1.0).contains(&w)) {
+        return usage("--blend doit être dans [0, 1]");
+    }
+    let json = args.global.json;
+    args.positional(0, 0)?;
+    let (mut store, mut g) = open_store(&args.global, [])?;
+
+    let kge = KgeEmbeddings::train(&g, &params);
+    if let Some(path) = &save {
+        kge.save(path)?;
+    }
+    let blended = match blend {
+        Some(w) => {
+            let n = g.blend_embeddings(&kge, w)?;
+            store.commit(&mut g)?;
+            Some(n)
+        }
+        None => None,
+    };
+    let report = TrainReport {
+        model: kge.model.to_string(),
+        dim: kge.dim,
+        nodes: kge.names.len(),
+        relations: kge.relations.len(),
+        epochs: params.epochs,
+        loss: kge.loss,
+        saved: save,
+        blended,
+    };
+    emit(&report, json);
+    Ok(())
+}
+
+/// `predict <sujet> <rel> ?` ou `predict ? <rel> <objet>`.
+fn predict(mut args: Args) -> CliResult<()> {
+    let k = args.opt_parse::<usize>("-k")?.unwrap_or(5);
+    let model_file = args.opt("--load")?;
+    let mut params = kge_params(&mut args)?;
+    let json = args.global.json;
+    let [subj, rel, obj]: [String; 3] = args
+        .positional(3, 3)?
+        .try_into()
+        .expect("3 arguments vérifiés");
+    let (_store, g) = open_store(&args.global, [])?;
+    let kge = match model_file {
+        Some(path) => KgeEmbeddings::load(path)?,
+        None => {
+            params.dim.get_or_insert(32);
+            KgeEmbeddings::train(&g, &params)
+        }
+    };
+    let (query, predictions) = match (subj.as_str(), obj.as_str()) {
+        (s, "?") if s != "?" => (format!("{s} {rel} ?"), g.predict_objects(&kge, s, &rel, k)?),
+        ("?", o) if o != "?" => (
+            format!("? {rel} {o}"),
+            g.predict_subjects(&kge, &rel, o, k)?,
+        ),
+        _ => return usage("un et un seul '?' attendu : <sujet> <rel> ? ou ? <rel> <objet>"),
+    };
+    emit(&PredictReport { query, predictions }, json);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cli_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn cli(dir: &Path, argv: &[&str]) -> CliResult<()> {
+        let store = dir.join("store");
+        let global = ["--store", store.to_str().unwrap(), "--embedder", "hashing"];
+        Args::parse(global.iter().chain(argv).map(|a| a.to_string())).and_then(run)
+    }
+
+    fn load_pluton(dir: &Path) {
+        let path = dir.join("faits.csv");
+        fs::write(
+            &path,
+            "subj,rel,obj,confidence,source\nPluton,est_une,planète,0.8,UAI 1930\nCharon,orbite,Pluton,,\n",
+        )
+        .unwrap();
+        cli(dir, &["load", path.to_str().unwrap()]).unwrap();
+    }
+
+    #[test]
+    fn correct_reports_the_facts_stored_in_the_graph() {
+        let dir = temp_dir("correct");
+        load_pluton(&dir);
+        cli(
+            &dir,
+            &[
+                "correct",
+                "pluton",
+                "est_une",
+                "planète",
+                "planète_naine",
+                "--at",
+                "2006-08-24",
+            ],
+        )
+        .unwrap();
+
+        let (_, g) = GraphStore::open(dir.join("store")).unwrap();
+        let at = time::from_ymd(2006, 8, 24);
+        let closed = stored_fact(&g, "Pluton", "est_une", "planète", |e| {
+            e.valid_to == Some(at)
+        })
+        .unwrap();
+        assert_eq!(closed.valid_to.as_deref(), Some("2006-08-24"));
+        assert_eq!(closed.confidence, 0.8);
+        assert_eq!(closed.source.as_deref(), Some("UAI 1930"));
+        let added = stored_fact(&g, "Pluton", "est_une", "planète_naine", |e| {
+            e.valid_to.is_none()
+        })
+        .unwrap();
+        assert_eq!(added.valid_from.as_deref(), Some("2006-08-24"));
+        assert_eq!(added.source, None);
+        // l'ancien fait n'est plus courant
+        let current: Vec<&str> = g
+            .outgoing("Pluton")
+            .into_iter()
+            .map(|e| g.nodes[e.to].name.as_str())
+            .collect();
+        assert_eq!(current, ["planète_naine"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_arguments_and_unknown_facts() {
+        let dir = temp_dir("errors");
+        load_pluton(&dir);
+        let err = |argv: &[&str]| cli(&dir, argv).unwrap_err();
+
+        assert!(matches!(err(&["inconnue"]), CliError::Usage(_)));
+        assert!(matches!(
+            err(&["correct", "Pluton", "est_une"]),
+            CliError::Usage(_)
+        ));
+        assert!(matches!(
+            err(&[
+                "correct",
+                "Pluton",
+                "est_une",
+                "planète",
+                "x",
+                "--at",
+                "2006-02-31"
+            ]),
+            CliError::Usage(_)
+        ));
+        assert!(matches!(
+            err(&["facts", "--bogus", "Pluton"]),
+            CliError::Usage(_)
+        ));
+        assert!(matches!(
+            err(&["correct", "Pluton", "orbite", "planète", "Soleil"]),
+            CliError::NotFound(_)
+        ));
+        assert!(matches!(err(&["facts", "Vulcain"]), CliError::NotFound(_)));
+        cli(&dir, &["facts", "charon"]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn tfidf_is_learned_once_from_the_stored_names() {
+        let dir = temp_dir("tfidf");
+        load_pluton(&dir);
+        let mut global = Args::parse([
+            "--store".to_string(),
+            dir.join("store").display().to_string(),
+        ])
+        .unwrap()
+        .global;
+        global.embedder = "tfidf:32".into();
+
+        let model = embedder(&global, []).unwrap();
+        assert_eq!((model.name(), model.dim()), ("tfidf", 32));
+        let saved = TfIdfEmbedder::load(dir.join("store").join(TFIDF_MODEL)).unwrap();
+        assert!(saved.vocabulary_size() > 0);
+        // le modèle conservé est réutilisé, le corpus n'est plus lu
+        let again = embedder(&global, ["Vulcain".to_string()]).unwrap();
+        let text = "Pluton planète";
+        assert!(cosine(&again.embed(text), &saved.embed(text)) > 0.9999);
+
+        global.embedder = "tfidf:64".into();
+        assert!(matches!(embedder(&global, []), Err(CliError::Usage(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
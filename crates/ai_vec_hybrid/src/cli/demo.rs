@@ -0,0 +1,148 @@
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use ai_vec_hybrid::fact::Fact;
+use ai_vec_hybrid::formats::{read_facts, Format, Namespace};
+use ai_vec_hybrid::graph::Graph;
+use ai_vec_hybrid::hybrid::HybridQuery;
+use ai_vec_hybrid::time;
+
+use crate::cli::args::GlobalOpts;
+use crate::cli::cli_error::{usage, CliError, CliResult};
+use crate::cli::commands::open_store;
+
+// --------- Démo ---------
+
+fn print_facts(g: &Graph, name: &str) {
+    let outs = g.outgoing(name);
+    if outs.is_empty() {
+        println!("  (aucun fait sortant pour {name})");
+    } else {
+        for e in outs {
+            let to = &g.nodes[e.to].name;
+            let mark = if e.derived { "  (dérivé)" } else { "" };
+            println!("  {name} --{}--> {}{mark}", e.rel, to);
+        }
+    }
+}
+
+/// Démonstration Pluton : faits initiaux (`$AI_VEC_FACTS` ou `facts.json`
+/// du crate) chargés au premier lancement, puis recherche, correction datée
+/// et mise à jour EMA.
+pub fn run(global: &GlobalOpts) -> CliResult<()> {
+    let facts_path =
+        env::var("AI_VEC_FACTS").unwrap_or_else(|_| "crates/ai_vec_hybrid/facts.json".into());
+    let Some(format) = Format::from_path(Path::new(&facts_path)) else {
+        return usage(format!("{facts_path} : format de faits inconnu"));
+    };
+    let input = File::open(&facts_path)?;
+    let facts: Vec<Fact> = read_facts(format, BufReader::new(input), &Namespace::default())
+        .collect::<Result<_, _>>()
+        .map_err(|source| CliError::Format {
+            path: facts_path.clone(),
+            source,
+        })?;
+
+    // règles du crate par défaut
+    let mut global = global.clone();
+    let default_rules = PathBuf::from("crates/ai_vec_hybrid/rules.json");
+    if global.rules.is_none() && default_rules.exists() {
+        global.rules = Some(default_rules);
+    }
+    let corpus = facts.iter().flat_map(|f| [f.subj.clone(), f.obj.clone()]);
+    let (mut store, mut g) = open_store(&global, corpus)?;
+    println!(
+        "(embedder : {} dim={})",
+        g.embedder().name(),
+        g.embedder().dim()
+    );
+
+    // Premier lancement : charger les faits initiaux
+    if g.nodes.is_empty() {
+        for fact in &facts {
+            g.add_edge(&fact.subj, &fact.rel, &fact.obj);
+        }
+        store.commit(&mut g)?;
+    } else {
+        println!("(graphe rechargé depuis {})", store.dir().display());
+    }
+    if !g.rules().is_empty() {
+        println!(
+            "({} règles, {} faits dérivés)",
+            g.rules().rules.len(),
+            g.derived_edges().len()
+        );
+    }
+
+    println!("== Recherche vectorielle (avant correction) ==");
+    for (name, score) in g.search_text("pluton", 5) {
+        let node = g.nodes.iter().find(|n| n.name == name).unwrap();
+        println!("  match: {name:>12}  sim={score:.3}  emb={:?}", node.emb);
+    }
+
+    println!("\n== Recherche hybride (k-NN + est_une / super-classe) ==");
+    let hq = HybridQuery::default().with_relations(&["est_une", "super-classe"]);
+    for hit in g.hybrid_search("pluton", &hq) {
+        println!("  {:>14}  score={:.3}  via {}", hit.node, hit.score, hit);
+    }
+
+    println!("\n== Faits sur Pluton (avant) ==");
+    print_facts(&g, "Pluton");
+
+    // Correction d’un fait : Pluton est_une planète -> Pluton est_une planète_naine
+    // (reclassement par l'UAI le 24 août 2006 ; l'ancien fait reste dans l'historique)
+    let iau_2006 = time::from_ymd(2006, 8, 24);
+    println!(
+        "\n>> Correction au {} : 'Pluton est_une planète'  ->  'Pluton est_une planète_naine'",
+        time::format_date(iau_2006)
+    );
+    g.update_fact_at(
+        "Pluton",
+        "est_une",
+        "planète",
+        "est_une",
+        "planète_naine",
+        iau_2006,
+    );
+    store.commit(&mut g)?;
+
+    println!("\n== Faits sur Pluton (après correction) ==");
+    print_facts(&g, "Pluton");
+
+    for t in [time::from_ymd(2000, 1, 1), time::now()] {
+        println!("\n== Faits sur Pluton au {} ==", time::format_date(t));
+        for e in g.outgoing_as_of("Pluton", t) {
+            println!("  Pluton --{}--> {}", e.rel, g.nodes[e.to].name);
+        }
+    }
+
+    // Mise à jour embedding locale
+    println!("\n>> Mise à jour embedding locale de 'Pluton' (alpha=0.3) avec texte : 'dwarf planet kuiper belt object'");
+    g.set_node_embedding_ema("Pluton", "dwarf planet kuiper belt object", 0.3);
+    store.commit(&mut g)?;
+
+    println!("\n== Recherche vectorielle (après correction + EMA locale) ==");
+    for (name, score) in g.search_text("pluton dwarf planet", 5) {
+        let node = g.nodes.iter().find(|n| n.name == name).unwrap();
+        println!("  match: {name:>12}  sim={score:.3}  emb={:?}", node.emb);
+    }
+
+    // Statut courant via le graphe
+    println!("\n== Statut de Pluton via le graphe ==");
+    let outs = g.outgoing("Pluton");
+    let mut statut: Option<String> = None;
+    for e in outs {
+        if e.rel == "est_une" {
+            statut = Some(g.nodes[e.to].name.clone());
+        }
+    }
+    println!(
+        "  Pluton est_une -> {}",
+        statut.unwrap_or_else(|| "(inconnu)".into())
+    );
+
+    println!("\nOK.");
+    Ok(())
+}
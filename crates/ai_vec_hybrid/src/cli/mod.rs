@@ -0,0 +1,5 @@
+pub mod args;
+pub mod cli_error;
+pub mod commands;
+pub mod demo;
+pub mod reports;
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use ai_vec_hybrid::fact_row::FactRow;
+use ai_vec_hybrid::feedback::EmbeddingUpdate;
+use ai_vec_hybrid::kge::LinkPrediction;
+use ai_vec_hybrid::names::NameMatch;
+use ai_vec_hybrid::schema::ViolationReport;
+
+#[derive(Serialize)]
+pub struct LoadedFile {
+    pub path: String,
+    pub format: String,
+    pub facts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LoadReport {
+    pub files: Vec<LoadedFile>,
+    pub nodes: usize,
+    pub facts: usize,
+    /// Validation du graphe chargé (avec `--schema`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<ViolationReport>,
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            write!(f, "{} ({}) : {} faits", file.path, file.format, file.facts)?;
+            if let Some(e) = &file.error {
+                write!(f, ", interrompu : {e}")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "graphe : {} nœuds, {} faits", self.nodes, self.facts)?;
+        if let Some(report) = &self.schema {
+            write!(f, "\nschéma : {report}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SearchHit {
+    pub name: String,
+    pub score: f32,
+    /// Sources des faits qui mentionnent le nœud.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct NamesReport {
+    pub text: String,
+    pub matches: Vec<NameMatch>,
+}
+
+impl fmt::Display for NamesReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.matches.is_empty() {
+            return write!(f, "aucun nom proche de « {} »", self.text);
+        }
+        let lines: Vec<String> = self.matches.iter().map(ToString::to_string).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[derive(Serialize)]
+pub struct SearchReport {
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+}
+
+impl fmt::Display for SearchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.hits.is_empty() {
+            return write!(f, "(aucun résultat)");
+        }
+        let lines: Vec<String> = self
+            .hits
+            .iter()
+            .map(|h| match h.sources.is_empty() {
+                true => format!("{:>8.3}  {}", h.score, h.name),
+                false => format!("{:>8.3}  {}  [{}]", h.score, h.name, h.sources.join(", ")),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[derive(Serialize)]
+pub struct FactsReport {
+    pub node: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outgoing: Option<Vec<FactRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incoming: Option<Vec<FactRow>>,
+}
+
+impl fmt::Display for FactsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.node)?;
+        if !self.aliases.is_empty() {
+            write!(f, "  (alias : {})", self.aliases.join(", "))?;
+        }
+        if let Some(d) = &self.as_of {
+            write!(f, "  au {d}")?;
+        }
+        for (title, rows) in [("sortants", &self.outgoing), ("entrants", &self.incoming)] {
+            let Some(rows) = rows else { continue };
+            write!(f, "\n{title} :")?;
+            if rows.is_empty() {
+                write!(f, "\n  (aucun)")?;
+            }
+            for r in rows {
+                write!(f, "\n  {r}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct CorrectReport {
+    pub at: String,
+    pub closed: FactRow,
+    pub added: FactRow,
+}
+
+impl fmt::Display for CorrectReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "correction au {} :\n  - {}\n  + {}",
+            self.at, self.closed, self.added
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReembedReport {
+    pub node: String,
+    pub text: String,
+    pub alpha: f32,
+    /// Cosinus entre l'ancien et le nouvel embedding.
+    pub similarity_to_previous: f32,
+}
+
+impl fmt::Display for ReembedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ré-encodé (alpha={}) avec « {} » ; similarité avec l'ancien vecteur : {:.3}",
+            self.node, self.alpha, self.text, self.similarity_to_previous
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct FeedbackReport {
+    pub query: String,
+    pub updates: Vec<EmbeddingUpdate>,
+}
+
+impl fmt::Display for FeedbackReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "retour sur « {} »", self.query)?;
+        if self.updates.is_empty() {
+            write!(f, "\n  (aucun déplacement)")?;
+        }
+        for u in &self.updates {
+            write!(f, "\n  {:<20} {u}", u.node)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct HistoryReport {
+    pub node: String,
+    /// Version courante.
+    pub version: usize,
+    pub updates: Vec<EmbeddingUpdate>,
+}
+
+impl fmt::Display for HistoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} : version courante {}", self.node, self.version)?;
+        for u in &self.updates {
+            write!(f, "\n  {u}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct StatsReport {
+    pub store: String,
+    pub seq: u64,
+    pub embedder: String,
+    pub dim: usize,
+    pub nodes: usize,
+    pub merged_nodes: usize,
+    pub facts: usize,
+    pub derived_facts: usize,
+    pub history: usize,
+    pub rules: usize,
+    pub conflicts: usize,
+    pub relations: BTreeMap<String, usize>,
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "stockage    : {} (séquence {})", self.store, self.seq)?;
+        writeln!(f, "embedder    : {} (dim={})", self.embedder, self.dim)?;
+        writeln!(
+            f,
+            "nœuds       : {} ({} fusionnés)",
+            self.nodes, self.merged_nodes
+        )?;
+        writeln!(
+            f,
+            "faits       : {} de base, {} dérivés ({} règles)",
+            self.facts, self.derived_facts, self.rules
+        )?;
+        writeln!(f, "conflits    : {}", self.conflicts)?;
+        write!(f, "historique  : {} versions", self.history)?;
+        for (rel, n) in &self.relations {
+            write!(f, "\n  {rel:<16} {n}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct ConflictEntry {
+    pub subj: String,
+    pub rel: String,
+    /// Du plus sûr au moins sûr.
+    pub facts: Vec<FactRow>,
+}
+
+#[derive(Serialize)]
+pub struct ConflictsReport {
+    pub conflicts: Vec<ConflictEntry>,
+    /// Faits retirés par `--resolve`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retracted: Option<usize>,
+}
+
+impl fmt::Display for ConflictsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.conflicts.is_empty() {
+            write!(f, "(aucun conflit)")?;
+        }
+        for (i, c) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{} {} :", c.subj, c.rel)?;
+            for (j, fact) in c.facts.iter().enumerate() {
+                let mark = if j == 0 { "*" } else { " " };
+                write!(f, "\n  {mark} {fact}")?;
+            }
+        }
+        if let Some(n) = self.retracted {
+            write!(f, "\n{n} faits retirés")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrainReport {
+    pub model: String,
+    pub dim: usize,
+    pub nodes: usize,
+    pub relations: usize,
+    pub epochs: usize,
+    /// Perte moyenne de la dernière époque.
+    pub loss: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved: Option<String>,
+    /// Nœuds dont l'embedding a été mélangé (`--blend`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blended: Option<usize>,
+}
+
+impl fmt::Display for TrainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (dim={}) : {} nœuds, {} relations, {} époques, perte finale {:.4}",
+            self.model, self.dim, self.nodes, self.relations, self.epochs, self.loss
+        )?;
+        if let Some(path) = &self.saved {
+            write!(f, "\nmodèle enregistré dans {path}")?;
+        }
+        if let Some(n) = self.blended {
+            write!(f, "\n{n} embeddings mélangés")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct PredictReport {
+    pub query: String,
+    pub predictions: Vec<LinkPrediction>,
+}
+
+impl fmt::Display for PredictReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.query)?;
+        if self.predictions.is_empty() {
+            write!(f, "\n  (aucun candidat)")?;
+        }
+        for p in &self.predictions {
+            write!(f, "\n{p}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::provenance::Provenance;
+use crate::time::Timestamp;
+
+/// Version d'un fait, bitemporelle : validité dans le monde
+/// `[valid_from, valid_to)` et période de croyance `[recorded_at, retracted_at)`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: usize,
+    pub rel: String,
+    pub to: usize,
+    /// Arête produite par le moteur de règles (et non un fait de base).
+    pub derived: bool,
+    /// Début de validité (`None` = depuis toujours).
+    pub valid_from: Option<Timestamp>,
+    /// Fin de validité, exclue (`None` = toujours valide).
+    pub valid_to: Option<Timestamp>,
+    /// Date d'enregistrement de cette version.
+    pub recorded_at: Timestamp,
+    /// Date à laquelle cette version a cessé d'être tenue pour vraie.
+    pub retracted_at: Option<Timestamp>,
+    /// Confiance et source ; pour une arête dérivée, la confiance est la
+    /// plus faible de ses prémisses.
+    #[serde(flatten)]
+    pub provenance: Provenance,
+}
+
+impl Edge {
+    pub fn valid_at(&self, t: Timestamp) -> bool {
+        self.valid_from.is_none_or(|f| f <= t) && self.valid_to.is_none_or(|v| t < v)
+    }
+
+    pub fn confidence(&self) -> f32 {
+        self.provenance.confidence
+    }
+
+    pub fn known_at(&self, t: Timestamp) -> bool {
+        self.recorded_at <= t && self.retracted_at.is_none_or(|r| t < r)
+    }
+}
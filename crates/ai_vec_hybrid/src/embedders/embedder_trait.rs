@@ -0,0 +1,8 @@
+/// Transforme un texte en vecteur de dimension fixe (connue à l'exécution).
+pub trait Embedder: Send + Sync {
+    /// Nom court, utilisé dans les logs et les snapshots.
+    fn name(&self) -> &str;
+    fn dim(&self) -> usize;
+    /// Vecteur normalisé L2, de longueur `self.dim()`.
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
@@ -0,0 +1,65 @@
+use crate::embedders::embedder_trait::Embedder;
+use crate::embedding::{fnv1a, l2_normalize};
+
+/// Embedder historique : hash-binning des n-grammes d'octets (1..3).
+pub struct HashingEmbedder {
+    dim: usize,
+}
+
+impl HashingEmbedder {
+    pub const DEFAULT_DIM: usize = 16;
+
+    pub fn new(dim: usize) -> Self {
+        Self { dim: dim.max(1) }
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DIM)
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn name(&self) -> &str {
+        "hashing"
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn embed(&self, s: &str) -> Vec<f32> {
+        // hash-binning super simple + n-grams (1..3)
+        let mut v = vec![0f32; self.dim];
+        let bytes = s.as_bytes();
+        for n in 1..=3 {
+            for w in bytes.windows(n) {
+                let idx = (fnv1a(w) as usize) % self.dim;
+                v[idx] += 1.0;
+            }
+        }
+        l2_normalize(&mut v);
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::cosine;
+
+    #[test]
+    fn embeds_deterministically_into_unit_vectors() {
+        let e = HashingEmbedder::new(32);
+        let v = e.embed("Pluton");
+        assert_eq!(v.len(), 32);
+        assert!((v.iter().map(|x| x * x).sum::<f32>() - 1.0).abs() < 1e-5);
+        assert_eq!(v, HashingEmbedder::new(32).embed("Pluton"));
+        // les n-grammes partagés rapprochent les textes voisins
+        assert!(cosine(&v, &e.embed("Plutons")) > cosine(&v, &e.embed("Soleil")));
+
+        assert_eq!(HashingEmbedder::new(0).dim(), 1);
+        assert_eq!(e.embed(""), vec![0.0; 32]);
+    }
+}
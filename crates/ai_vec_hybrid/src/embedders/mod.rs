@@ -0,0 +1,82 @@
+pub mod embedder_trait;
+pub mod hashing_embedder;
+pub mod static_vectors_embedder;
+pub mod tfidf_embedder;
+
+pub use embedder_trait::Embedder;
+pub use hashing_embedder::HashingEmbedder;
+pub use static_vectors_embedder::StaticVectorsEmbedder;
+pub use tfidf_embedder::TfIdfEmbedder;
+
+/// Construit un embedder depuis une spécification texte :
+/// `hashing[:dim]`, `tfidf[:dim]` (IDF appris sur `corpus`) ou `vectors:<fichier>`.
+pub fn from_spec<'a>(
+    spec: &str,
+    corpus: impl IntoIterator<Item = &'a str>,
+) -> std::io::Result<Box<dyn Embedder>> {
+    let (kind, arg) = spec.split_once(':').unwrap_or((spec, ""));
+    match kind {
+        "hashing" => Ok(Box::new(HashingEmbedder::new(spec_dim(
+            spec,
+            HashingEmbedder::DEFAULT_DIM,
+        )?))),
+        "tfidf" => Ok(Box::new(TfIdfEmbedder::fit(
+            corpus,
+            spec_dim(spec, TfIdfEmbedder::DEFAULT_DIM)?,
+        ))),
+        "vectors" => Ok(Box::new(StaticVectorsEmbedder::load(arg)?)),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("embedder inconnu : {kind} (hashing, tfidf, vectors)"),
+        )),
+    }
+}
+
+/// Dimension d'une spécification `kind[:dim]` (`default` sans dimension).
+pub fn spec_dim(spec: &str, default: usize) -> std::io::Result<usize> {
+    let arg = spec.split_once(':').map_or("", |(_, arg)| arg);
+    if arg.is_empty() {
+        return Ok(default);
+    }
+    arg.parse().map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("dimension invalide : {arg}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_embedders_from_their_spec() {
+        let e = from_spec("hashing", []).unwrap();
+        assert_eq!(
+            (e.name(), e.dim()),
+            ("hashing", HashingEmbedder::DEFAULT_DIM)
+        );
+        let e = from_spec("hashing:64", []).unwrap();
+        assert_eq!(e.dim(), 64);
+        let e = from_spec("tfidf:32", ["Pluton", "planète naine"]).unwrap();
+        assert_eq!((e.name(), e.dim()), ("tfidf", 32));
+
+        let path = std::env::temp_dir().join(format!("spec_vectors_{}.txt", std::process::id()));
+        std::fs::write(&path, "planète 1 0 0\n").unwrap();
+        let e = from_spec(&format!("vectors:{}", path.display()), []).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((e.name(), e.dim()), ("static_vectors", 3));
+    }
+
+    #[test]
+    fn rejects_unknown_kinds_and_bad_dimensions() {
+        let kind = |spec| from_spec(spec, []).err().unwrap().kind();
+        assert_eq!(kind("word2vec"), std::io::ErrorKind::InvalidInput);
+        assert_eq!(kind("hashing:large"), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            kind("vectors:/nonexistent/vectors.txt"),
+            std::io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::embedders::embedder_trait::Embedder;
+use crate::embedding::l2_normalize;
+
+/// Vecteurs de mots pré-entraînés (format texte word2vec ou GloVe).
+///
+/// Le texte est découpé en mots ; le vecteur est la moyenne des vecteurs
+/// connus (recherche exacte puis en minuscules). Sans mot connu : vecteur nul.
+pub struct StaticVectorsEmbedder {
+    dim: usize,
+    vectors: HashMap<String, Vec<f32>>,
+}
+
+impl StaticVectorsEmbedder {
+    /// Charge un fichier `mot v1 v2 ...` ; l'en-tête word2vec `<n> <dim>`
+    /// est détecté et ignoré.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut vectors = HashMap::new();
+        let mut dim = 0usize;
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let Some(word) = parts.next() else {
+                continue;
+            };
+            let values: Vec<&str> = parts.collect();
+            if i == 0 && values.len() == 1 && word.parse::<usize>().is_ok() {
+                continue; // en-tête word2vec
+            }
+            let v: Vec<f32> = values
+                .iter()
+                .map(|x| x.parse::<f32>())
+                .collect::<Result<_, _>>()
+                .map_err(|e| invalid(i + 1, &format!("valeur invalide : {e}")))?;
+            // `parse` accepte « nan » et « inf », qui casseraient les tris par similarité
+            if let Some(x) = v.iter().find(|x| !x.is_finite()) {
+                return Err(invalid(i + 1, &format!("valeur non finie : {x}")));
+            }
+            if dim == 0 {
+                dim = v.len();
+            }
+            if v.len() != dim || dim == 0 {
+                return Err(invalid(
+                    i + 1,
+                    &format!("dimension {} au lieu de {dim}", v.len()),
+                ));
+            }
+            vectors.insert(word.to_string(), v);
+        }
+        if vectors.is_empty() {
+            return Err(invalid(0, "aucun vecteur"));
+        }
+        Ok(Self { dim, vectors })
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+}
+
+fn invalid(line: usize, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("ligne {line}: {msg}"))
+}
+
+impl Embedder for StaticVectorsEmbedder {
+    fn name(&self) -> &str {
+        "static_vectors"
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut v = vec![0f32; self.dim];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            let found = self
+                .vectors
+                .get(word)
+                .or_else(|| self.vectors.get(&word.to_lowercase()));
+            if let Some(w) = found {
+                for (x, y) in v.iter_mut().zip(w) {
+                    *x += y;
+                }
+            }
+        }
+        l2_normalize(&mut v);
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::embedding::cosine;
+
+    fn load_str(name: &str, content: &str) -> io::Result<StaticVectorsEmbedder> {
+        let path = std::env::temp_dir().join(format!("vectors_{name}_{}.txt", std::process::id()));
+        fs::write(&path, content).unwrap();
+        let loaded = StaticVectorsEmbedder::load(&path);
+        fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn averages_known_words_and_skips_the_header() {
+        let model = load_str("ok", "3 2\nplanète 1 0\nnaine 0 1\nsoleil -1 0\n").unwrap();
+        assert_eq!((model.len(), model.dim()), (3, 2));
+
+        let v = model.embed("Planète naine");
+        assert!(cosine(&v, &[1.0, 1.0]) > 0.9999);
+        // repli en minuscules, mots inconnus ignorés
+        assert!(cosine(&model.embed("SOLEIL Vulcain"), &[-1.0, 0.0]) > 0.9999);
+        assert_eq!(model.embed("Vulcain"), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn rejects_malformed_lines_with_their_number() {
+        let err = |name, content| load_str(name, content).err().unwrap().to_string();
+        assert!(err("nan", "a 1 0\nb nan 0\n").starts_with("ligne 2:"));
+        assert!(err("inf", "a 1 0\nb 0 1\nc 1 -inf\n").starts_with("ligne 3:"));
+        assert!(err("dim", "a 1 0\nb 1\n").starts_with("ligne 2: dimension 1 au lieu de 2"));
+        assert!(err("value", "a 1 x\n").starts_with("ligne 1: valeur invalide"));
+        assert!(err("empty", "\n").contains("aucun vecteur"));
+    }
+}
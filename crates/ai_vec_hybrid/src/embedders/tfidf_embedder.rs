@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::embedders::embedder_trait::Embedder;
+use crate::embedding::{fnv1a, l2_normalize};
+
+/// TF-IDF sur mots + trigrammes de caractères, réduit à `dim` dimensions
+/// par projection aléatoire (signes ±1 dérivés d'un hash, aucune matrice stockée).
+///
+/// Les IDF sont appris sur un corpus (typiquement les noms des nœuds) ;
+/// une caractéristique inconnue reçoit l'IDF maximal. Le modèle appris
+/// s'enregistre (`save`) pour que tous les vecteurs d'un même graphe soient
+/// pondérés de la même façon.
+#[derive(Serialize, Deserialize)]
+pub struct TfIdfEmbedder {
+    dim: usize,
+    idf: HashMap<String, f32>,
+    unseen_idf: f32,
+}
+
+impl TfIdfEmbedder {
+    pub const DEFAULT_DIM: usize = 64;
+
+    pub fn fit<S: AsRef<str>>(corpus: impl IntoIterator<Item = S>, dim: usize) -> Self {
+        let mut df: HashMap<String, usize> = HashMap::new();
+        let mut n_docs = 0usize;
+        for doc in corpus {
+            n_docs += 1;
+            let uniq: HashSet<String> = features(doc.as_ref()).into_iter().collect();
+            for f in uniq {
+                *df.entry(f).or_insert(0) += 1;
+            }
+        }
+        let idf_of = |df: usize| ((1.0 + n_docs as f32) / (1.0 + df as f32)).ln() + 1.0;
+        Self {
+            dim: dim.max(1),
+            idf: df.into_iter().map(|(f, d)| (f, idf_of(d))).collect(),
+            unseen_idf: idf_of(0),
+        }
+    }
+
+    pub fn vocabulary_size(&self) -> usize {
+        self.idf.len()
+    }
+
+    /// Recharge un modèle enregistré par `save`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Enregistre le modèle en JSON (écriture dans un fichier temporaire
+    /// puis renommage, pour ne jamais laisser un modèle tronqué).
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+/// Finaliseur de MurmurHash3 : les bits bas de FNV-1a ne dépendent que
+/// des bits bas des octets, il faut les mélanger avant d'en tirer un signe.
+fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^= h >> 16;
+    h
+}
+
+/// Mots en minuscules + trigrammes de `#mot#`.
+fn features(text: &str) -> Vec<String> {
+    let mut out = vec![];
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase();
+        let padded: Vec<char> = format!("#{word}#").chars().collect();
+        for w in padded.windows(3) {
+            out.push(w.iter().collect());
+        }
+        out.push(word);
+    }
+    out
+}
+
+impl Embedder for TfIdfEmbedder {
+    fn name(&self) -> &str {
+        "tfidf"
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut tf: HashMap<String, f32> = HashMap::new();
+        for f in features(text) {
+            *tf.entry(f).or_insert(0.0) += 1.0;
+        }
+        let mut v = vec![0f32; self.dim];
+        for (f, count) in tf {
+            let w = count * self.idf.get(&f).copied().unwrap_or(self.unseen_idf);
+            let mut key = f.into_bytes();
+            let base = key.len();
+            for (j, x) in v.iter_mut().enumerate() {
+                key.truncate(base);
+                key.extend_from_slice(&(j as u32).to_le_bytes());
+                let sign = if fmix32(fnv1a(&key)) & 1 == 0 {
+                    1.0
+                } else {
+                    -1.0
+                };
+                *x += sign * w;
+            }
+        }
+        l2_normalize(&mut v);
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::cosine;
+
+    #[test]
+    fn saved_model_embeds_identically() {
+        let model = TfIdfEmbedder::fit(["Pluton", "planète naine", "Soleil", "planète"], 32);
+        let dir = std::env::temp_dir().join(format!("tfidf_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tfidf.json");
+        model.save(&path).unwrap();
+        let loaded = TfIdfEmbedder::load(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.dim(), 32);
+        assert_eq!(loaded.vocabulary_size(), model.vocabulary_size());
+        for text in ["planète naine", "Vulcain"] {
+            assert!(cosine(&loaded.embed(text), &model.embed(text)) > 0.9999);
+        }
+        // un IDF appris ne se réduit pas à la fréquence des termes
+        let tf_only = TfIdfEmbedder::fit(Vec::<&str>::new(), 32);
+        assert!(
+            cosine(
+                &tf_only.embed("planète naine"),
+                &model.embed("planète naine")
+            ) < 0.9999
+        );
+    }
+}
@@ -0,0 +1,34 @@
+// --------- Outils vectoriels ---------
+
+pub fn l2_normalize(v: &mut [f32]) {
+    let mut n2 = 0.0;
+    for x in v.iter() {
+        n2 += x * x;
+    }
+    let n = n2.sqrt().max(1e-9);
+    for x in v.iter_mut() {
+        *x /= n;
+    }
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0;
+    let mut na = 0.0;
+    let mut nb = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    dot / (na.sqrt() * nb.sqrt() + 1e-9)
+}
+
+/// Hash FNV-1a 32 bits (« cheap hash » partagé par les embedders).
+pub fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut h: u32 = 2166136261;
+    for &b in bytes {
+        h ^= b as u32;
+        h = h.wrapping_mul(16777619);
+    }
+    h
+}
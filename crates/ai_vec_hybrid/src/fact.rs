@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::provenance::Provenance;
+
+/// Fait brut (sujet, relation, objet), unité d'échange des imports/exports.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Fact {
+    pub subj: String,
+    pub rel: String,
+    pub obj: String,
+    /// Absente des formats RDF ; `confidence` et `source` en JSON et CSV.
+    #[serde(flatten)]
+    pub provenance: Provenance,
+}
+
+impl Fact {
+    pub fn new(subj: impl Into<String>, rel: impl Into<String>, obj: impl Into<String>) -> Self {
+        Self {
+            subj: subj.into(),
+            rel: rel.into(),
+            obj: obj.into(),
+            provenance: Provenance::default(),
+        }
+    }
+
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::edge::Edge;
+use crate::graph::Graph;
+use crate::time;
+
+/// Un fait tel qu'affiché (dates au format `AAAA-MM-JJ`).
+#[derive(Serialize)]
+pub struct FactRow {
+    pub subj: String,
+    pub rel: String,
+    pub obj: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub derived: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<String>,
+    pub confidence: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Date d'insertion.
+    pub recorded_at: String,
+}
+
+impl FactRow {
+    pub fn new(g: &Graph, e: &Edge) -> Self {
+        Self {
+            subj: g.nodes[e.from].name.clone(),
+            rel: e.rel.clone(),
+            obj: g.nodes[e.to].name.clone(),
+            derived: e.derived,
+            valid_from: e.valid_from.map(time::format_date),
+            valid_to: e.valid_to.map(time::format_date),
+            confidence: e.confidence(),
+            source: e.provenance.source.clone(),
+            recorded_at: time::format_date(e.recorded_at),
+        }
+    }
+}
+
+impl fmt::Display for FactRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} --{}--> {}", self.subj, self.rel, self.obj)?;
+        if let Some(d) = &self.valid_from {
+            write!(f, "  depuis {d}")?;
+        }
+        if let Some(d) = &self.valid_to {
+            write!(f, "  jusqu'au {d}")?;
+        }
+        if self.derived {
+            write!(f, "  (dérivé)")?;
+        }
+        if self.confidence < 1.0 {
+            write!(f, "  confiance {:.2}", self.confidence)?;
+        }
+        if let Some(s) = &self.source {
+            write!(f, "  [{s}]")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::feedback::embedding_update::EmbeddingUpdate;
+use crate::feedback::feedback_error::{FeedbackError, FeedbackResult};
+use crate::feedback::update_reason::UpdateReason;
+use crate::graph::Graph;
+use crate::time::now;
+
+impl Graph {
+    /// Piste d'audit : toutes les mises à jour d'embedding, dans l'ordre.
+    pub fn embedding_log(&self) -> &[EmbeddingUpdate] {
+        &self.embedding_log
+    }
+
+    /// Mises à jour d'embedding d'un nœud (nom ou alias), de la plus
+    /// ancienne à la plus récente.
+    pub fn embedding_history(&self, name: &str) -> Vec<&EmbeddingUpdate> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        let name = &self.nodes[id].name;
+        self.embedding_log
+            .iter()
+            .filter(|u| &u.node == name)
+            .collect()
+    }
+
+    /// Version `version` de l'embedding d'un nœud (0 = premier encodage ; la
+    /// dernière version est le vecteur courant).
+    pub fn embedding_version(&self, name: &str, version: usize) -> Option<&[f32]> {
+        let node = &self.nodes[*self.name2id.get(name)?];
+        match version.cmp(&node.emb_history.len()) {
+            std::cmp::Ordering::Less => Some(&node.emb_history[version].1),
+            std::cmp::Ordering::Equal => Some(&node.emb),
+            std::cmp::Ordering::Greater => None,
+        }
+    }
+
+    /// Remet l'embedding d'un nœud à une version antérieure. Le retour en
+    /// arrière est lui-même une nouvelle version : rien n'est effacé et il
+    /// peut être annulé de la même façon.
+    pub fn rollback_embedding(
+        &mut self,
+        name: &str,
+        version: usize,
+    ) -> FeedbackResult<EmbeddingUpdate> {
+        let id = *self
+            .name2id
+            .get(name)
+            .ok_or_else(|| FeedbackError::UnknownNode(name.to_string()))?;
+        let emb = self
+            .embedding_version(name, version)
+            .ok_or_else(|| FeedbackError::UnknownVersion {
+                node: name.to_string(),
+                version,
+                last: self.nodes[id].emb_history.len(),
+            })?
+            .to_vec();
+        self.set_node_embedding_at(name, &emb, now(), UpdateReason::Rollback { version })
+            .ok_or_else(|| FeedbackError::ForeignVersion {
+                node: name.to_string(),
+                version,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emb(g: &Graph, name: &str) -> Vec<f32> {
+        g.nodes[g.name2id[name]].emb.clone()
+    }
+
+    #[test]
+    fn audit_trail_and_rollback() {
+        let mut g = Graph::new();
+        g.add_node("Pluton");
+        let v0 = emb(&g, "Pluton");
+        g.set_node_embedding_ema("Pluton", "planète naine", 0.5);
+        let v1 = emb(&g, "Pluton");
+        g.set_node_embedding_ema("Pluton", "ceinture de Kuiper", 0.5);
+
+        let history = g.embedding_history("Pluton");
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history.iter().map(|u| u.version).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(matches!(history[0].reason, UpdateReason::Ema { .. }));
+        assert_eq!(g.embedding_version("Pluton", 0), Some(v0.as_slice()));
+        assert_eq!(g.embedding_version("Pluton", 1), Some(v1.as_slice()));
+        assert_eq!(g.embedding_version("Pluton", 3), None);
+
+        // le retour en arrière est une nouvelle version, elle-même réversible
+        let update = g.rollback_embedding("Pluton", 0).unwrap();
+        assert_eq!(update.version, 3);
+        assert_eq!(update.reason, UpdateReason::Rollback { version: 0 });
+        assert_eq!(emb(&g, "Pluton"), v0);
+        g.rollback_embedding("Pluton", 1).unwrap();
+        assert_eq!(emb(&g, "Pluton"), v1);
+        assert_eq!(g.embedding_log().len(), 4);
+
+        assert_eq!(
+            g.rollback_embedding("Pluton", 9),
+            Err(FeedbackError::UnknownVersion {
+                node: "Pluton".into(),
+                version: 9,
+                last: 4,
+            })
+        );
+        assert_eq!(
+            g.rollback_embedding("Vulcain", 0),
+            Err(FeedbackError::UnknownNode("Vulcain".into()))
+        );
+    }
+}
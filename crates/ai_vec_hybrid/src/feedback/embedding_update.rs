@@ -0,0 +1,34 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::feedback::update_reason::UpdateReason;
+use crate::time::{self, Timestamp};
+
+/// Entrée de la piste d'audit des embeddings.
+///
+/// Les versions d'un nœud sont numérotées depuis 0 (encodage initial) : la
+/// version `v` est `emb_history[v]`, la dernière est le vecteur courant.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingUpdate {
+    pub node: String,
+    pub at: Timestamp,
+    pub reason: UpdateReason,
+    /// Version créée par cette mise à jour.
+    pub version: usize,
+    /// Distance cosinus avec la version remplacée.
+    pub drift: f32,
+}
+
+impl fmt::Display for EmbeddingUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "v{:<3} {}  {}  (dérive {:.3})",
+            self.version,
+            time::format_date(self.at),
+            self.reason,
+            self.drift
+        )
+    }
+}
@@ -0,0 +1,17 @@
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum FeedbackError {
+    #[error("nœud inconnu '{0}'")]
+    UnknownNode(String),
+    #[error("'{0}' est jugé à la fois pertinent et non pertinent")]
+    Contradictory(String),
+    #[error("'{node}' n'a pas de version {version} (dernière : {last})")]
+    UnknownVersion {
+        node: String,
+        version: usize,
+        last: usize,
+    },
+    #[error("la version {version} de '{node}' vient d'un autre embedder")]
+    ForeignVersion { node: String, version: usize },
+}
+
+pub type FeedbackResult<T> = Result<T, FeedbackError>;
@@ -0,0 +1,29 @@
+/// Règle de Rocchio appliquée aux résultats jugés :
+/// `d' = normalise(alpha · d + beta · q)` pour un résultat pertinent,
+/// `d' = normalise(alpha · d − gamma · q)` pour un résultat non pertinent,
+/// où `q` est l'embedding de la requête.
+///
+/// Les distances sont des distances cosinus (`1 − cos`).
+#[derive(Clone, Debug)]
+pub struct FeedbackParams {
+    pub alpha: f32,
+    pub beta: f32,
+    pub gamma: f32,
+    /// Déplacement maximal par mise à jour.
+    pub max_step: f32,
+    /// Écart maximal avec l'encodage du nom ; un retour ne peut pas
+    /// éloigner davantage un nœud qui l'aurait déjà dépassé.
+    pub max_drift: f32,
+}
+
+impl Default for FeedbackParams {
+    fn default() -> Self {
+        Self {
+            alpha: 1.0,
+            beta: 0.5,
+            gamma: 0.25,
+            max_step: 0.05,
+            max_drift: 0.3,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod embedding_audit;
+pub mod embedding_update;
+pub mod feedback_error;
+pub mod feedback_params;
+pub mod relevance_feedback;
+pub mod update_reason;
+
+pub use embedding_update::EmbeddingUpdate;
+pub use feedback_error::{FeedbackError, FeedbackResult};
+pub use feedback_params::FeedbackParams;
+pub use update_reason::UpdateReason;
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use crate::embedding::{cosine, l2_normalize};
+use crate::feedback::embedding_update::EmbeddingUpdate;
+use crate::feedback::feedback_error::{FeedbackError, FeedbackResult};
+use crate::feedback::feedback_params::FeedbackParams;
+use crate::feedback::update_reason::UpdateReason;
+use crate::graph::Graph;
+use crate::time::now;
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - cosine(a, b)
+}
+
+/// `normalise((1 − t) · from + t · to)`.
+fn lerp(from: &[f32], to: &[f32], t: f32) -> Vec<f32> {
+    let mut v: Vec<f32> = from
+        .iter()
+        .zip(to)
+        .map(|(a, b)| (1.0 - t) * a + t * b)
+        .collect();
+    l2_normalize(&mut v);
+    v
+}
+
+impl Graph {
+    /// Retour de pertinence sur les résultats de `search_text(query, …)` :
+    /// les nœuds `relevant` se rapprochent de la requête, les nœuds
+    /// `irrelevant` s'en éloignent (règle de Rocchio, voir `FeedbackParams`).
+    ///
+    /// Le déplacement est borné deux fois : au plus `max_step` par appel, et
+    /// jamais au-delà de `max_drift` de l'encodage du nom, pour que des retours
+    /// répétés ne fassent pas dériver un nœud sans limite. Chaque mise à jour
+    /// est journalisée et consignée dans la piste d'audit (`embedding_log`).
+    /// Rien n'est modifié si un nom est inconnu ou jugé des deux côtés.
+    pub fn apply_feedback(
+        &mut self,
+        query: &str,
+        relevant: &[&str],
+        irrelevant: &[&str],
+        params: &FeedbackParams,
+    ) -> FeedbackResult<Vec<EmbeddingUpdate>> {
+        if let Some(name) = relevant
+            .iter()
+            .chain(irrelevant)
+            .find(|n| !self.name2id.contains_key(**n))
+        {
+            return Err(FeedbackError::UnknownNode(name.to_string()));
+        }
+        // un alias désigne le même nœud que son nom canonique
+        let judged: HashSet<usize> = relevant.iter().map(|n| self.name2id[*n]).collect();
+        if let Some(name) = irrelevant
+            .iter()
+            .find(|n| judged.contains(&self.name2id[**n]))
+        {
+            return Err(FeedbackError::Contradictory(name.to_string()));
+        }
+
+        let q = self.embed(query);
+        let at = now();
+        let judgements = relevant
+            .iter()
+            .map(|n| (*n, true))
+            .chain(irrelevant.iter().map(|n| (*n, false)));
+        let mut updates = vec![];
+        for (name, is_relevant) in judgements {
+            let id = self.name2id[name];
+            let name = self.nodes[id].name.clone();
+            let current = self.nodes[id].emb.clone();
+            let coef = if is_relevant {
+                params.beta
+            } else {
+                -params.gamma
+            };
+            let mut target: Vec<f32> = current
+                .iter()
+                .zip(&q)
+                .map(|(d, q)| params.alpha * d + coef * q)
+                .collect();
+            l2_normalize(&mut target);
+
+            // plus grand pas vers la cible qui respecte les deux bornes
+            let anchor = self.embed(&name);
+            let drift_limit = params.max_drift.max(distance(&anchor, &current));
+            let within = |v: &[f32]| {
+                distance(&current, v) <= params.max_step && distance(&anchor, v) <= drift_limit
+            };
+            let (emb, bounded) = if within(&target) {
+                (target, false)
+            } else {
+                let (mut lo, mut hi) = (0.0f32, 1.0f32);
+                for _ in 0..24 {
+                    let mid = (lo + hi) / 2.0;
+                    if within(&lerp(&current, &target, mid)) {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                if lo == 0.0 {
+                    continue;
+                }
+                (lerp(&current, &target, lo), true)
+            };
+            let reason = UpdateReason::Feedback {
+                query: query.to_string(),
+                relevant: is_relevant,
+                bounded,
+            };
+            if let Some(update) = self.set_node_embedding_at(&name, &emb, at, reason) {
+                updates.push(update);
+            }
+        }
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solar_system() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Charon", "orbite", "Pluton");
+        g
+    }
+
+    fn emb(g: &Graph, name: &str) -> Vec<f32> {
+        g.nodes[g.name2id[name]].emb.clone()
+    }
+
+    #[test]
+    fn repeated_feedback_respects_step_and_drift_bounds() {
+        let mut g = solar_system();
+        let params = FeedbackParams {
+            max_step: 0.01,
+            max_drift: 0.05,
+            ..FeedbackParams::default()
+        };
+        let anchor = g.embed("Pluton");
+        // la requête est plus loin du nom que `max_drift` : la dérive sera plafonnée
+        assert!(distance(&anchor, &g.embed("planète naine glacée")) > params.max_drift);
+        let mut bounded = 0;
+        for _ in 0..50 {
+            let before = emb(&g, "Pluton");
+            let updates = g
+                .apply_feedback("planète naine glacée", &["Pluton"], &[], &params)
+                .unwrap();
+            for u in &updates {
+                assert!(u.drift <= params.max_step + 1e-4, "pas {}", u.drift);
+                if let UpdateReason::Feedback { bounded: true, .. } = u.reason {
+                    bounded += 1;
+                }
+            }
+            let after = emb(&g, "Pluton");
+            assert!(distance(&before, &after) <= params.max_step + 1e-4);
+            assert!(distance(&anchor, &after) <= params.max_drift + 1e-4);
+        }
+        // la dérive a fini par être plafonnée
+        assert!(bounded > 0);
+        assert!(distance(&anchor, &emb(&g, "Pluton")) > params.max_drift - 0.01);
+    }
+
+    #[test]
+    fn rejects_unknown_and_contradictory_judgements() {
+        let mut g = solar_system();
+        let params = FeedbackParams::default();
+        assert_eq!(
+            g.apply_feedback("q", &["Vulcain"], &[], &params),
+            Err(FeedbackError::UnknownNode("Vulcain".into()))
+        );
+        assert_eq!(
+            g.apply_feedback("q", &["Pluton"], &["Pluton"], &params),
+            Err(FeedbackError::Contradictory("Pluton".into()))
+        );
+        assert!(g.embedding_log().is_empty());
+    }
+
+    #[test]
+    fn updates_are_recorded_under_the_canonical_name() {
+        let mut g = solar_system();
+        g.add_node("Pluto");
+        g.merge_nodes("Pluton", "Pluto").unwrap();
+        let updates = g
+            .apply_feedback("planète naine", &["Pluto"], &[], &FeedbackParams::default())
+            .unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].node, "Pluton");
+        assert_eq!(g.embedding_history("Pluton").len(), 1);
+        // l'alias donne accès à la même histoire
+        assert_eq!(g.embedding_history("Pluto"), g.embedding_history("Pluton"));
+
+        // nom canonique et alias désignent le même nœud
+        assert_eq!(
+            g.apply_feedback("q", &["Pluton"], &["Pluto"], &FeedbackParams::default()),
+            Err(FeedbackError::Contradictory("Pluto".into()))
+        );
+    }
+}
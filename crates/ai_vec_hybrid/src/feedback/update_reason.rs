@@ -0,0 +1,54 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Origine d'une mise à jour d'embedding (piste d'audit).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UpdateReason {
+    /// Vecteur fourni tel quel.
+    Manual,
+    /// `set_node_embedding_ema`.
+    Ema { text: String, alpha: f32 },
+    /// Retour de pertinence sur un résultat de `search_text`.
+    Feedback {
+        query: String,
+        relevant: bool,
+        /// Le pas ou la dérive ont été plafonnés.
+        bounded: bool,
+    },
+    /// Mélange avec des embeddings de graphe appris.
+    Blend { weight: f32 },
+    /// Ré-encodage du nom par un nouvel embedder.
+    Reencode { embedder: String },
+    /// Retour à une version antérieure.
+    Rollback { version: usize },
+}
+
+impl fmt::Display for UpdateReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateReason::Manual => write!(f, "vecteur fourni"),
+            UpdateReason::Ema { text, alpha } => write!(f, "EMA « {text} » (alpha={alpha})"),
+            UpdateReason::Feedback {
+                query,
+                relevant,
+                bounded,
+            } => {
+                let verdict = if *relevant {
+                    "pertinent"
+                } else {
+                    "non pertinent"
+                };
+                write!(f, "{verdict} pour « {query} »")?;
+                if *bounded {
+                    write!(f, " (plafonné)")?;
+                }
+                Ok(())
+            }
+            UpdateReason::Blend { weight } => write!(f, "mélange KGE (poids={weight})"),
+            UpdateReason::Reencode { embedder } => write!(f, "ré-encodage ({embedder})"),
+            UpdateReason::Rollback { version } => write!(f, "retour à la version {version}"),
+        }
+    }
+}
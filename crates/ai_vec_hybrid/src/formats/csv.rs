@@ -0,0 +1,170 @@
+use std::io::{BufRead, Write};
+
+use crate::fact::Fact;
+use crate::formats::format_error::{syntax, FormatResult};
+use crate::provenance::Provenance;
+
+const HEADER: [&str; 5] = ["subj", "rel", "obj", "confidence", "source"];
+
+/// Lecteur CSV (RFC 4180) en flux : colonnes `subj,rel,obj[,confidence[,source]]`.
+///
+/// Une ligne d'en-tête (`subj,rel,obj`…) est ignorée si présente. Les champs
+/// entre guillemets peuvent contenir virgules, `""` et retours à la ligne ;
+/// une confiance ou une source vide prend la valeur par défaut.
+pub struct CsvReader<R: BufRead> {
+    input: R,
+    line: usize,
+    first: bool,
+    done: bool,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            line: 0,
+            first: true,
+            done: false,
+        }
+    }
+
+    /// Lit un enregistrement (éventuellement sur plusieurs lignes physiques) ;
+    /// renvoie la ligne où il commence et ses champs.
+    fn record(&mut self) -> FormatResult<Option<(usize, Vec<String>)>> {
+        let mut fields = vec![];
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut start = 0;
+        loop {
+            let mut buf = String::new();
+            if self.input.read_line(&mut buf)? == 0 {
+                if quoted {
+                    return syntax(start, "guillemet non fermé");
+                }
+                return Ok(None);
+            }
+            self.line += 1;
+            if !quoted {
+                start = self.line;
+                if buf.trim().is_empty() {
+                    continue;
+                }
+            }
+            let mut chars = buf.chars().peekable();
+            while let Some(c) = chars.next() {
+                match (quoted, c) {
+                    (true, '"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    (true, '"') => {
+                        quoted = false;
+                        if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                            return syntax(self.line, "caractère après un guillemet fermant");
+                        }
+                    }
+                    (true, c) => field.push(c),
+                    (false, '"') if field.is_empty() => quoted = true,
+                    (false, '"') => {
+                        return syntax(self.line, "guillemet au milieu d'un champ non cité")
+                    }
+                    (false, ',') => fields.push(std::mem::take(&mut field)),
+                    (false, '\r' | '\n') => {}
+                    (false, c) => field.push(c),
+                }
+            }
+            if !quoted {
+                fields.push(field);
+                return Ok(Some((start, fields)));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CsvReader<R> {
+    type Item = FormatResult<Fact>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (line, fields) = match self.record() {
+                Ok(Some(r)) => r,
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+            if std::mem::take(&mut self.first) && is_header(&fields) {
+                continue;
+            }
+            let fact = parse_record(line, fields);
+            self.done = fact.is_err();
+            return Some(fact);
+        }
+        None
+    }
+}
+
+fn is_header(fields: &[String]) -> bool {
+    fields.len() >= 3
+        && fields.len() <= HEADER.len()
+        && fields.iter().zip(HEADER).all(|(f, h)| f == h)
+}
+
+fn parse_record(line: usize, fields: Vec<String>) -> FormatResult<Fact> {
+    if !(3..=5).contains(&fields.len()) {
+        return syntax(
+            line,
+            format!(
+                "3 à 5 champs attendus (subj,rel,obj[,confidence[,source]]), {} trouvés",
+                fields.len()
+            ),
+        );
+    }
+    let mut it = fields.into_iter();
+    let (subj, rel, obj) = (it.next().unwrap(), it.next().unwrap(), it.next().unwrap());
+    if subj.is_empty() || rel.is_empty() || obj.is_empty() {
+        return syntax(line, "champ vide");
+    }
+    let confidence = match it.next().filter(|c| !c.is_empty()) {
+        None => 1.0,
+        Some(c) => match c.trim().parse::<f32>() {
+            Ok(c) if (0.0..=1.0).contains(&c) => c,
+            _ => return syntax(line, format!("confiance invalide '{c}' (attendu : 0 à 1)")),
+        },
+    };
+    let source = it.next().filter(|s| !s.is_empty());
+    Ok(Fact::new(subj, rel, obj).with_provenance(Provenance::new(confidence, source)))
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Écrit les faits en CSV avec une ligne d'en-tête ; les colonnes
+/// `confidence,source` ne sont écrites que si un fait a une provenance.
+pub fn write_csv<'a, W: Write>(
+    facts: impl IntoIterator<Item = &'a Fact>,
+    w: &mut W,
+) -> FormatResult<()> {
+    let facts: Vec<&Fact> = facts.into_iter().collect();
+    let with_prov = facts.iter().any(|f| !f.provenance.is_default());
+    let columns = if with_prov { 5 } else { 3 };
+    writeln!(w, "{}", HEADER[..columns].join(","))?;
+    for f in facts {
+        write!(w, "{},{},{}", quote(&f.subj), quote(&f.rel), quote(&f.obj))?;
+        if with_prov {
+            let source = f.provenance.source.as_deref().unwrap_or("");
+            write!(w, ",{},{}", f.provenance.confidence, quote(source))?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Formats d'échange de faits reconnus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Tableau JSON natif (`facts.json`).
+    Json,
+    NTriples,
+    Turtle,
+    Csv,
+    JsonLd,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Json,
+        Format::NTriples,
+        Format::Turtle,
+        Format::Csv,
+        Format::JsonLd,
+    ];
+
+    /// Format déduit de l'extension du fichier.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::NTriples => "nt",
+            Format::Turtle => "ttl",
+            Format::Csv => "csv",
+            Format::JsonLd => "jsonld",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    /// Extension ou nom du format (`nt`, `ntriples`, `turtle`, `json-ld`...).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "nt" | "ntriples" | "n-triples" => Ok(Format::NTriples),
+            "ttl" | "turtle" => Ok(Format::Turtle),
+            "csv" => Ok(Format::Csv),
+            "jsonld" | "json-ld" => Ok(Format::JsonLd),
+            _ => Err(format!("format inconnu '{s}' (json, nt, ttl, csv, jsonld)")),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
@@ -0,0 +1,33 @@
+#[derive(thiserror::Error, Debug)]
+pub enum FormatError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("ligne {line} : {message}")]
+    Syntax { line: usize, message: String },
+    /// Erreur de structure sans position de ligne (p. ex. chemin JSON-LD).
+    #[error("{0}")]
+    Invalid(String),
+}
+
+pub type FormatResult<T> = Result<T, FormatError>;
+
+pub(crate) fn syntax<T>(line: usize, message: impl Into<String>) -> FormatResult<T> {
+    Err(FormatError::Syntax {
+        line,
+        message: message.into(),
+    })
+}
+
+/// Erreur serde_json : ligne conservée, position retirée du message.
+pub(crate) fn from_json(e: serde_json::Error) -> FormatError {
+    if e.classify() == serde_json::error::Category::Io {
+        return FormatError::Io(e.into());
+    }
+    let line = e.line();
+    let message = e.to_string();
+    let message = match message.rfind(" at line ") {
+        Some(pos) => message[..pos].to_string(),
+        None => message,
+    };
+    FormatError::Syntax { line, message }
+}
@@ -0,0 +1,214 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::fact::Fact;
+use crate::formats::csv::{write_csv, CsvReader};
+use crate::formats::fact_format::Format;
+use crate::formats::format_error::{FormatError, FormatResult};
+use crate::formats::iri::Namespace;
+use crate::formats::json_facts::{read_json_facts, write_json_facts};
+use crate::formats::jsonld::{read_jsonld, write_jsonld};
+use crate::formats::ntriples::{read_ntriples, write_ntriples};
+use crate::formats::turtle::{write_turtle, TurtleReader};
+use crate::graph::Graph;
+
+/// Itérateur de faits ; s'arrête après la première erreur.
+pub type FactIter<'a> = Box<dyn Iterator<Item = FormatResult<Fact>> + 'a>;
+
+/// Lit des faits au format donné. N-Triples, Turtle et CSV sont lus en
+/// flux ; JSON et JSON-LD sont chargés en entier.
+pub fn read_facts<'a>(format: Format, input: impl BufRead + 'a, ns: &Namespace) -> FactIter<'a> {
+    let all = |r: FormatResult<Vec<Fact>>| -> FactIter<'a> {
+        match r {
+            Ok(facts) => Box::new(facts.into_iter().map(Ok)),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        }
+    };
+    match format {
+        Format::Json => all(read_json_facts(input)),
+        Format::JsonLd => all(read_jsonld(input, ns)),
+        Format::NTriples => Box::new(read_ntriples(input, ns.clone())),
+        Format::Turtle => Box::new(TurtleReader::new(input, ns.clone())),
+        Format::Csv => Box::new(CsvReader::new(input)),
+    }
+}
+
+pub fn write_facts<'a, W: Write>(
+    format: Format,
+    facts: impl IntoIterator<Item = &'a Fact>,
+    ns: &Namespace,
+    w: &mut W,
+) -> FormatResult<()> {
+    match format {
+        Format::Json => write_json_facts(facts, w),
+        Format::NTriples => write_ntriples(facts, ns, w),
+        Format::Turtle => write_turtle(facts, ns, w),
+        Format::Csv => write_csv(facts, w),
+        Format::JsonLd => write_jsonld(facts, ns, w),
+    }
+}
+
+fn format_of(path: &Path, format: Option<Format>) -> FormatResult<Format> {
+    format.or_else(|| Format::from_path(path)).ok_or_else(|| {
+        FormatError::Invalid(format!(
+            "{} : format non reconnu d'après l'extension",
+            path.display()
+        ))
+    })
+}
+
+impl Graph {
+    /// Importe un fichier de faits (format explicite ou déduit de l'extension).
+    /// Renvoie le nombre de faits lus ; à la première erreur, les faits déjà
+    /// lus restent dans le graphe. Les faits sans source ont pour source le
+    /// chemin du fichier.
+    pub fn import_path(
+        &mut self,
+        path: impl AsRef<Path>,
+        format: Option<Format>,
+    ) -> FormatResult<usize> {
+        let path = path.as_ref();
+        let format = format_of(path, format)?;
+        let input = BufReader::new(File::open(path)?);
+        let source = path.display().to_string();
+        self.import_facts(
+            read_facts(format, input, &Namespace::default()),
+            Some(&source),
+        )
+    }
+
+    /// `source` : source par défaut des faits qui n'en ont pas.
+    pub fn import_facts(
+        &mut self,
+        facts: impl IntoIterator<Item = FormatResult<Fact>>,
+        source: Option<&str>,
+    ) -> FormatResult<usize> {
+        let mut n = 0;
+        for fact in facts {
+            let mut f = fact?;
+            if f.provenance.source.is_none() {
+                f.provenance.source = source.map(str::to_string);
+            }
+            self.add_fact(&f);
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    /// Faits de base courants (hors faits dérivés), triés par sujet.
+    pub fn base_facts(&self) -> Vec<Fact> {
+        let mut facts: Vec<Fact> = self
+            .edges
+            .iter()
+            .filter(|e| !e.derived)
+            .map(|e| {
+                Fact::new(&self.nodes[e.from].name, &e.rel, &self.nodes[e.to].name)
+                    .with_provenance(e.provenance.clone())
+            })
+            .collect();
+        facts.sort_by(|a, b| a.subj.cmp(&b.subj));
+        facts
+    }
+
+    /// Exporte les faits de base courants ; renvoie leur nombre.
+    pub fn export_path(
+        &self,
+        path: impl AsRef<Path>,
+        format: Option<Format>,
+    ) -> FormatResult<usize> {
+        let path = path.as_ref();
+        let format = format_of(path, format)?;
+        let facts = self.base_facts();
+        let mut w = BufWriter::new(File::create(path)?);
+        write_facts(format, &facts, &Namespace::default(), &mut w)?;
+        w.flush()?;
+        Ok(facts.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> Vec<Fact> {
+        vec![
+            Fact::new("Pluton", "est_une", "planète naine"),
+            Fact::new("Pluton", "nom_complet", "134340 \"Pluton\", ex-planète"),
+            Fact::new("Terre", "orbite", "Soleil"),
+            Fact::new("http://example.org/Vénus", "orbite", "Soleil"),
+        ]
+    }
+
+    fn triples(facts: &[Fact]) -> Vec<(&str, &str, &str)> {
+        facts
+            .iter()
+            .map(|f| (f.subj.as_str(), f.rel.as_str(), f.obj.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let ns = Namespace::default();
+        for format in Format::ALL {
+            let mut out = vec![];
+            write_facts(format, &facts(), &ns, &mut out).unwrap();
+            let read: Vec<Fact> = read_facts(format, out.as_slice(), &ns)
+                .collect::<FormatResult<_>>()
+                .unwrap_or_else(|e| panic!("{format} : {e}"));
+            assert_eq!(triples(&read), triples(&facts()), "{format}");
+        }
+    }
+
+    #[test]
+    fn syntax_errors_carry_their_line() {
+        let ns = Namespace::default();
+        let line_of = |format: Format, src: &str| match read_facts(format, src.as_bytes(), &ns)
+            .find_map(Result::err)
+        {
+            Some(FormatError::Syntax { line, .. }) => line,
+            other => panic!("{format} : erreur de syntaxe attendue, obtenu {other:?}"),
+        };
+        assert_eq!(
+            line_of(
+                Format::Csv,
+                "subj,rel,obj\nTerre,orbite,Soleil\nMars,orbite\n"
+            ),
+            3
+        );
+        assert_eq!(line_of(Format::Csv, "a,b,c\n\"ouvert,b,c\n"), 2);
+        assert_eq!(
+            line_of(
+                Format::Turtle,
+                "@prefix kg: <http://x/> .\n\nkg:a kg:b kg:c .\nkg:a kg:b .\n"
+            ),
+            4
+        );
+        assert_eq!(
+            line_of(
+                Format::NTriples,
+                "<http://x/a> <http://x/b> <http://x/c> .\n<http://x/a> <http://x/b>\n"
+            ),
+            2
+        );
+        assert_eq!(
+            line_of(Format::Json, "[\n  {\"subj\": \"a\", \"rel\": \"b\"}\n]"),
+            2
+        );
+    }
+
+    #[test]
+    fn import_keeps_facts_read_before_an_error() {
+        let mut g = Graph::new();
+        let facts = read_facts(
+            Format::Csv,
+            "Terre,orbite,Soleil\nMars,orbite\n".as_bytes(),
+            &Namespace::default(),
+        );
+        let err = g.import_facts(facts, Some("test.csv")).unwrap_err();
+        assert!(matches!(err, FormatError::Syntax { line: 2, .. }));
+        let base = g.base_facts();
+        assert_eq!(triples(&base), [("Terre", "orbite", "Soleil")]);
+        assert_eq!(base[0].provenance.source.as_deref(), Some("test.csv"));
+    }
+}
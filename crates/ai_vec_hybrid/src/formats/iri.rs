@@ -0,0 +1,91 @@
+/// Espace de noms par défaut des nœuds et relations exportés.
+pub const DEFAULT_BASE: &str = "http://ai-search.local/kg/";
+
+/// Correspondance nom de nœud ⇄ IRI : `base` + nom encodé en pourcentage
+/// pour les caractères interdits dans un IRI. Un IRI hors de `base` garde
+/// sa forme complète comme nom.
+#[derive(Clone, Debug)]
+pub struct Namespace {
+    pub base: String,
+}
+
+impl Default for Namespace {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE)
+    }
+}
+
+impl Namespace {
+    pub fn new(base: &str) -> Self {
+        Self {
+            base: base.to_string(),
+        }
+    }
+
+    pub fn to_iri(&self, name: &str) -> String {
+        if is_absolute_iri(name) {
+            return name.to_string();
+        }
+        format!("{}{}", self.base, encode(name))
+    }
+
+    pub fn to_name(&self, iri: &str) -> String {
+        match iri.strip_prefix(&self.base) {
+            Some(local) => decode(local),
+            None => iri.to_string(),
+        }
+    }
+}
+
+/// `scheme:...` avec un schéma RFC 3986 (les noms blancs `_:b` n'en sont pas).
+pub fn is_absolute_iri(s: &str) -> bool {
+    let Some((scheme, _)) = s.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && s.contains("//")
+}
+
+fn encode(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_control()
+            || c.is_whitespace()
+            || matches!(
+                c,
+                '<' | '>' | '"' | '{' | '}' | '|' | '\\' | '^' | '`' | '%' | '#' | '?'
+            )
+        {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{b:02X}"));
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(b) = s
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
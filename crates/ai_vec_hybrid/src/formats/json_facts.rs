@@ -0,0 +1,24 @@
+use std::io::{Read, Write};
+
+use crate::fact::Fact;
+use crate::formats::format_error::{from_json, FormatResult};
+
+/// Lit le format natif : un tableau JSON `[{"subj", "rel", "obj"}, ...]`.
+pub fn read_json_facts<R: Read>(input: R) -> FormatResult<Vec<Fact>> {
+    serde_json::from_reader(input).map_err(from_json)
+}
+
+/// Écrit le format natif, un fait par ligne.
+pub fn write_json_facts<'a, W: Write>(
+    facts: impl IntoIterator<Item = &'a Fact>,
+    w: &mut W,
+) -> FormatResult<()> {
+    write!(w, "[")?;
+    for (i, f) in facts.into_iter().enumerate() {
+        let sep = if i == 0 { "" } else { "," };
+        let f = serde_json::to_string(f).map_err(std::io::Error::from)?;
+        write!(w, "{sep}\n  {f}")?;
+    }
+    writeln!(w, "\n]")?;
+    Ok(())
+}
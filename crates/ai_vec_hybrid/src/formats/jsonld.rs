@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::fact::Fact;
+use crate::formats::format_error::{from_json, FormatError, FormatResult};
+use crate::formats::iri::{is_absolute_iri, Namespace};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// Contexte JSON-LD simplifié : `@vocab`, `@base` et préfixes `p: iri`.
+#[derive(Default)]
+struct Context {
+    vocab: Option<String>,
+    base: Option<String>,
+    terms: HashMap<String, String>,
+}
+
+impl Context {
+    fn extend(&mut self, ctx: &Value, path: &str) -> FormatResult<()> {
+        match ctx {
+            Value::Null => *self = Context::default(),
+            Value::Array(items) => {
+                for (i, c) in items.iter().enumerate() {
+                    self.extend(c, &format!("{path}[{i}]"))?;
+                }
+            }
+            Value::Object(map) => {
+                for (k, v) in map {
+                    let Some(s) = v.as_str() else {
+                        // définitions étendues : seule `@id` est retenue
+                        if let Some(id) = v.get("@id").and_then(Value::as_str) {
+                            self.terms.insert(k.clone(), id.to_string());
+                            continue;
+                        }
+                        return invalid(format!("{path}.{k}"), "chaîne attendue");
+                    };
+                    match k.as_str() {
+                        "@vocab" => self.vocab = Some(s.to_string()),
+                        "@base" => self.base = Some(s.to_string()),
+                        _ => {
+                            self.terms.insert(k.clone(), s.to_string());
+                        }
+                    }
+                }
+            }
+            Value::String(_) => return invalid(path, "contextes distants non supportés"),
+            _ => return invalid(path, "contexte invalide"),
+        }
+        Ok(())
+    }
+
+    /// Développe une clé de propriété (terme, préfixe ou `@vocab`).
+    fn expand_vocab(&self, key: &str) -> String {
+        if let Some(iri) = self.terms.get(key) {
+            return self.expand_prefix(iri);
+        }
+        if is_absolute_iri(key) {
+            return key.to_string();
+        }
+        if key.contains(':') {
+            return self.expand_prefix(key);
+        }
+        match &self.vocab {
+            Some(v) => format!("{v}{key}"),
+            None => key.to_string(),
+        }
+    }
+
+    /// Développe une valeur `@id` (préfixe ou `@base`).
+    fn expand_id(&self, id: &str) -> String {
+        if is_absolute_iri(id) || id.starts_with("_:") {
+            return id.to_string();
+        }
+        if id.contains(':') {
+            return self.expand_prefix(id);
+        }
+        match &self.base {
+            Some(b) => format!("{b}{id}"),
+            None => id.to_string(),
+        }
+    }
+
+    fn expand_prefix(&self, s: &str) -> String {
+        match s.split_once(':') {
+            Some((p, local)) if !local.starts_with("//") => match self.terms.get(p) {
+                Some(ns) => format!("{ns}{local}"),
+                None => s.to_string(),
+            },
+            _ => s.to_string(),
+        }
+    }
+}
+
+fn invalid<T>(path: impl AsRef<str>, message: &str) -> FormatResult<T> {
+    Err(FormatError::Invalid(format!(
+        "{} : {message}",
+        path.as_ref()
+    )))
+}
+
+/// Importe un document JSON-LD (forme compacte ou développée, sous-ensemble).
+///
+/// Le document est lu en entier : JSON-LD n'est pas un format ligne à ligne.
+/// Les erreurs de syntaxe JSON donnent la ligne ; les erreurs de structure,
+/// le chemin dans le document (`$.@graph[2].orbite`).
+pub fn read_jsonld<R: Read>(input: R, ns: &Namespace) -> FormatResult<Vec<Fact>> {
+    let doc: Value = serde_json::from_reader(input).map_err(from_json)?;
+    let mut out = vec![];
+    let mut blank = 0;
+    let ctx = Context::default();
+    match &doc {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                node(item, &ctx, ns, &format!("$[{i}]"), &mut blank, &mut out)?;
+            }
+        }
+        Value::Object(_) => {
+            node(&doc, &ctx, ns, "$", &mut blank, &mut out)?;
+        }
+        _ => return invalid("$", "objet ou tableau attendu"),
+    }
+    Ok(out)
+}
+
+/// Parcourt un objet nœud ; renvoie son nom (ou `None` pour un conteneur
+/// `@graph` sans `@id`).
+fn node(
+    value: &Value,
+    parent: &Context,
+    ns: &Namespace,
+    path: &str,
+    blank: &mut usize,
+    out: &mut Vec<Fact>,
+) -> FormatResult<Option<String>> {
+    let Value::Object(map) = value else {
+        return invalid(path, "objet nœud attendu");
+    };
+    let mut owned;
+    let mut ctx = parent;
+    if let Some(c) = map.get("@context") {
+        owned = Context {
+            vocab: parent.vocab.clone(),
+            base: parent.base.clone(),
+            terms: parent.terms.clone(),
+        };
+        owned.extend(c, &format!("{path}.@context"))?;
+        ctx = &owned;
+    }
+
+    let subj = match map.get("@id") {
+        Some(Value::String(id)) => Some(ns.to_name(&ctx.expand_id(id))),
+        Some(_) => return invalid(format!("{path}.@id"), "chaîne attendue"),
+        None => None,
+    };
+
+    if let Some(graph) = map.get("@graph") {
+        let Value::Array(items) = graph else {
+            return invalid(format!("{path}.@graph"), "tableau attendu");
+        };
+        for (i, item) in items.iter().enumerate() {
+            node(item, ctx, ns, &format!("{path}.@graph[{i}]"), blank, out)?;
+        }
+        if subj.is_none() && properties(map).next().is_none() {
+            return Ok(None);
+        }
+    }
+
+    let subj = subj.unwrap_or_else(|| {
+        *blank += 1;
+        format!("_:b{blank}")
+    });
+
+    if let Some(types) = map.get("@type") {
+        let rel = ns.to_name(RDF_TYPE);
+        for (i, t) in as_list(types).enumerate() {
+            let Some(t) = t.as_str() else {
+                return invalid(format!("{path}.@type[{i}]"), "chaîne attendue");
+            };
+            let obj = ns.to_name(&ctx.expand_vocab(t));
+            out.push(Fact::new(subj.clone(), rel.clone(), obj));
+        }
+    }
+
+    for (key, v) in properties(map) {
+        let rel = ns.to_name(&ctx.expand_vocab(key));
+        for (i, item) in as_list(v).enumerate() {
+            let p = format!("{path}.{key}[{i}]");
+            let obj = match item {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                Value::Object(o) if o.contains_key("@value") => match &o["@value"] {
+                    Value::String(s) => s.clone(),
+                    Value::Null => return invalid(format!("{p}.@value"), "valeur nulle"),
+                    other => other.to_string(),
+                },
+                Value::Object(o) if o.len() == 1 && o.contains_key("@id") => {
+                    match o["@id"].as_str() {
+                        Some(id) => ns.to_name(&ctx.expand_id(id)),
+                        None => return invalid(format!("{p}.@id"), "chaîne attendue"),
+                    }
+                }
+                Value::Object(_) => match node(item, ctx, ns, &p, blank, out)? {
+                    Some(name) => name,
+                    None => return invalid(p, "nœud imbriqué sans sujet"),
+                },
+                Value::Null => continue,
+                Value::Array(_) => return invalid(p, "listes imbriquées non supportées"),
+            };
+            out.push(Fact::new(subj.clone(), rel.clone(), obj));
+        }
+    }
+    Ok(Some(subj))
+}
+
+fn properties(map: &Map<String, Value>) -> impl Iterator<Item = (&String, &Value)> {
+    map.iter().filter(|(k, _)| !k.starts_with('@'))
+}
+
+fn as_list(v: &Value) -> Box<dyn Iterator<Item = &Value> + '_> {
+    match v {
+        Value::Array(items) => Box::new(items.iter()),
+        other => Box::new(std::iter::once(other)),
+    }
+}
+
+/// Écrit les faits en JSON-LD compact : un objet par sujet dans `@graph`,
+/// relations et nœuds relatifs à l'espace de noms (`@vocab` / `@base`).
+pub fn write_jsonld<'a, W: Write>(
+    facts: impl IntoIterator<Item = &'a Fact>,
+    ns: &Namespace,
+    w: &mut W,
+) -> FormatResult<()> {
+    let mut order: Vec<&str> = vec![];
+    let mut subjects: HashMap<&str, Map<String, Value>> = HashMap::new();
+    for f in facts {
+        let entry = subjects.entry(f.subj.as_str()).or_insert_with(|| {
+            order.push(f.subj.as_str());
+            let mut m = Map::new();
+            m.insert("@id".into(), Value::String(f.subj.clone()));
+            m
+        });
+        let objs = entry
+            .entry(relative(ns, &f.rel))
+            .or_insert_with(|| Value::Array(vec![]));
+        if let Value::Array(items) = objs {
+            items.push(json!({ "@id": f.obj }));
+        }
+    }
+    let graph: Vec<Value> = order
+        .into_iter()
+        .map(|s| Value::Object(subjects.remove(s).expect("sujet enregistré")))
+        .collect();
+    let doc = json!({
+        "@context": { "@vocab": ns.base, "@base": ns.base },
+        "@graph": graph,
+    });
+    serde_json::to_writer_pretty(&mut *w, &doc).map_err(std::io::Error::from)?;
+    writeln!(w)?;
+    Ok(())
+}
+
+/// Nom relatif à `@vocab` ; une relation qui ressemble à un IRI ou à un
+/// mot-clé est écrite sous sa forme complète.
+fn relative(ns: &Namespace, rel: &str) -> String {
+    if rel.starts_with('@') || rel.contains(':') {
+        ns.to_iri(rel)
+    } else {
+        rel.to_string()
+    }
+}
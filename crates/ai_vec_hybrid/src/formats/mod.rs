@@ -0,0 +1,14 @@
+pub mod csv;
+pub mod fact_format;
+pub mod format_error;
+pub mod import_export;
+pub mod iri;
+pub mod json_facts;
+pub mod jsonld;
+pub mod ntriples;
+pub mod turtle;
+
+pub use fact_format::Format;
+pub use format_error::{FormatError, FormatResult};
+pub use import_export::{read_facts, write_facts, FactIter};
+pub use iri::Namespace;
@@ -0,0 +1,29 @@
+use std::io::{BufRead, Write};
+
+use crate::fact::Fact;
+use crate::formats::format_error::FormatResult;
+use crate::formats::iri::Namespace;
+use crate::formats::turtle::TurtleReader;
+
+/// Lecteur N-Triples : le N-Triples est un sous-ensemble du Turtle.
+pub fn read_ntriples<R: BufRead>(input: R, ns: Namespace) -> TurtleReader<R> {
+    TurtleReader::new(input, ns)
+}
+
+/// Écrit les faits en N-Triples (un triplet d'IRIs par ligne).
+pub fn write_ntriples<'a, W: Write>(
+    facts: impl IntoIterator<Item = &'a Fact>,
+    ns: &Namespace,
+    w: &mut W,
+) -> FormatResult<()> {
+    for f in facts {
+        writeln!(
+            w,
+            "<{}> <{}> <{}> .",
+            ns.to_iri(&f.subj),
+            ns.to_iri(&f.rel),
+            ns.to_iri(&f.obj)
+        )?;
+    }
+    Ok(())
+}
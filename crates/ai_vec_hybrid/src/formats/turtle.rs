@@ -0,0 +1,418 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Write};
+
+use crate::fact::Fact;
+use crate::formats::format_error::{syntax, FormatError, FormatResult};
+use crate::formats::iri::{is_absolute_iri, Namespace};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Iri(String),
+    PName(String, String),
+    BNode(String),
+    Literal(String),
+    A,
+    /// `@prefix` (terminé par `.`) ou `PREFIX` (style SPARQL, sans `.`).
+    Prefix {
+        sparql: bool,
+    },
+    Base {
+        sparql: bool,
+    },
+    Dot,
+    Semi,
+    Comma,
+    /// `[`, `]`, `(`, `)` : listes et nœuds blancs anonymes, non supportés.
+    Unsupported(char),
+}
+
+/// Lecteur Turtle en flux (sous-ensemble) ; lit aussi le N-Triples.
+///
+/// Supporté : `@prefix`/`PREFIX`, `@base`/`BASE`, IRIs, noms préfixés, `a`,
+/// littéraux (avec `@lang` ou `^^type`, ignorés), nœuds blancs `_:x`,
+/// listes `;` et `,`. Les littéraux deviennent des nœuds portant leur texte.
+/// Les instructions sont lues une à une : la mémoire ne dépend pas de la
+/// taille du fichier.
+pub struct TurtleReader<R: BufRead> {
+    input: R,
+    line: usize,
+    ns: Namespace,
+    prefixes: HashMap<String, String>,
+    base: Option<String>,
+    tokens: VecDeque<(Tok, usize)>,
+    pending: VecDeque<Fact>,
+    done: bool,
+}
+
+impl<R: BufRead> TurtleReader<R> {
+    pub fn new(input: R, ns: Namespace) -> Self {
+        Self {
+            input,
+            line: 0,
+            ns,
+            prefixes: HashMap::new(),
+            base: None,
+            tokens: VecDeque::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Lit des lignes jusqu'à disposer d'une instruction complète et l'analyse.
+    fn fill(&mut self) -> FormatResult<()> {
+        loop {
+            // directives SPARQL : pas de point final
+            match self.tokens.front() {
+                Some((Tok::Prefix { sparql: true }, _)) if self.tokens.len() >= 3 => {
+                    let stmt: Vec<_> = self.tokens.drain(..3).collect();
+                    return self.directive(&stmt);
+                }
+                Some((Tok::Base { sparql: true }, _)) if self.tokens.len() >= 2 => {
+                    let stmt: Vec<_> = self.tokens.drain(..2).collect();
+                    return self.directive(&stmt);
+                }
+                _ => {}
+            }
+            if let Some(end) = self.tokens.iter().position(|(t, _)| *t == Tok::Dot) {
+                let stmt: Vec<_> = self.tokens.drain(..=end).collect();
+                return self.statement(&stmt[..stmt.len() - 1], stmt[end].1);
+            }
+
+            let mut buf = String::new();
+            if self.input.read_line(&mut buf)? == 0 {
+                self.done = true;
+                return match self.tokens.front() {
+                    Some((_, line)) => syntax(*line, "instruction non terminée par '.'"),
+                    None => Ok(()),
+                };
+            }
+            self.line += 1;
+            let toks = tokenize_line(&buf, self.line)?;
+            self.tokens.extend(toks.into_iter().map(|t| (t, self.line)));
+        }
+    }
+
+    fn directive(&mut self, stmt: &[(Tok, usize)]) -> FormatResult<()> {
+        let line = stmt[0].1;
+        match stmt {
+            [(Tok::Prefix { .. }, _), (Tok::PName(p, local), _), (Tok::Iri(iri), _)]
+                if local.is_empty() =>
+            {
+                let iri = self.resolve_relative(iri);
+                self.prefixes.insert(p.clone(), iri);
+                Ok(())
+            }
+            [(Tok::Base { .. }, _), (Tok::Iri(iri), _)] => {
+                self.base = Some(self.resolve_relative(iri));
+                Ok(())
+            }
+            [(Tok::Prefix { .. }, _), ..] => syntax(line, "attendu : @prefix p: <iri> ."),
+            _ => syntax(line, "attendu : @base <iri> ."),
+        }
+    }
+
+    fn statement(&mut self, stmt: &[(Tok, usize)], line: usize) -> FormatResult<()> {
+        if stmt.is_empty() {
+            return syntax(line, "instruction vide");
+        }
+        if matches!(stmt[0].0, Tok::Prefix { .. } | Tok::Base { .. }) {
+            return self.directive(stmt);
+        }
+        let mut it = stmt.iter().peekable();
+        let (tok, l) = it.next().expect("non vide");
+        let subj = self.node(tok, *l, "sujet")?;
+        loop {
+            let Some((tok, l)) = it.next() else {
+                return syntax(line, "prédicat attendu après le sujet");
+            };
+            let rel = match tok {
+                Tok::A => self
+                    .ns
+                    .to_name("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+                Tok::Literal(_) | Tok::BNode(_) => {
+                    return syntax(*l, "un prédicat doit être un IRI")
+                }
+                other => self.node(other, *l, "prédicat")?,
+            };
+            loop {
+                let Some((tok, l)) = it.next() else {
+                    return syntax(line, "objet attendu");
+                };
+                let obj = self.node(tok, *l, "objet")?;
+                self.pending
+                    .push_back(Fact::new(subj.clone(), rel.clone(), obj));
+                match it.peek() {
+                    Some((Tok::Comma, _)) => {
+                        it.next();
+                    }
+                    _ => break,
+                }
+            }
+            match it.next() {
+                None => return Ok(()),
+                Some((Tok::Semi, _)) => {
+                    // `;` multiples ou final autorisés
+                    while matches!(it.peek(), Some((Tok::Semi, _))) {
+                        it.next();
+                    }
+                    if it.peek().is_none() {
+                        return Ok(());
+                    }
+                }
+                Some((_, l)) => return syntax(*l, "',', ';' ou '.' attendu"),
+            }
+        }
+    }
+
+    fn node(&self, tok: &Tok, line: usize, role: &str) -> FormatResult<String> {
+        match tok {
+            Tok::Iri(iri) => Ok(self.ns.to_name(&self.resolve_relative(iri))),
+            Tok::PName(p, local) => match self.prefixes.get(p) {
+                Some(ns) => Ok(self.ns.to_name(&format!("{ns}{local}"))),
+                None => syntax(line, format!("préfixe inconnu '{p}:'")),
+            },
+            Tok::BNode(label) => Ok(format!("_:{label}")),
+            Tok::Literal(value) => Ok(value.clone()),
+            Tok::Unsupported(c) => {
+                syntax(line, format!("'{c}' (listes, nœuds anonymes) non supporté"))
+            }
+            _ => syntax(line, format!("{role} attendu")),
+        }
+    }
+
+    fn resolve_relative(&self, iri: &str) -> String {
+        match &self.base {
+            Some(base) if !is_absolute_iri(iri) => format!("{base}{iri}"),
+            _ => iri.to_string(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TurtleReader<R> {
+    type Item = FormatResult<Fact>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(f) = self.pending.pop_front() {
+                return Some(Ok(f));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                // une erreur arrête la lecture
+                self.done = true;
+                self.tokens.clear();
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+fn tokenize_line(line: &str, n: usize) -> FormatResult<Vec<Tok>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '#' => break,
+            '<' => {
+                let Some(end) = chars[i + 1..].iter().position(|&c| c == '>') else {
+                    return syntax(n, "IRI non terminé ('>' manquant)");
+                };
+                let raw: String = chars[i + 1..i + 1 + end].iter().collect();
+                out.push(Tok::Iri(unescape(&raw, n)?));
+                i += end + 2;
+            }
+            '"' | '\'' => {
+                if chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c) {
+                    return syntax(n, "chaînes longues (\"\"\") non supportées");
+                }
+                let mut j = i + 1;
+                let mut raw = String::new();
+                loop {
+                    match chars.get(j) {
+                        None => return syntax(n, "littéral non terminé"),
+                        Some(&ch) if ch == c => break,
+                        Some('\\') => {
+                            raw.push('\\');
+                            if let Some(&next) = chars.get(j + 1) {
+                                raw.push(next);
+                            }
+                            j += 2;
+                        }
+                        Some(&ch) => {
+                            raw.push(ch);
+                            j += 1;
+                        }
+                    }
+                }
+                i = j + 1;
+                // suffixe @lang ou ^^type, sans effet sur le nom
+                if chars.get(i) == Some(&'@') {
+                    while i < chars.len()
+                        && (chars[i] == '@' || chars[i].is_alphanumeric() || chars[i] == '-')
+                    {
+                        i += 1;
+                    }
+                } else if chars.get(i) == Some(&'^') && chars.get(i + 1) == Some(&'^') {
+                    i += 2;
+                    if chars.get(i) == Some(&'<') {
+                        match chars[i..].iter().position(|&c| c == '>') {
+                            Some(end) => i += end + 1,
+                            None => return syntax(n, "type de littéral non terminé"),
+                        }
+                    } else {
+                        while i < chars.len() && is_name_char(chars[i]) {
+                            i += 1;
+                        }
+                    }
+                }
+                out.push(Tok::Literal(unescape(&raw, n)?));
+            }
+            '.' => {
+                out.push(Tok::Dot);
+                i += 1;
+            }
+            ';' => {
+                out.push(Tok::Semi);
+                i += 1;
+            }
+            ',' => {
+                out.push(Tok::Comma);
+                i += 1;
+            }
+            '[' | ']' | '(' | ')' => {
+                out.push(Tok::Unsupported(c));
+                i += 1;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && (is_name_char(chars[i]) || chars[i] == '@') {
+                    i += 1;
+                }
+                if i == start {
+                    return syntax(n, format!("caractère inattendu '{c}'"));
+                }
+                let mut word: String = chars[start..i].iter().collect();
+                // un '.' final termine l'instruction, il ne fait pas partie du nom
+                let mut dots = 0;
+                while word.ends_with('.') {
+                    word.pop();
+                    dots += 1;
+                }
+                out.push(classify(&word, n)?);
+                out.extend(std::iter::repeat_n(Tok::Dot, dots));
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.' | '%' | '+')
+}
+
+fn classify(word: &str, n: usize) -> FormatResult<Tok> {
+    if word == "a" {
+        return Ok(Tok::A);
+    }
+    match word {
+        "@prefix" => return Ok(Tok::Prefix { sparql: false }),
+        "@base" => return Ok(Tok::Base { sparql: false }),
+        w if w.eq_ignore_ascii_case("prefix") => return Ok(Tok::Prefix { sparql: true }),
+        w if w.eq_ignore_ascii_case("base") => return Ok(Tok::Base { sparql: true }),
+        _ => {}
+    }
+    if let Some(label) = word.strip_prefix("_:") {
+        return Ok(Tok::BNode(label.to_string()));
+    }
+    if word == "true" || word == "false" || word.parse::<f64>().is_ok() {
+        return Ok(Tok::Literal(word.to_string()));
+    }
+    match word.split_once(':') {
+        Some((p, local)) => Ok(Tok::PName(p.to_string(), local.to_string())),
+        None => syntax(n, format!("terme inconnu '{word}'")),
+    }
+}
+
+/// Échappements `\t \n \r \" \' \\ \uXXXX \UXXXXXXXX`.
+fn unescape(raw: &str, n: usize) -> FormatResult<String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some(q @ ('"' | '\'' | '\\')) => out.push(q),
+            Some(u @ ('u' | 'U')) => {
+                let len = if u == 'u' { 4 } else { 8 };
+                let hex: String = chars.by_ref().take(len).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(ch) if hex.len() == len => out.push(ch),
+                    _ => return syntax(n, format!("échappement \\{u}{hex} invalide")),
+                }
+            }
+            other => {
+                return Err(FormatError::Syntax {
+                    line: n,
+                    message: format!("échappement invalide \\{}", other.unwrap_or(' ')),
+                })
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Nom préfixé `kg:local` si `local` est un PN_LOCAL simple, sinon `<iri>`.
+fn turtle_term(ns: &Namespace, name: &str) -> String {
+    let iri = ns.to_iri(name);
+    if let Some(local) = iri.strip_prefix(&ns.base) {
+        let simple = !local.is_empty()
+            && !local.starts_with(['-', '.'])
+            && !local.ends_with('.')
+            && local
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+        if simple {
+            return format!("kg:{local}");
+        }
+    }
+    format!("<{iri}>")
+}
+
+/// Écrit les faits en Turtle, regroupés par sujet consécutif.
+pub fn write_turtle<'a, W: Write>(
+    facts: impl IntoIterator<Item = &'a Fact>,
+    ns: &Namespace,
+    w: &mut W,
+) -> FormatResult<()> {
+    writeln!(w, "@prefix kg: <{}> .", ns.base)?;
+    let mut current: Option<&str> = None;
+    for f in facts {
+        let s = f.subj.as_str();
+        let po = format!("{} {}", turtle_term(ns, &f.rel), turtle_term(ns, &f.obj));
+        if current == Some(s) {
+            write!(w, " ;\n    {po}")?;
+        } else {
+            if current.is_some() {
+                writeln!(w, " .")?;
+            }
+            write!(w, "\n{} {po}", turtle_term(ns, s))?;
+            current = Some(s);
+        }
+    }
+    if current.is_some() {
+        writeln!(w, " .")?;
+    }
+    Ok(())
+}
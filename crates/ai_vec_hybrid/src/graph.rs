@@ -0,0 +1,658 @@
+use std::collections::HashMap;
+
+use crate::adjacency::Adjacency;
+use crate::ann::{Hnsw, HnswParams};
+use crate::edge::Edge;
+use crate::embedders::{Embedder, HashingEmbedder};
+use crate::embedding::{cosine, l2_normalize};
+use crate::fact::Fact;
+use crate::feedback::{EmbeddingUpdate, UpdateReason};
+use crate::graph_op::GraphOp;
+use crate::names::NameTrie;
+use crate::node::Node;
+use crate::resolve::MergeRecord;
+use crate::rules::RuleSet;
+use crate::schema::{GraphSchema, Violation};
+use crate::time::{now, Timestamp};
+
+// --------- Graphe + index ---------
+
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub name2id: HashMap<String, usize>,
+    // noms normalisés, pour la complétion et la recherche approchée
+    pub(crate) names: NameTrie,
+    /// Faits courants (de base et dérivés). Lecture seule : les mutations
+    /// passent par les méthodes du graphe, qui tiennent `index` à jour.
+    pub edges: Vec<Edge>,
+    /// Versions de faits closes ou retirées (jamais détruites).
+    pub history: Vec<Edge>,
+    // positions dans `edges` par source, cible et relation
+    index: Adjacency,
+    // mutations pas encore persistées (vidé par `take_journal`)
+    pub(crate) journal: Vec<GraphOp>,
+    // index ANN optionnel, tenu à jour par `add_node` / `set_node_embedding`
+    ann: Option<Hnsw>,
+    embedder: Box<dyn Embedder>,
+    // règles d'inférence (arêtes `derived` recalculées à chaque changement)
+    pub(crate) rules: RuleSet,
+    // journal des fusions de nœuds (voir `merge_nodes` / `revert_merge`)
+    pub(crate) merges: Vec<MergeRecord>,
+    // piste d'audit des embeddings (voir `embedding_log`)
+    pub(crate) embedding_log: Vec<EmbeddingUpdate>,
+    // schéma des relations, vérifié à chaque ajout de fait
+    pub(crate) schema: Option<GraphSchema>,
+    // violations relevées depuis le dernier `take_violations`
+    pub(crate) violations: Vec<Violation>,
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::with_embedder(Box::new(HashingEmbedder::default()))
+    }
+
+    pub fn with_embedder(embedder: Box<dyn Embedder>) -> Self {
+        Self {
+            nodes: vec![],
+            name2id: HashMap::new(),
+            names: NameTrie::default(),
+            edges: vec![],
+            history: vec![],
+            index: Adjacency::default(),
+            journal: vec![],
+            ann: None,
+            embedder,
+            rules: RuleSet::default(),
+            merges: vec![],
+            embedding_log: vec![],
+            schema: None,
+            violations: vec![],
+        }
+    }
+
+    pub fn embedder(&self) -> &dyn Embedder {
+        self.embedder.as_ref()
+    }
+
+    pub fn embed(&self, text: &str) -> Vec<f32> {
+        self.embedder.embed(text)
+    }
+
+    /// Change d'embedder : tous les nœuds sont ré-encodés depuis leur nom
+    /// (les ajustements EMA sont perdus) et l'index ANN est reconstruit.
+    pub fn set_embedder(&mut self, embedder: Box<dyn Embedder>) {
+        self.embedder = embedder;
+        let names: Vec<String> = self.nodes.iter().map(|n| n.name.clone()).collect();
+        let params = self.ann.take().map(|ann| ann.params().clone());
+        let reason = UpdateReason::Reencode {
+            embedder: self.embedder.name().to_string(),
+        };
+        let at = now();
+        for name in names {
+            let emb = self.embed(&name);
+            self.set_node_embedding_at(&name, &emb, at, reason.clone());
+        }
+        if let Some(params) = params {
+            self.enable_ann(params);
+        }
+    }
+
+    /// Active l'index HNSW (construit sur les nœuds existants) ; `k_nn`
+    /// l'utilise ensuite à la place du scan exhaustif.
+    pub fn enable_ann(&mut self, params: HnswParams) {
+        let mut ann = Hnsw::new(params);
+        for n in &self.nodes {
+            ann.insert(n.id, &n.emb);
+        }
+        self.ann = Some(ann);
+    }
+
+    pub fn disable_ann(&mut self) {
+        self.ann = None;
+    }
+
+    /// Accès à l'index (p. ex. pour régler `ef_search`).
+    pub fn ann_mut(&mut self) -> Option<&mut Hnsw> {
+        self.ann.as_mut()
+    }
+
+    /// Récupère (et vide) les mutations effectuées depuis le dernier appel.
+    pub fn take_journal(&mut self) -> Vec<GraphOp> {
+        std::mem::take(&mut self.journal)
+    }
+
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.name2id.get(name) {
+            return id;
+        }
+        let id = self.nodes.len();
+        let emb = self.embed(name);
+        if let Some(ann) = &mut self.ann {
+            ann.insert(id, &emb);
+        }
+        self.nodes.push(Node {
+            id,
+            name: name.to_string(),
+            emb,
+            emb_history: vec![],
+            merged_into: None,
+        });
+        self.name2id.insert(name.to_string(), id);
+        self.names.insert(name, id);
+        self.journal.push(GraphOp::AddNode {
+            name: name.to_string(),
+        });
+        id
+    }
+
+    pub fn set_node_embedding_ema(&mut self, name: &str, new_text: &str, alpha: f32) {
+        let Some(&id) = self.name2id.get(name) else {
+            return;
+        };
+        let new_emb = self.embed(new_text);
+        let mut emb = self.nodes[id].emb.clone();
+        for (x, n) in emb.iter_mut().zip(new_emb) {
+            *x = (1.0 - alpha) * *x + alpha * n;
+        }
+        l2_normalize(&mut emb);
+        let reason = UpdateReason::Ema {
+            text: new_text.to_string(),
+            alpha,
+        };
+        self.set_node_embedding_at(name, &emb, now(), reason);
+    }
+
+    /// Remplace l'embedding d'un nœud (ignoré si le nœud est inconnu ou si la
+    /// dimension ne correspond pas). L'ancien vecteur est gardé dans `emb_history`.
+    pub fn set_node_embedding(&mut self, name: &str, emb: &[f32]) {
+        self.set_node_embedding_at(name, emb, now(), UpdateReason::Manual);
+    }
+
+    /// Remplace l'embedding et consigne la mise à jour dans la piste d'audit
+    /// (`None` si elle a été ignorée).
+    pub(crate) fn set_node_embedding_at(
+        &mut self,
+        name: &str,
+        emb: &[f32],
+        at: Timestamp,
+        reason: UpdateReason,
+    ) -> Option<EmbeddingUpdate> {
+        let &id = self.name2id.get(name)?;
+        if emb.len() != self.embedder.dim() {
+            return None;
+        }
+        // un alias (nœud fusionné) est consigné sous le nom canonique
+        let name = self.nodes[id].name.clone();
+        let old = std::mem::replace(&mut self.nodes[id].emb, emb.to_vec());
+        let drift = 1.0 - cosine(&old, emb);
+        self.nodes[id].emb_history.push((at, old));
+        if let Some(ann) = &mut self.ann {
+            ann.update(id, emb);
+        }
+        self.journal.push(GraphOp::SetEmbedding {
+            name: name.clone(),
+            emb: emb.to_vec(),
+            at: Some(at),
+            reason: Some(reason.clone()),
+        });
+        let update = EmbeddingUpdate {
+            node: name,
+            at,
+            reason,
+            version: self.nodes[id].emb_history.len(),
+            drift,
+        };
+        self.embedding_log.push(update.clone());
+        Some(update)
+    }
+
+    pub fn add_edge(&mut self, from_name: &str, rel: &str, to_name: &str) {
+        self.add_edge_valid(from_name, rel, to_name, None, None);
+    }
+
+    /// Ajoute un fait valide sur `[valid_from, valid_to)` (`None` = non borné).
+    /// Un fait dont la validité est déjà terminée va directement dans l'historique.
+    pub fn add_edge_valid(
+        &mut self,
+        from_name: &str,
+        rel: &str,
+        to_name: &str,
+        valid_from: Option<Timestamp>,
+        valid_to: Option<Timestamp>,
+    ) {
+        self.add_fact_valid(&Fact::new(from_name, rel, to_name), valid_from, valid_to);
+    }
+
+    /// Ajoute un fait avec sa provenance (confiance, source).
+    pub fn add_fact(&mut self, fact: &Fact) {
+        self.add_fact_valid(fact, None, None);
+    }
+
+    pub fn add_fact_valid(
+        &mut self,
+        fact: &Fact,
+        valid_from: Option<Timestamp>,
+        valid_to: Option<Timestamp>,
+    ) {
+        self.add_fact_recorded(fact, valid_from, valid_to, now());
+    }
+
+    pub(crate) fn add_fact_recorded(
+        &mut self,
+        fact: &Fact,
+        valid_from: Option<Timestamp>,
+        valid_to: Option<Timestamp>,
+        recorded_at: Timestamp,
+    ) {
+        let from = self.add_node(&fact.subj);
+        let to = self.add_node(&fact.obj);
+        let rel = fact.rel.as_str();
+        let edge = Edge {
+            from,
+            rel: rel.to_string(),
+            to,
+            derived: false,
+            valid_from,
+            valid_to,
+            recorded_at,
+            retracted_at: None,
+            provenance: fact.provenance.clone(),
+        };
+        let same = |e: &Edge| e.from == from && e.to == to && e.rel == rel;
+
+        let mut added = false;
+        let current = if valid_to.is_some_and(|t| t <= recorded_at) {
+            // fait passé : directement dans l'historique
+            if self.history.iter().any(|e| {
+                same(e)
+                    && e.retracted_at.is_none()
+                    && e.valid_from == valid_from
+                    && e.valid_to == valid_to
+            }) {
+                return;
+            }
+            self.history.push(edge);
+            false
+        } else {
+            // un fait déjà présent n'est pas dupliqué (rejeu idempotent) mais
+            // garde la provenance la plus sûre ; un fait déjà dérivé devient
+            // un fait de base
+            let pos = self
+                .index
+                .outgoing(from)
+                .iter()
+                .copied()
+                .find(|&p| same(&self.edges[p]));
+            match pos.map(|p| &mut self.edges[p]) {
+                Some(e) if !e.derived && e.confidence() >= edge.confidence() => return,
+                Some(e) if !e.derived => e.provenance = edge.provenance,
+                Some(e) => *e = edge,
+                None => {
+                    self.push_edge(edge);
+                    added = true;
+                }
+            }
+            true
+        };
+        self.journal.push(GraphOp::AddEdge {
+            from: fact.subj.clone(),
+            rel: rel.to_string(),
+            to: fact.obj.clone(),
+            valid_from,
+            valid_to,
+            recorded_at: Some(recorded_at),
+            provenance: fact.provenance.clone(),
+        });
+        if current && self.rules.is_functional(rel) {
+            self.resolve_functional(from, rel, recorded_at);
+        }
+        // un fait écarté par une relation fonctionnelle n'est ni propagé ni vérifié
+        let Some(pos) = self.find_base_edge(&fact.subj, rel, &fact.obj) else {
+            return;
+        };
+        if added {
+            self.infer(vec![pos]);
+        }
+        if self.schema.is_some() {
+            self.record_violations(&fact.subj, rel, &fact.obj);
+        }
+    }
+
+    /// Ajoute une arête courante en tenant l'index à jour.
+    pub(crate) fn push_edge(&mut self, edge: Edge) {
+        self.index.insert(self.edges.len(), &edge);
+        self.edges.push(edge);
+    }
+
+    /// Retire l'arête courante `pos` : la dernière arête prend sa place et
+    /// l'index est mis à jour sur place, en O(degré) plutôt qu'en O(|E|).
+    pub(crate) fn swap_remove_edge(&mut self, pos: usize) -> Edge {
+        self.index.remove(pos, &self.edges);
+        let last = self.edges.len() - 1;
+        if pos != last {
+            self.index.relocate(last, pos, &self.edges[last]);
+        }
+        self.edges.swap_remove(pos)
+    }
+
+    /// Reconstruit l'index après une réécriture complète des arêtes.
+    pub(crate) fn reindex(&mut self) {
+        self.index = Adjacency::build(&self.edges);
+    }
+
+    /// Position de l'arête courante (de base ou dérivée) `from rel to`.
+    pub(crate) fn position_of(&self, from: usize, rel: &str, to: usize) -> Option<usize> {
+        self.index.position(from, rel, to)
+    }
+
+    /// Position d'un fait de base courant.
+    fn find_base_edge(&self, from_name: &str, rel: &str, to_name: &str) -> Option<usize> {
+        let from = *self.name2id.get(from_name)?;
+        let to = *self.name2id.get(to_name)?;
+        self.index.outgoing(from).iter().copied().find(|&p| {
+            let e = &self.edges[p];
+            !e.derived && e.to == to && e.rel == rel
+        })
+    }
+
+    /// Retire un fait erroné. Rien n'est détruit : la version part dans
+    /// l'historique avec `retracted_at`. Seuls les faits de base se retirent ;
+    /// une arête dérivée disparaît quand plus aucun fait ne la soutient.
+    pub fn remove_edge_exact(&mut self, from_name: &str, rel: &str, to_name: &str) {
+        self.retract_edge_at(from_name, rel, to_name, now());
+    }
+
+    pub(crate) fn retract_edge_at(
+        &mut self,
+        from_name: &str,
+        rel: &str,
+        to_name: &str,
+        at: Timestamp,
+    ) {
+        let Some(pos) = self.find_base_edge(from_name, rel, to_name) else {
+            return;
+        };
+        let (mut old, suspended) = self.take_with_derivations(pos);
+        old.retracted_at = Some(at);
+        self.history.push(old);
+        self.journal.push(GraphOp::RemoveEdge {
+            from: from_name.to_string(),
+            rel: rel.to_string(),
+            to: to_name.to_string(),
+            at: Some(at),
+        });
+        self.restore_supported(suspended);
+    }
+
+    /// Le fait cesse d'être valide à `valid_to` (il a été vrai jusque-là).
+    /// La version courante est remplacée par une version bornée ; les deux
+    /// restent consultables dans l'historique.
+    pub fn close_edge(&mut self, from_name: &str, rel: &str, to_name: &str, valid_to: Timestamp) {
+        self.close_edge_at(from_name, rel, to_name, valid_to, now());
+    }
+
+    pub(crate) fn close_edge_at(
+        &mut self,
+        from_name: &str,
+        rel: &str,
+        to_name: &str,
+        valid_to: Timestamp,
+        at: Timestamp,
+    ) {
+        let Some(pos) = self.find_base_edge(from_name, rel, to_name) else {
+            return;
+        };
+        let (mut old, suspended) = self.take_with_derivations(pos);
+        let mut closed = old.clone();
+        closed.valid_to = Some(valid_to);
+        closed.recorded_at = at;
+        old.retracted_at = Some(at);
+        self.history.push(old);
+        if valid_to > at {
+            self.push_edge(closed);
+        } else {
+            self.history.push(closed);
+        }
+        self.journal.push(GraphOp::CloseEdge {
+            from: from_name.to_string(),
+            rel: rel.to_string(),
+            to: to_name.to_string(),
+            valid_to,
+            at,
+        });
+        self.restore_supported(suspended);
+    }
+
+    /// Correction : l'ancien fait cesse d'être valide maintenant et le
+    /// nouveau prend le relais (voir `update_fact_at`).
+    pub fn update_fact(
+        &mut self,
+        subj: &str,
+        old_rel: &str,
+        old_obj: &str,
+        new_rel: &str,
+        new_obj: &str,
+    ) {
+        self.update_fact_at(subj, old_rel, old_obj, new_rel, new_obj, now());
+    }
+
+    /// Correction datée : l'ancien fait reste dans l'historique, valide
+    /// jusqu'à `valid_at`, et le nouveau est valide à partir de `valid_at`.
+    pub fn update_fact_at(
+        &mut self,
+        subj: &str,
+        old_rel: &str,
+        old_obj: &str,
+        new_rel: &str,
+        new_obj: &str,
+        valid_at: Timestamp,
+    ) {
+        self.close_edge(subj, old_rel, old_obj, valid_at);
+        self.add_edge_valid(subj, new_rel, new_obj, Some(valid_at), None);
+    }
+
+    /// Arêtes courantes aux positions `positions` (fournies par l'index).
+    fn at_positions<'a>(&'a self, positions: &'a [usize]) -> impl Iterator<Item = &'a Edge> {
+        positions.iter().map(|&p| &self.edges[p])
+    }
+
+    /// Faits sortants du nœud `id`, en O(degré).
+    pub fn out_edges(&self, id: usize) -> impl Iterator<Item = &Edge> {
+        self.at_positions(self.index.outgoing(id))
+    }
+
+    /// Faits entrants du nœud `id`, en O(degré).
+    pub fn in_edges(&self, id: usize) -> impl Iterator<Item = &Edge> {
+        self.at_positions(self.index.incoming(id))
+    }
+
+    /// Faits courants d'une relation donnée.
+    pub fn edges_with_rel(&self, rel: &str) -> Vec<&Edge> {
+        self.at_positions(self.index.with_rel(rel)).collect()
+    }
+
+    pub fn outgoing(&self, name: &str) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.out_edges(id).collect()
+    }
+
+    pub fn incoming(&self, name: &str) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.in_edges(id).collect()
+    }
+
+    /// Faits sortants d'une relation donnée (`Pluton orbite ?`).
+    pub fn outgoing_rel(&self, name: &str, rel: &str) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.out_edges(id).filter(|e| e.rel == rel).collect()
+    }
+
+    /// Sources distinctes des faits courants qui touchent le nœud.
+    pub fn sources_of(&self, name: &str) -> Vec<&str> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        let mut sources: Vec<&str> = self
+            .out_edges(id)
+            .chain(self.in_edges(id))
+            .filter_map(|e| e.provenance.source.as_deref())
+            .collect();
+        sources.sort_unstable();
+        sources.dedup();
+        sources
+    }
+
+    /// Les nœuds fusionnés dans un autre sont exclus des résultats.
+    pub fn k_nn(&self, q: &[f32], k: usize) -> Vec<(usize, f32)> {
+        match &self.ann {
+            Some(ann) => {
+                // l'index ne supprime rien : on demande de quoi compenser
+                let merged = self.merges.iter().filter(|m| m.is_active()).count();
+                let mut hits = ann.search(q, k + merged);
+                hits.retain(|&(id, _)| self.nodes[id].merged_into.is_none());
+                hits.truncate(k);
+                hits
+            }
+            None => self.k_nn_exact(q, k),
+        }
+    }
+
+    /// Scan exhaustif (référence pour l'index ANN).
+    pub fn k_nn_exact(&self, q: &[f32], k: usize) -> Vec<(usize, f32)> {
+        let mut sims: Vec<(usize, f32)> = self
+            .nodes
+            .iter()
+            .filter(|n| n.merged_into.is_none())
+            .map(|n| (n.id, cosine(&n.emb, q)))
+            .collect();
+        sims.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        sims.truncate(k);
+        sims
+    }
+
+    /// k plus proches nœuds du texte : k-NN vectoriel complété par les noms
+    /// proches du texte saisi (voir `candidates`).
+    pub fn search_text(&self, query: &str, k: usize) -> Vec<(String, f32)> {
+        let q = self.embed(query);
+        self.candidates(query, &q, k)
+            .into_iter()
+            .map(|(id, s)| (self.nodes[id].name.clone(), s))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+
+    /// L'index décrit exactement `edges` : mêmes positions par source, cible
+    /// et relation, et un triplet retrouvé pour chaque arête.
+    fn assert_index_matches(g: &Graph) {
+        let sorted = |list: &[usize]| {
+            let mut list = list.to_vec();
+            list.sort_unstable();
+            list
+        };
+        let positions = |keep: &dyn Fn(&Edge) -> bool| -> Vec<usize> {
+            (0..g.edges.len()).filter(|&p| keep(&g.edges[p])).collect()
+        };
+        for id in 0..g.nodes.len() {
+            assert_eq!(sorted(g.index.outgoing(id)), positions(&|e| e.from == id));
+            assert_eq!(sorted(g.index.incoming(id)), positions(&|e| e.to == id));
+        }
+        for e in &g.edges {
+            assert_eq!(
+                sorted(g.index.with_rel(&e.rel)),
+                positions(&|x| x.rel == e.rel)
+            );
+            let q = g.position_of(e.from, &e.rel, e.to).expect("triplet indexé");
+            let found = &g.edges[q];
+            assert_eq!((found.from, &found.rel, found.to), (e.from, &e.rel, e.to));
+        }
+    }
+
+    fn objects(g: &Graph, subj: &str) -> Vec<String> {
+        g.outgoing(subj)
+            .into_iter()
+            .map(|e| format!("{} {}", e.rel, g.nodes[e.to].name))
+            .collect()
+    }
+
+    #[test]
+    fn index_stays_in_sync_after_add_remove_and_update() {
+        let mut g = Graph::new();
+        g.set_rules(RuleSet::new(vec![
+            Rule::Transitive {
+                rel: "partie_de".into(),
+            },
+            Rule::Inverse {
+                rel: "orbite".into(),
+                inverse: "orbité_par".into(),
+            },
+        ]));
+        g.add_edge("Pluton", "est_une", "planète");
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Pluton", "partie_de", "Kuiper");
+        g.add_edge("Kuiper", "partie_de", "système_solaire");
+        g.add_edge("Charon", "orbite", "Pluton");
+        g.add_edge("Pluton", "découvert_par", "Tombaugh");
+        assert_index_matches(&g);
+
+        // retrait au milieu, avec ses dérivations
+        g.remove_edge_exact("Pluton", "orbite", "Soleil");
+        assert_index_matches(&g);
+        assert!(g
+            .incoming("Pluton")
+            .iter()
+            .all(|e| e.rel != "orbité_par" || g.nodes[e.from].name != "Soleil"));
+
+        // correction d'un fait source d'une dérivation transitive
+        g.update_fact(
+            "Kuiper",
+            "partie_de",
+            "système_solaire",
+            "partie_de",
+            "héliosphère",
+        );
+        assert_index_matches(&g);
+        g.update_fact("Pluton", "est_une", "planète", "est_une", "planète_naine");
+        assert_index_matches(&g);
+
+        // les listes d'un sujet gardent l'ordre d'insertion
+        assert_eq!(
+            objects(&g, "Pluton"),
+            [
+                "partie_de Kuiper",
+                "orbité_par Charon",
+                "découvert_par Tombaugh",
+                "partie_de héliosphère",
+                "est_une planète_naine",
+            ]
+        );
+
+        // retrait de tous les faits, un à un
+        while let Some(e) = g.edges.iter().find(|e| !e.derived) {
+            let (from, rel, to) = (
+                g.nodes[e.from].name.clone(),
+                e.rel.clone(),
+                g.nodes[e.to].name.clone(),
+            );
+            g.remove_edge_exact(&from, &rel, &to);
+            assert_index_matches(&g);
+        }
+        assert!(g.edges.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use crate::fact::Fact;
+use crate::feedback::UpdateReason;
+use crate::graph::Graph;
+use crate::provenance::Provenance;
+use crate::time::Timestamp;
+
+/// Mutation élémentaire du graphe, telle qu'elle est journalisée dans le WAL.
+///
+/// Les embeddings sont enregistrés par valeur (et non par texte + alpha) pour
+/// que le rejeu redonne exactement le même vecteur ; de même les dates sont
+/// celles de l'opération d'origine. Une date absente (journal antérieur aux
+/// faits datés) est lue comme l'epoch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphOp {
+    AddNode {
+        name: String,
+    },
+    AddEdge {
+        from: String,
+        rel: String,
+        to: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        valid_from: Option<Timestamp>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        valid_to: Option<Timestamp>,
+        #[serde(default)]
+        recorded_at: Option<Timestamp>,
+        #[serde(flatten)]
+        provenance: Provenance,
+    },
+    RemoveEdge {
+        from: String,
+        rel: String,
+        to: String,
+        #[serde(default)]
+        at: Option<Timestamp>,
+    },
+    CloseEdge {
+        from: String,
+        rel: String,
+        to: String,
+        valid_to: Timestamp,
+        at: Timestamp,
+    },
+    SetEmbedding {
+        name: String,
+        emb: Vec<f32>,
+        #[serde(default)]
+        at: Option<Timestamp>,
+        /// Absent des journaux antérieurs à la piste d'audit (lu comme `Manual`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<UpdateReason>,
+    },
+    MergeNodes {
+        keep: String,
+        merged: String,
+        at: Timestamp,
+    },
+    /// `merge` : position dans le journal des fusions.
+    RevertMerge {
+        merge: usize,
+        at: Timestamp,
+    },
+}
+
+impl GraphOp {
+    /// Rejoue l'opération sur `g` (l'opération est de nouveau journalisée).
+    pub fn apply(&self, g: &mut Graph) {
+        match self {
+            GraphOp::AddNode { name } => {
+                g.add_node(name);
+            }
+            GraphOp::AddEdge {
+                from,
+                rel,
+                to,
+                valid_from,
+                valid_to,
+                recorded_at,
+                provenance,
+            } => g.add_fact_recorded(
+                &Fact::new(from, rel, to).with_provenance(provenance.clone()),
+                *valid_from,
+                *valid_to,
+                recorded_at.unwrap_or(0),
+            ),
+            GraphOp::RemoveEdge { from, rel, to, at } => {
+                g.retract_edge_at(from, rel, to, at.unwrap_or(0))
+            }
+            GraphOp::CloseEdge {
+                from,
+                rel,
+                to,
+                valid_to,
+                at,
+            } => g.close_edge_at(from, rel, to, *valid_to, *at),
+            GraphOp::SetEmbedding {
+                name,
+                emb,
+                at,
+                reason,
+            } => {
+                let reason = reason.clone().unwrap_or(UpdateReason::Manual);
+                g.set_node_embedding_at(name, emb, at.unwrap_or(0), reason);
+            }
+            // une fusion rejouée sur le même état réussit toujours
+            GraphOp::MergeNodes { keep, merged, at } => {
+                let _ = g.merge_nodes_at(keep, merged, *at);
+            }
+            GraphOp::RevertMerge { merge, at } => {
+                let _ = g.revert_merge_at(*merge, *at);
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Un saut du chemin d'explication.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PathStep {
+    pub rel: String,
+    pub to: String,
+    /// Provenance du fait emprunté.
+    pub confidence: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Résultat de recherche hybride, avec le chemin qui l'explique.
+#[derive(Clone, Debug, Serialize)]
+pub struct HybridHit {
+    pub node: String,
+    /// Score combiné utilisé pour le classement.
+    pub score: f32,
+    /// Similarité directe entre la requête et le nœud.
+    pub vector_score: f32,
+    /// Graine k-NN d'où part le chemin.
+    pub seed: String,
+    pub seed_score: f32,
+    pub path: Vec<PathStep>,
+}
+
+impl HybridHit {
+    pub fn hops(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Confiance du chemin : produit des confiances de ses faits.
+    pub fn confidence(&self) -> f32 {
+        self.path.iter().map(|s| s.confidence).product()
+    }
+
+    /// Ex. `Pluton --est_une--> planète --super-classe--> corps_céleste`.
+    pub fn explanation(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for HybridHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.seed)?;
+        for step in &self.path {
+            write!(f, " --{}--> {}", step.rel, step.to)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+/// Paramètres d'une recherche hybride (vecteurs + parcours du graphe).
+#[derive(Clone, Debug)]
+pub struct HybridQuery {
+    /// Nombre de graines issues du k-NN.
+    pub seeds: usize,
+    /// Profondeur maximale d'expansion le long des arêtes sortantes.
+    pub max_hops: usize,
+    /// Relations suivies pendant l'expansion (vide = toutes).
+    pub relations: Vec<String>,
+    /// Poids du score propagé depuis la graine ; `1 - alpha` pour la similarité directe.
+    pub alpha: f32,
+    /// Atténuation du score de la graine à chaque saut.
+    pub hop_decay: f32,
+    /// Nombre maximal de résultats.
+    pub limit: usize,
+}
+
+impl Default for HybridQuery {
+    fn default() -> Self {
+        Self {
+            seeds: 3,
+            max_hops: 2,
+            relations: vec![],
+            alpha: 0.7,
+            hop_decay: 0.8,
+            limit: 10,
+        }
+    }
+}
+
+impl HybridQuery {
+    pub fn with_relations(mut self, rels: &[&str]) -> Self {
+        self.relations = rels.iter().map(|r| r.to_string()).collect();
+        self
+    }
+
+    pub fn follows(&self, rel: &str) -> bool {
+        self.relations.is_empty() || self.relations.iter().any(|r| r == rel)
+    }
+}
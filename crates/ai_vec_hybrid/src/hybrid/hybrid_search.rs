@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::embedding::cosine;
+use crate::graph::Graph;
+use crate::hybrid::hybrid_hit::{HybridHit, PathStep};
+use crate::hybrid::hybrid_query::HybridQuery;
+
+impl Graph {
+    /// Recherche hybride : graines par k-NN (et par nom, voir `search_text`), expansion de `max_hops` sauts
+    /// le long des relations choisies, puis re-classement par score combiné
+    /// `alpha * sim(graine) * decay^sauts * confiance + (1 - alpha) * sim(nœud)`,
+    /// où `confiance` est le produit des confiances des faits du chemin.
+    ///
+    /// Chaque nœud n'apparaît qu'une fois, avec son meilleur chemin.
+    pub fn hybrid_search(&self, query: &str, params: &HybridQuery) -> Vec<HybridHit> {
+        let q = self.embed(query);
+        let mut best: HashMap<usize, HybridHit> = HashMap::new();
+
+        for (seed, seed_score) in self.candidates(query, &q, params.seeds) {
+            let seed_name = &self.nodes[seed].name;
+            let mut queue: VecDeque<(usize, Vec<PathStep>)> = VecDeque::new();
+            let mut seen = HashSet::from([seed]);
+            queue.push_back((seed, vec![]));
+
+            while let Some((id, path)) = queue.pop_front() {
+                let vector_score = cosine(&self.nodes[id].emb, &q);
+                let confidence: f32 = path.iter().map(|s: &PathStep| s.confidence).product();
+                let propagated = seed_score * params.hop_decay.powi(path.len() as i32) * confidence;
+                let score = params.alpha * propagated + (1.0 - params.alpha) * vector_score;
+                if best.get(&id).is_none_or(|h| score > h.score) {
+                    best.insert(
+                        id,
+                        HybridHit {
+                            node: self.nodes[id].name.clone(),
+                            score,
+                            vector_score,
+                            seed: seed_name.clone(),
+                            seed_score,
+                            path: path.clone(),
+                        },
+                    );
+                }
+                if path.len() >= params.max_hops {
+                    continue;
+                }
+                for e in self.out_edges(id) {
+                    if !params.follows(&e.rel) || !seen.insert(e.to) {
+                        continue;
+                    }
+                    let mut next = path.clone();
+                    next.push(PathStep {
+                        rel: e.rel.clone(),
+                        to: self.nodes[e.to].name.clone(),
+                        confidence: e.confidence(),
+                        source: e.provenance.source.clone(),
+                    });
+                    queue.push_back((e.to, next));
+                }
+            }
+        }
+
+        let mut hits: Vec<HybridHit> = best.into_values().collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.node.cmp(&b.node)));
+        hits.truncate(params.limit);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solar_system() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "est_une", "planète");
+        g.add_edge("planète", "super-classe", "corps_céleste");
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Mars", "est_une", "planète");
+        g
+    }
+
+    #[test]
+    fn ranks_by_hops_and_explains_the_path() {
+        let g = solar_system();
+        let params = HybridQuery {
+            seeds: 1,
+            ..HybridQuery::default()
+        }
+        .with_relations(&["est_une", "super-classe"]);
+        let hits = g.hybrid_search("Pluton", &params);
+
+        let nodes: Vec<&str> = hits.iter().map(|h| h.node.as_str()).collect();
+        // seule la graine est proche de la requête : le classement suit les sauts,
+        // « orbite » n'est pas suivie et Mars n'est pas atteignable
+        assert_eq!(nodes, ["Pluton", "planète", "corps_céleste"]);
+        assert!(hits.windows(2).all(|w| w[0].score > w[1].score));
+        assert!(hits.iter().all(|h| h.seed == "Pluton"));
+        // score combiné : graine atténuée par saut + similarité directe
+        for h in &hits {
+            let propagated = h.seed_score * params.hop_decay.powi(h.hops() as i32) * h.confidence();
+            let expected = params.alpha * propagated + (1.0 - params.alpha) * h.vector_score;
+            assert!((h.score - expected).abs() < 1e-6, "{}", h.node);
+        }
+
+        let last = &hits[2];
+        assert_eq!(last.hops(), 2);
+        assert_eq!(
+            last.explanation(),
+            "Pluton --est_une--> planète --super-classe--> corps_céleste"
+        );
+        assert_eq!(hits[0].explanation(), "Pluton");
+    }
+}
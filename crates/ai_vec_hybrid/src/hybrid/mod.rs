@@ -0,0 +1,6 @@
+pub mod hybrid_hit;
+pub mod hybrid_query;
+pub mod hybrid_search;
+
+pub use hybrid_hit::{HybridHit, PathStep};
+pub use hybrid_query::HybridQuery;
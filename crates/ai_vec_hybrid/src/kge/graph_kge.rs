@@ -0,0 +1,146 @@
+use crate::embedding::l2_normalize;
+use crate::feedback::UpdateReason;
+use crate::graph::Graph;
+use crate::kge::kge_embeddings::KgeEmbeddings;
+use crate::kge::kge_error::{KgeError, KgeResult};
+use crate::kge::link_prediction::LinkPrediction;
+use crate::time::now;
+
+impl Graph {
+    fn canonical(&self, name: &str) -> KgeResult<&str> {
+        self.name2id
+            .get(name)
+            .map(|&id| self.nodes[id].name.as_str())
+            .ok_or_else(|| KgeError::UnknownNode(name.to_string()))
+    }
+
+    /// `Pluton orbite ?` : objets les plus plausibles selon `kge`, en
+    /// signalant ceux qui forment déjà un fait du graphe.
+    pub fn predict_objects(
+        &self,
+        kge: &KgeEmbeddings,
+        subj: &str,
+        rel: &str,
+        k: usize,
+    ) -> KgeResult<Vec<LinkPrediction>> {
+        let subj = self.canonical(subj)?;
+        let known: Vec<&str> = self
+            .outgoing_rel(subj, rel)
+            .into_iter()
+            .map(|e| self.nodes[e.to].name.as_str())
+            .collect();
+        Ok(kge
+            .rank_objects(subj, rel, k)?
+            .into_iter()
+            .map(|(node, score)| LinkPrediction {
+                known: known.contains(&node.as_str()),
+                node,
+                score,
+            })
+            .collect())
+    }
+
+    /// `? orbite Soleil` : sujets les plus plausibles.
+    pub fn predict_subjects(
+        &self,
+        kge: &KgeEmbeddings,
+        rel: &str,
+        obj: &str,
+        k: usize,
+    ) -> KgeResult<Vec<LinkPrediction>> {
+        let obj = self.canonical(obj)?;
+        let known: Vec<&str> = self
+            .incoming(obj)
+            .into_iter()
+            .filter(|e| e.rel == rel)
+            .map(|e| self.nodes[e.from].name.as_str())
+            .collect();
+        Ok(kge
+            .rank_subjects(rel, obj, k)?
+            .into_iter()
+            .map(|(node, score)| LinkPrediction {
+                known: known.contains(&node.as_str()),
+                node,
+                score,
+            })
+            .collect())
+    }
+
+    /// Mélange les vecteurs appris avec l'encodage du nom :
+    /// `normalise((1 − weight) · embed(nom) + weight · kge)`. Les nœuds
+    /// absents du modèle ne changent pas ; renvoie le nombre de nœuds mis à
+    /// jour (via `set_node_embedding`, donc journalisés).
+    pub fn blend_embeddings(&mut self, kge: &KgeEmbeddings, weight: f32) -> KgeResult<usize> {
+        if kge.dim != self.embedder().dim() {
+            return Err(KgeError::DimensionMismatch {
+                model: kge.dim,
+                embedder: self.embedder().dim(),
+            });
+        }
+        let weight = weight.clamp(0.0, 1.0);
+        let at = now();
+        let mut n = 0;
+        for (name, v) in kge.names.iter().zip(&kge.entities) {
+            if !self.name2id.contains_key(name) {
+                continue;
+            }
+            let mut emb = self.embed(name);
+            for (x, k) in emb.iter_mut().zip(v) {
+                *x = (1.0 - weight) * *x + weight * k;
+            }
+            l2_normalize(&mut emb);
+            self.set_node_embedding_at(name, &emb, at, UpdateReason::Blend { weight });
+            n += 1;
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::cosine;
+    use crate::kge::KgeParams;
+
+    fn solar_system() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Charon", "orbite", "Pluton");
+        g
+    }
+
+    #[test]
+    fn blending_requires_the_embedder_dimension() {
+        let mut g = solar_system();
+        let dim = g.embedder().dim();
+        let params = KgeParams {
+            dim: Some(dim * 2),
+            epochs: 5,
+            ..KgeParams::default()
+        };
+        let kge = KgeEmbeddings::train(&g, &params);
+        match g.blend_embeddings(&kge, 0.5) {
+            Err(KgeError::DimensionMismatch { model, embedder }) => {
+                assert_eq!((model, embedder), (dim * 2, dim))
+            }
+            other => panic!("DimensionMismatch attendu : {other:?}"),
+        }
+        // rien n'a changé
+        assert_eq!(g.nodes[g.name2id["Pluton"]].emb, g.embed("Pluton"));
+        assert!(g.embedding_log().is_empty());
+    }
+
+    #[test]
+    fn blending_moves_nodes_towards_the_model() {
+        let mut g = solar_system();
+        let params = KgeParams {
+            epochs: 5,
+            ..KgeParams::default()
+        };
+        let kge = KgeEmbeddings::train(&g, &params);
+        assert_eq!(g.blend_embeddings(&kge, 1.0).unwrap(), 3);
+        let pluton = &g.nodes[g.name2id["Pluton"]].emb;
+        assert!(cosine(pluton, kge.entity("Pluton").unwrap()) > 0.9999);
+        assert_eq!(g.embedding_log().len(), 3);
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::kge::kge_error::{KgeError, KgeResult};
+use crate::kge::kge_model::KgeModel;
+
+/// Vecteurs de nœuds et de relations appris sur les faits du graphe
+/// (voir `KgeEmbeddings::train`). Les nœuds sont désignés par leur nom
+/// canonique au moment de l'entraînement.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KgeEmbeddings {
+    pub model: KgeModel,
+    pub dim: usize,
+    /// Noms des nœuds, parallèle à `entities`.
+    pub names: Vec<String>,
+    pub entities: Vec<Vec<f32>>,
+    pub relations: BTreeMap<String, Vec<f32>>,
+    /// Perte moyenne de la dernière époque.
+    pub loss: f32,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl KgeEmbeddings {
+    pub(crate) fn new(
+        model: KgeModel,
+        dim: usize,
+        names: Vec<String>,
+        entities: Vec<Vec<f32>>,
+        relations: BTreeMap<String, Vec<f32>>,
+        loss: f32,
+    ) -> Self {
+        let mut kge = Self {
+            model,
+            dim,
+            names,
+            entities,
+            relations,
+            loss,
+            index: HashMap::new(),
+        };
+        kge.reindex();
+        kge
+    }
+
+    fn reindex(&mut self) {
+        self.index = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), i))
+            .collect();
+    }
+
+    pub fn entity(&self, name: &str) -> Option<&[f32]> {
+        self.index.get(name).map(|&i| self.entities[i].as_slice())
+    }
+
+    pub fn relation(&self, rel: &str) -> Option<&[f32]> {
+        self.relations.get(rel).map(Vec::as_slice)
+    }
+
+    fn lookup(&self, name: &str) -> KgeResult<&[f32]> {
+        self.entity(name)
+            .ok_or_else(|| KgeError::UnknownNode(name.to_string()))
+    }
+
+    fn lookup_rel(&self, rel: &str) -> KgeResult<&[f32]> {
+        self.relation(rel)
+            .ok_or_else(|| KgeError::UnknownRelation(rel.to_string()))
+    }
+
+    /// Plausibilité de `subj rel obj` (opposé de l'énergie).
+    pub fn score(&self, subj: &str, rel: &str, obj: &str) -> KgeResult<f32> {
+        let (h, r, t) = (self.lookup(subj)?, self.lookup_rel(rel)?, self.lookup(obj)?);
+        Ok(-self.model.energy(h, r, t))
+    }
+
+    /// `subj rel ?` : les `k` objets les plus plausibles (hors `subj`).
+    pub fn rank_objects(&self, subj: &str, rel: &str, k: usize) -> KgeResult<Vec<(String, f32)>> {
+        let (h, r) = (self.lookup(subj)?, self.lookup_rel(rel)?);
+        Ok(self.rank(subj, k, |t| self.model.energy(h, r, t)))
+    }
+
+    /// `? rel obj` : les `k` sujets les plus plausibles (hors `obj`).
+    pub fn rank_subjects(&self, rel: &str, obj: &str, k: usize) -> KgeResult<Vec<(String, f32)>> {
+        let (r, t) = (self.lookup_rel(rel)?, self.lookup(obj)?);
+        Ok(self.rank(obj, k, |h| self.model.energy(h, r, t)))
+    }
+
+    fn rank(&self, skip: &str, k: usize, energy: impl Fn(&[f32]) -> f32) -> Vec<(String, f32)> {
+        let mut scored: Vec<(usize, f32)> = self
+            .entities
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.names[*i] != skip)
+            .map(|(i, v)| (i, -energy(v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.truncate(k);
+        scored
+            .into_iter()
+            .map(|(i, s)| (self.names[i].clone(), s))
+            .collect()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> KgeResult<()> {
+        fs::write(path, serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> KgeResult<Self> {
+        let mut kge: Self = serde_json::from_slice(&fs::read(path)?)?;
+        kge.reindex();
+        Ok(kge)
+    }
+}
@@ -0,0 +1,16 @@
+#[derive(thiserror::Error, Debug)]
+pub enum KgeError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("modèle invalide : {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("nœud inconnu du modèle '{0}'")]
+    UnknownNode(String),
+    #[error("relation inconnue du modèle '{0}'")]
+    UnknownRelation(String),
+    /// Le mélange exige la dimension de l'embedder du graphe.
+    #[error("dimension du modèle ({model}) différente de celle de l'embedder ({embedder})")]
+    DimensionMismatch { model: usize, embedder: usize },
+}
+
+pub type KgeResult<T> = Result<T, KgeError>;
@@ -0,0 +1,75 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Modèle d'embedding de faits ; l'énergie est basse pour un fait plausible.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KgeModel {
+    /// `h + r ≈ t` : énergie `‖h + r − t‖`.
+    #[default]
+    TransE,
+    /// Produit trilinéaire : énergie `−Σ hᵢ·rᵢ·tᵢ` (relations symétriques).
+    DistMult,
+}
+
+impl KgeModel {
+    pub fn energy(self, h: &[f32], r: &[f32], t: &[f32]) -> f32 {
+        let terms = h.iter().zip(r).zip(t);
+        match self {
+            KgeModel::TransE => terms
+                .map(|((h, r), t)| (h + r - t).powi(2))
+                .sum::<f32>()
+                .sqrt(),
+            KgeModel::DistMult => -terms.map(|((h, r), t)| h * r * t).sum::<f32>(),
+        }
+    }
+
+    /// Gradients de l'énergie par rapport à `h`, `r` et `t`.
+    pub(crate) fn gradients(self, h: &[f32], r: &[f32], t: &[f32]) -> [Vec<f32>; 3] {
+        let dim = h.len();
+        let (mut gh, mut gr, mut gt) = (vec![0.0; dim], vec![0.0; dim], vec![0.0; dim]);
+        let norm = match self {
+            KgeModel::TransE => self.energy(h, r, t).max(1e-9),
+            KgeModel::DistMult => 1.0,
+        };
+        for i in 0..dim {
+            match self {
+                KgeModel::TransE => {
+                    let d = (h[i] + r[i] - t[i]) / norm;
+                    gh[i] = d;
+                    gr[i] = d;
+                    gt[i] = -d;
+                }
+                KgeModel::DistMult => {
+                    gh[i] = -r[i] * t[i];
+                    gr[i] = -h[i] * t[i];
+                    gt[i] = -h[i] * r[i];
+                }
+            }
+        }
+        [gh, gr, gt]
+    }
+}
+
+impl FromStr for KgeModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "transe" => Ok(KgeModel::TransE),
+            "distmult" => Ok(KgeModel::DistMult),
+            _ => Err(format!("modèle inconnu '{s}' (transe, distmult)")),
+        }
+    }
+}
+
+impl fmt::Display for KgeModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KgeModel::TransE => "transe",
+            KgeModel::DistMult => "distmult",
+        })
+    }
+}
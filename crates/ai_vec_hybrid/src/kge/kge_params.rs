@@ -0,0 +1,33 @@
+use crate::kge::kge_model::KgeModel;
+
+/// Paramètres d'entraînement (SGD, perte de classement à marge).
+#[derive(Clone, Debug)]
+pub struct KgeParams {
+    pub model: KgeModel,
+    /// `None` : dimension de l'embedder du graphe (requise pour le mélange).
+    pub dim: Option<usize>,
+    pub epochs: usize,
+    pub learning_rate: f32,
+    /// Écart d'énergie visé entre un fait et un fait corrompu.
+    pub margin: f32,
+    /// Faits corrompus (sujet ou objet tiré au hasard) par fait.
+    pub negatives: usize,
+    /// Apprend aussi sur les arêtes dérivées par les règles.
+    pub include_derived: bool,
+    pub seed: u64,
+}
+
+impl Default for KgeParams {
+    fn default() -> Self {
+        Self {
+            model: KgeModel::TransE,
+            dim: None,
+            epochs: 200,
+            learning_rate: 0.01,
+            margin: 1.0,
+            negatives: 4,
+            include_derived: false,
+            seed: 42,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Candidat pour le trou d'une requête `Pluton orbite ?` ou `? orbite Soleil`.
+#[derive(Clone, Debug, Serialize)]
+pub struct LinkPrediction {
+    pub node: String,
+    /// Opposé de l'énergie : plus haut = plus plausible.
+    pub score: f32,
+    /// Le fait est déjà dans le graphe.
+    pub known: bool,
+}
+
+impl fmt::Display for LinkPrediction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>9.3}  {}", self.score, self.node)?;
+        if self.known {
+            write!(f, "  (connu)")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+pub mod graph_kge;
+pub mod kge_embeddings;
+pub mod kge_error;
+pub mod kge_model;
+pub mod kge_params;
+pub mod link_prediction;
+pub mod trainer;
+
+pub use kge_embeddings::KgeEmbeddings;
+pub use kge_error::{KgeError, KgeResult};
+pub use kge_model::KgeModel;
+pub use kge_params::KgeParams;
+pub use link_prediction::LinkPrediction;
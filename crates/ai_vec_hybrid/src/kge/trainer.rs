@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::embedding::l2_normalize;
+use crate::graph::Graph;
+use crate::kge::kge_embeddings::KgeEmbeddings;
+use crate::kge::kge_params::KgeParams;
+
+/// Générateur xorshift64* (entraînement reproductible à graine égale).
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Uniforme dans `[0, 1)`.
+    fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniforme dans `[-a, a)`.
+    fn uniform(&mut self, a: f32) -> f32 {
+        (self.unit() * 2.0 - 1.0) * a
+    }
+}
+
+fn random_vector(rng: &mut Rng, dim: usize) -> Vec<f32> {
+    let bound = 6.0 / (dim as f32).sqrt();
+    let mut v: Vec<f32> = (0..dim).map(|_| rng.uniform(bound)).collect();
+    l2_normalize(&mut v);
+    v
+}
+
+fn apply(v: &mut [f32], grad: &[f32], step: f32) {
+    for (x, g) in v.iter_mut().zip(grad) {
+        *x -= step * g;
+    }
+}
+
+/// Probabilité de corrompre le sujet plutôt que l'objet, par relation
+/// (échantillonnage « bern ») : `objets par sujet / (objets par sujet +
+/// sujets par objet)`. Pour `est_une`, où beaucoup de sujets partagent un
+/// objet, on corrompt surtout l'objet, ce qui évite d'apprendre comme faux
+/// des faits simplement absents (`Pluton est_une planète`).
+fn head_bias(triples: &[(usize, usize, usize)], relations: usize) -> Vec<f32> {
+    let mut heads: Vec<HashMap<usize, usize>> = vec![HashMap::new(); relations];
+    let mut tails: Vec<HashMap<usize, usize>> = vec![HashMap::new(); relations];
+    for &(h, r, t) in triples {
+        *heads[r].entry(h).or_default() += 1;
+        *tails[r].entry(t).or_default() += 1;
+    }
+    (0..relations)
+        .map(|r| {
+            let n = heads[r].values().sum::<usize>() as f32;
+            let tph = n / heads[r].len().max(1) as f32;
+            let hpt = n / tails[r].len().max(1) as f32;
+            tph / (tph + hpt)
+        })
+        .collect()
+}
+
+impl KgeEmbeddings {
+    /// Apprend les vecteurs sur les faits courants de `g` par descente de
+    /// gradient stochastique : pour chaque fait, `negatives` faits corrompus
+    /// (sujet ou objet remplacé selon `head_bias`, jamais par un fait connu)
+    /// et la perte
+    /// `max(0, marge + E(fait) − E(corrompu))`. Les vecteurs de nœuds sont
+    /// renormalisés à chaque époque.
+    pub fn train(g: &Graph, params: &KgeParams) -> KgeEmbeddings {
+        let dim = params.dim.unwrap_or_else(|| g.embedder().dim()).max(1);
+        let mut rng = Rng(params.seed | 1);
+
+        let ids: Vec<usize> = g
+            .nodes
+            .iter()
+            .filter(|n| n.merged_into.is_none())
+            .map(|n| n.id)
+            .collect();
+        let ent: HashMap<usize, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let mut rel_names: Vec<String> = vec![];
+        let mut rel_idx: HashMap<&str, usize> = HashMap::new();
+        let mut triples: Vec<(usize, usize, usize)> = vec![];
+        for e in g
+            .edges
+            .iter()
+            .filter(|e| params.include_derived || !e.derived)
+        {
+            let r = *rel_idx.entry(&e.rel).or_insert_with(|| {
+                rel_names.push(e.rel.clone());
+                rel_names.len() - 1
+            });
+            triples.push((ent[&e.from], r, ent[&e.to]));
+        }
+        let known: HashSet<(usize, usize, usize)> = triples.iter().copied().collect();
+        let head_bias = head_bias(&triples, rel_names.len());
+
+        let mut entities: Vec<Vec<f32>> =
+            ids.iter().map(|_| random_vector(&mut rng, dim)).collect();
+        let mut relations: Vec<Vec<f32>> = rel_names
+            .iter()
+            .map(|_| random_vector(&mut rng, dim))
+            .collect();
+
+        let model = params.model;
+        let lr = params.learning_rate;
+        let mut loss = 0.0;
+        for _ in 0..params.epochs {
+            // ordre de passage mélangé à chaque époque (Fisher-Yates)
+            for i in (1..triples.len()).rev() {
+                triples.swap(i, rng.below(i + 1));
+            }
+            let (mut total, mut count) = (0.0, 0usize);
+            for &(h, r, t) in &triples {
+                for _ in 0..params.negatives {
+                    let corrupt = rng.below(entities.len());
+                    let (nh, nt) = if rng.unit() < head_bias[r] {
+                        (corrupt, t)
+                    } else {
+                        (h, corrupt)
+                    };
+                    if known.contains(&(nh, r, nt)) {
+                        continue;
+                    }
+                    let pos = model.energy(&entities[h], &relations[r], &entities[t]);
+                    let neg = model.energy(&entities[nh], &relations[r], &entities[nt]);
+                    let l = params.margin + pos - neg;
+                    count += 1;
+                    if l <= 0.0 {
+                        continue;
+                    }
+                    total += l;
+                    // gradients calculés avant toute mise à jour
+                    let [ph, pr, pt] = model.gradients(&entities[h], &relations[r], &entities[t]);
+                    let [qh, qr, qt] = model.gradients(&entities[nh], &relations[r], &entities[nt]);
+                    apply(&mut entities[h], &ph, lr);
+                    apply(&mut entities[t], &pt, lr);
+                    apply(&mut relations[r], &pr, lr);
+                    apply(&mut entities[nh], &qh, -lr);
+                    apply(&mut entities[nt], &qt, -lr);
+                    apply(&mut relations[r], &qr, -lr);
+                }
+            }
+            for v in &mut entities {
+                l2_normalize(v);
+            }
+            loss = if count == 0 {
+                0.0
+            } else {
+                total / count as f32
+            };
+        }
+
+        let names = ids.iter().map(|&id| g.nodes[id].name.clone()).collect();
+        let relations: BTreeMap<String, Vec<f32>> = rel_names.into_iter().zip(relations).collect();
+        KgeEmbeddings::new(model, dim, names, entities, relations, loss)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kge::{KgeModel, LinkPrediction};
+
+    /// Lunes de Jupiter et de Saturne ; `orbite` est retenu pour la
+    /// dernière lune de chaque groupe, connue seulement par son groupe.
+    fn moons() -> Graph {
+        let mut g = Graph::new();
+        for (planet, group) in [("Jupiter", "galiléens"), ("Saturne", "inuits")] {
+            for i in 0..6 {
+                let moon = format!("{planet}_{i}");
+                g.add_edge(&moon, "membre_de", group);
+                if i < 5 {
+                    g.add_edge(&moon, "orbite", planet);
+                }
+            }
+            g.add_edge(planet, "orbite", "Soleil");
+        }
+        g
+    }
+
+    /// Trois systèmes de six lunes reliées deux à deux (relation symétrique),
+    /// sans le lien entre la première et la dernière lune de chacun.
+    fn systems() -> Graph {
+        let mut g = Graph::new();
+        for planet in SYSTEMS {
+            for i in 0..6 {
+                for j in (0..6).filter(|&j| j != i && ![(0, 5), (5, 0)].contains(&(i, j))) {
+                    g.add_edge(
+                        &format!("{planet}_{i}"),
+                        "même_système",
+                        &format!("{planet}_{j}"),
+                    );
+                }
+            }
+        }
+        g
+    }
+
+    const SYSTEMS: [&str; 3] = ["Jupiter", "Saturne", "Uranus"];
+
+    /// Rang (0 = premier) de `expected` parmi tous les candidats.
+    fn rank_of(predictions: &[LinkPrediction], expected: &str) -> usize {
+        predictions
+            .iter()
+            .position(|p| p.node == expected)
+            .expect("candidat classé")
+    }
+
+    fn train(g: &Graph, model: KgeModel) -> KgeEmbeddings {
+        let params = KgeParams {
+            model,
+            dim: Some(16),
+            ..KgeParams::default()
+        };
+        let kge = KgeEmbeddings::train(g, &params);
+        // même graine, même modèle
+        assert_eq!(kge.entities, KgeEmbeddings::train(g, &params).entities);
+        kge
+    }
+
+    #[test]
+    fn transe_ranks_held_out_links_above_random() {
+        let g = moons();
+        let kge = train(&g, KgeModel::TransE);
+        let candidates = g.nodes.len() - 1;
+        for (moon, planet) in [("Jupiter_5", "Jupiter"), ("Saturne_5", "Saturne")] {
+            let objects = g.predict_objects(&kge, moon, "orbite", usize::MAX).unwrap();
+            assert_eq!(objects.len(), candidates);
+            let rank = rank_of(&objects, planet);
+            // un classement au hasard donnerait en moyenne candidates / 2
+            assert!(rank < 3, "{moon} orbite {planet} au rang {rank}");
+            assert!(!objects[rank].known);
+
+            let subjects = g
+                .predict_subjects(&kge, "orbite", planet, usize::MAX)
+                .unwrap();
+            let rank = rank_of(&subjects, moon);
+            assert!(
+                rank < candidates / 2,
+                "{moon} orbite {planet} au rang {rank}"
+            );
+        }
+    }
+
+    #[test]
+    fn distmult_ranks_held_out_symmetric_links_above_random() {
+        let g = systems();
+        let kge = train(&g, KgeModel::DistMult);
+        for planet in SYSTEMS {
+            let (first, last) = (format!("{planet}_0"), format!("{planet}_5"));
+            let objects = g
+                .predict_objects(&kge, &first, "même_système", usize::MAX)
+                .unwrap();
+            let subjects = g
+                .predict_subjects(&kge, "même_système", &first, usize::MAX)
+                .unwrap();
+            for ranking in [objects, subjects] {
+                // juste après les quatre voisins connus, avant toute lune d'un autre système
+                let rank = rank_of(&ranking, &last);
+                assert!(ranking[..4].iter().all(|p| p.known), "{planet}");
+                assert_eq!(rank, 4, "{first} même_système {last}");
+                assert!(!ranking[rank].known);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+pub mod adjacency;
+pub mod ann;
+pub mod edge;
+pub mod embedders;
+pub mod embedding;
+pub mod fact;
+pub mod fact_row;
+pub mod feedback;
+pub mod formats;
+pub mod graph;
+pub mod graph_op;
+pub mod hybrid;
+pub mod kge;
+pub mod names;
+pub mod node;
+pub mod provenance;
+pub mod query;
+pub mod resolve;
+pub mod rules;
+pub mod schema;
+pub mod server;
+pub mod store;
+pub mod temporal;
+pub mod time;
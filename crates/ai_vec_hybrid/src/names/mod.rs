@@ -0,0 +1,7 @@
+pub mod name_lookup;
+pub mod name_match;
+pub mod name_trie;
+
+pub use name_lookup::max_distance_for;
+pub use name_match::{MatchKind, NameMatch};
+pub use name_trie::NameTrie;
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::embedding::cosine;
+use crate::graph::Graph;
+use crate::names::name_match::{MatchKind, NameMatch};
+use crate::resolve::normalize_name;
+
+/// Texte normalisé plus court que ceci : pas de candidats lexicaux dans
+/// `search_text` (un ou deux caractères sont le préfixe de trop de noms).
+const MIN_LEXICAL_LEN: usize = 3;
+
+/// Éditions tolérées pour un texte de `len` caractères : aucune jusqu'à 3,
+/// une jusqu'à 7, deux au-delà.
+pub fn max_distance_for(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().count()
+}
+
+impl Graph {
+    /// Nœud vivant derrière `id` (un nœud fusionné renvoie vers celui qui
+    /// l'a absorbé, comme ses alias).
+    fn live_node(&self, mut id: usize) -> usize {
+        while let Some(into) = self.nodes[id].merged_into {
+            id = into;
+        }
+        id
+    }
+
+    /// Garde la meilleure correspondance par nœud, triées par score.
+    fn best_matches(&self, found: Vec<(usize, MatchKind, usize, f32)>, k: usize) -> Vec<NameMatch> {
+        let mut best: HashMap<usize, NameMatch> = HashMap::new();
+        for (id, kind, distance, score) in found {
+            let id = self.live_node(id);
+            if best.get(&id).is_none_or(|m| score > m.score) {
+                best.insert(
+                    id,
+                    NameMatch {
+                        node: self.nodes[id].name.clone(),
+                        kind,
+                        distance,
+                        score,
+                    },
+                );
+            }
+        }
+        let mut matches: Vec<NameMatch> = best.into_values().collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.node.cmp(&b.node))
+        });
+        matches.truncate(k);
+        matches
+    }
+
+    /// Complétion : noms qui commencent par `prefix` (après normalisation),
+    /// les plus courts d'abord.
+    pub fn complete(&self, prefix: &str, k: usize) -> Vec<NameMatch> {
+        let typed = char_len(&normalize_name(prefix));
+        // des nœuds fusionnés peuvent se replier sur un même nœud
+        let merged = self.merges.iter().filter(|m| m.is_active()).count();
+        let found = self
+            .names
+            .prefix(prefix, k + merged)
+            .into_iter()
+            .map(|(key, id)| {
+                let len = char_len(&key);
+                if len == typed {
+                    (id, MatchKind::Exact, 0, 1.0)
+                } else {
+                    (
+                        id,
+                        MatchKind::Prefix,
+                        0,
+                        0.5 + 0.5 * typed as f32 / len as f32,
+                    )
+                }
+            })
+            .collect();
+        self.best_matches(found, k)
+    }
+
+    /// Noms à au plus `max_distance` éditions de `name`.
+    pub fn fuzzy_names(&self, name: &str, max_distance: usize, k: usize) -> Vec<NameMatch> {
+        let typed = char_len(&normalize_name(name));
+        let found = self
+            .names
+            .fuzzy(name, max_distance)
+            .into_iter()
+            .map(|(key, id, d)| {
+                let len = typed.max(char_len(&key)).max(1);
+                let kind = if d == 0 {
+                    MatchKind::Exact
+                } else {
+                    MatchKind::Fuzzy
+                };
+                (id, kind, d, 1.0 - d as f32 / len as f32)
+            })
+            .collect();
+        self.best_matches(found, k)
+    }
+
+    /// Recherche par nom saisi : complétions et noms approchés (distance
+    /// selon `max_distance_for`), la meilleure correspondance par nœud.
+    pub fn lookup_names(&self, text: &str, k: usize) -> Vec<NameMatch> {
+        let typed = char_len(&normalize_name(text));
+        let mut all = self.complete(text, k);
+        all.extend(self.fuzzy_names(text, max_distance_for(typed), k));
+        let found = all
+            .into_iter()
+            .map(|m| (self.name2id[&m.node], m.kind, m.distance, m.score))
+            .collect();
+        self.best_matches(found, k)
+    }
+
+    /// Premier étage de `search_text` : k-NN vectoriel et noms proches du
+    /// texte. Un nœud retrouvé par son nom garde le meilleur de ses deux
+    /// scores, si bien qu'un nom mal orthographié ou tronqué remonte même
+    /// quand son embedding est loin de la requête.
+    pub(crate) fn candidates(&self, query: &str, q: &[f32], k: usize) -> Vec<(usize, f32)> {
+        let mut scores: HashMap<usize, f32> = self.k_nn(q, k).into_iter().collect();
+        if char_len(&normalize_name(query)) >= MIN_LEXICAL_LEN {
+            for m in self.lookup_names(query, k) {
+                let id = self.name2id[&m.node];
+                let score = m.score.max(cosine(&self.nodes[id].emb, q));
+                let s = scores.entry(id).or_insert(score);
+                *s = s.max(score);
+            }
+        }
+        let mut hits: Vec<(usize, f32)> = scores.into_iter().collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.truncate(k);
+        hits
+    }
+}
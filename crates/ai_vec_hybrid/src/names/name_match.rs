@@ -0,0 +1,37 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Comment un nom a été retrouvé.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// Même forme normalisée.
+    Exact,
+    /// Le texte saisi est un début du nom.
+    Prefix,
+    /// Nom à quelques éditions près (`distance`).
+    Fuzzy,
+}
+
+/// Nœud retrouvé par son nom.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NameMatch {
+    pub node: String,
+    pub kind: MatchKind,
+    /// Éditions entre le texte saisi et le nom (0 sauf pour `Fuzzy`).
+    pub distance: usize,
+    /// Proximité lexicale dans [0, 1] (1 : nom exact).
+    pub score: f32,
+}
+
+impl fmt::Display for NameMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            MatchKind::Exact => "exact".to_string(),
+            MatchKind::Prefix => "préfixe".to_string(),
+            MatchKind::Fuzzy => format!("{} édition(s)", self.distance),
+        };
+        write!(f, "{:>8.3}  {}  ({kind})", self.score, self.node)
+    }
+}
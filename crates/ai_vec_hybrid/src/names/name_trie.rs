@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::resolve::normalize_name;
+
+#[derive(Clone, Debug, Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    /// Nœuds du graphe dont le nom normalisé s'arrête ici.
+    ids: Vec<usize>,
+}
+
+/// Trie des noms de nœuds, indexés sous leur forme normalisée
+/// (`normalize_name`) : complétion par préfixe et recherche approchée à
+/// distance de Levenshtein bornée.
+#[derive(Clone, Debug)]
+pub struct NameTrie {
+    // nœuds du trie, la racine en 0
+    nodes: Vec<TrieNode>,
+    len: usize,
+}
+
+impl Default for NameTrie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+            len: 0,
+        }
+    }
+}
+
+impl NameTrie {
+    /// Nombre de noms indexés.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, name: &str, id: usize) {
+        let mut cur = 0;
+        for c in normalize_name(name).chars() {
+            cur = match self.nodes[cur].children.get(&c) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[cur].children.insert(c, next);
+                    next
+                }
+            };
+        }
+        if !self.nodes[cur].ids.contains(&id) {
+            self.nodes[cur].ids.push(id);
+            self.len += 1;
+        }
+    }
+
+    fn find(&self, key: &str) -> Option<usize> {
+        key.chars()
+            .try_fold(0, |cur, c| self.nodes[cur].children.get(&c).copied())
+    }
+
+    /// Nœuds dont le nom a la même forme normalisée que `name`.
+    pub fn exact(&self, name: &str) -> &[usize] {
+        match self.find(&normalize_name(name)) {
+            Some(n) => &self.nodes[n].ids,
+            None => &[],
+        }
+    }
+
+    /// Noms qui commencent par `prefix` (forme normalisée, id), les plus
+    /// courts d'abord, au plus `limit`.
+    pub fn prefix(&self, prefix: &str, limit: usize) -> Vec<(String, usize)> {
+        let key = normalize_name(prefix);
+        let Some(start) = self.find(&key) else {
+            return vec![];
+        };
+        // parcours en largeur : les complétions les plus courtes sortent en premier
+        let mut out = vec![];
+        let mut queue = VecDeque::from([(start, key)]);
+        while let Some((n, key)) = queue.pop_front() {
+            for &id in &self.nodes[n].ids {
+                if out.len() == limit {
+                    return out;
+                }
+                out.push((key.clone(), id));
+            }
+            for (&c, &child) in &self.nodes[n].children {
+                let mut next = key.clone();
+                next.push(c);
+                queue.push_back((child, next));
+            }
+        }
+        out
+    }
+
+    /// Noms à au plus `max_distance` éditions de `name` (forme normalisée,
+    /// id, distance), triés par distance puis par nom. Les éditions sont
+    /// celles de Levenshtein, plus l'échange de deux lettres voisines
+    /// (`Kupier` → `Kuiper` : une faute de frappe, une édition).
+    ///
+    /// Une ligne de la matrice de distances est calculée par nœud du trie :
+    /// les préfixes communs ne sont évalués qu'une fois, et une branche est
+    /// abandonnée dès que sa ligne dépasse la borne partout.
+    pub fn fuzzy(&self, name: &str, max_distance: usize) -> Vec<(String, usize, usize)> {
+        struct Frame {
+            node: usize,
+            key: Vec<char>,
+            row: Vec<usize>,
+            // ligne du nœud parent (pour les échanges)
+            parent: Vec<usize>,
+        }
+
+        let query: Vec<char> = normalize_name(name).chars().collect();
+        let first: Vec<usize> = (0..=query.len()).collect();
+        let mut out = vec![];
+        let mut stack = vec![Frame {
+            node: 0,
+            key: vec![],
+            row: first.clone(),
+            parent: first,
+        }];
+        while let Some(f) = stack.pop() {
+            let distance = f.row[query.len()];
+            if distance <= max_distance {
+                let key: String = f.key.iter().collect();
+                out.extend(
+                    self.nodes[f.node]
+                        .ids
+                        .iter()
+                        .map(|&id| (key.clone(), id, distance)),
+                );
+            }
+            let last = f.key.last().copied();
+            for (&c, &child) in &self.nodes[f.node].children {
+                let mut next = vec![f.row[0] + 1; query.len() + 1];
+                for (j, &q) in query.iter().enumerate() {
+                    let subst = f.row[j] + usize::from(q != c);
+                    next[j + 1] = subst.min(f.row[j + 1] + 1).min(next[j] + 1);
+                    if j > 0 && q == last.unwrap_or('\0') && query[j - 1] == c {
+                        next[j + 1] = next[j + 1].min(f.parent[j - 1] + 1);
+                    }
+                }
+                if next.iter().min().is_some_and(|&m| m <= max_distance) {
+                    let mut key = f.key.clone();
+                    key.push(c);
+                    stack.push(Frame {
+                        node: child,
+                        key,
+                        row: next,
+                        parent: f.row.clone(),
+                    });
+                }
+            }
+        }
+        out.sort_by(|a, b| {
+            a.2.cmp(&b.2)
+                .then_with(|| a.0.cmp(&b.0))
+                .then(a.1.cmp(&b.1))
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: [&str; 8] = [
+        "Pluton",
+        "Plutonium",
+        "Pluto",
+        "Planète naine",
+        "Kuiper",
+        "Kuiper_belt",
+        "Charon",
+        "Éris",
+    ];
+
+    fn trie() -> NameTrie {
+        let mut t = NameTrie::default();
+        for (id, name) in NAMES.iter().enumerate() {
+            t.insert(name, id);
+        }
+        t
+    }
+
+    /// Distance d'édition avec échange de lettres voisines, par la matrice
+    /// complète (référence pour `fuzzy`).
+    fn distance(a: &str, b: &str) -> usize {
+        let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+        let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        d[0] = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                d[i][j] = (d[i - 1][j - 1] + cost)
+                    .min(d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    #[test]
+    fn normalized_forms_are_indexed_once() {
+        let mut t = trie();
+        assert_eq!(t.len(), NAMES.len());
+        t.insert("PLUTON", 0);
+        assert_eq!(t.len(), NAMES.len());
+        assert_eq!(t.exact("pluton"), [0]);
+        assert_eq!(t.exact("eris"), [7]);
+        assert_eq!(t.exact("kuiper belt"), [5]);
+        assert!(t.exact("plut").is_empty());
+    }
+
+    #[test]
+    fn prefix_lists_shortest_completions_first() {
+        let t = trie();
+        let found: Vec<String> = t.prefix("Plu", 10).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(found, ["pluto", "pluton", "plutonium"]);
+        assert_eq!(t.prefix("Plu", 2).len(), 2);
+        assert_eq!(
+            t.prefix("kuiper", 10),
+            [("kuiper".into(), 4), ("kuiper belt".into(), 5)]
+        );
+        assert_eq!(t.prefix("", 100).len(), NAMES.len());
+        assert!(t.prefix("Saturne", 10).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_counts_a_transposition_as_one_edit() {
+        let t = trie();
+        assert_eq!(t.fuzzy("Kupier", 1), [("kuiper".into(), 4, 1)]);
+        assert_eq!(t.fuzzy("Chraon", 1), [("charon".into(), 6, 1)]);
+        assert_eq!(
+            t.fuzzy("Plutno", 1),
+            [("pluto".into(), 2, 1), ("pluton".into(), 0, 1)]
+        );
+        assert!(t.fuzzy("Kupier", 0).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_matches_the_full_matrix() {
+        let t = trie();
+        for query in [
+            "plto",
+            "pultno",
+            "kuiperbelt",
+            "eirs",
+            "charno",
+            "planete",
+            "x",
+        ] {
+            for max in 0..=3 {
+                let mut expected: Vec<(String, usize, usize)> = NAMES
+                    .iter()
+                    .enumerate()
+                    .map(|(id, name)| {
+                        let name = normalize_name(name);
+                        let d = distance(query, &name);
+                        (name, id, d)
+                    })
+                    .filter(|&(_, _, d)| d <= max)
+                    .collect();
+                expected.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.0.cmp(&b.0)));
+                assert_eq!(t.fuzzy(query, max), expected, "{query} à {max}");
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::time::Timestamp;
+
+#[derive(Clone)]
+pub struct Node {
+    pub id: usize,
+    pub name: String,
+    pub emb: Vec<f32>,
+    /// Embeddings remplacés : (date du remplacement, ancien vecteur), du plus ancien au plus récent.
+    pub emb_history: Vec<(Timestamp, Vec<f32>)>,
+    /// Nœud conservé lors d'une fusion (ce nœud n'est plus qu'un alias).
+    pub merged_into: Option<usize>,
+}
@@ -0,0 +1,106 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Origine d'un fait : confiance dans `[0, 1]` et source (fichier,
+/// utilisateur, extracteur…). La date d'insertion est `Edge::recorded_at`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    #[serde(
+        default = "certain",
+        deserialize_with = "confidence",
+        skip_serializing_if = "is_certain"
+    )]
+    pub confidence: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+fn certain() -> f32 {
+    1.0
+}
+
+fn is_certain(c: &f32) -> bool {
+    *c == 1.0
+}
+
+/// Confiance relue (import JSON, WAL, snapshot) : une valeur hors de
+/// `[0, 1]` est refusée, et non ramenée dans l'intervalle comme par `new`.
+fn confidence<'de, D: Deserializer<'de>>(d: D) -> Result<f32, D::Error> {
+    let c = f32::deserialize(d)?;
+    if !(0.0..=1.0).contains(&c) {
+        return Err(D::Error::custom(format!("confidence {c} hors de [0, 1]")));
+    }
+    Ok(c)
+}
+
+impl Default for Provenance {
+    fn default() -> Self {
+        Self {
+            confidence: 1.0,
+            source: None,
+        }
+    }
+}
+
+impl Provenance {
+    /// La confiance est ramenée dans `[0, 1]`.
+    pub fn new(confidence: f32, source: Option<String>) -> Self {
+        Self {
+            confidence: if confidence.is_nan() {
+                0.0
+            } else {
+                confidence.clamp(0.0, 1.0)
+            },
+            source,
+        }
+    }
+
+    pub fn from_source(source: impl Into<String>) -> Self {
+        Self::new(1.0, Some(source.into()))
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fact::Fact;
+    use crate::formats::json_facts::read_json_facts;
+    use crate::formats::FormatError;
+
+    #[test]
+    fn round_trips_through_json() {
+        for p in [
+            Provenance::default(),
+            Provenance::new(0.25, Some("UAI 2006".into())),
+            Provenance::new(0.0, None),
+        ] {
+            let json = serde_json::to_string(&p).unwrap();
+            assert_eq!(serde_json::from_str::<Provenance>(&json).unwrap(), p);
+        }
+        // la valeur par défaut n'est pas écrite
+        assert_eq!(serde_json::to_string(&Provenance::default()).unwrap(), "{}");
+        assert_eq!(Provenance::new(f32::NAN, None).confidence, 0.0);
+        assert_eq!(Provenance::new(3.0, None).confidence, 1.0);
+    }
+
+    #[test]
+    fn rejects_out_of_range_confidence_with_its_line() {
+        let input = r#"[
+  {"subj": "Pluton", "rel": "est_une", "obj": "planète", "confidence": 0.5},
+  {"subj": "Pluton", "rel": "orbite", "obj": "Soleil", "confidence": 1.5}
+]"#;
+        match read_json_facts(input.as_bytes()) {
+            Err(FormatError::Syntax { line, message }) => {
+                assert_eq!(line, 3);
+                assert!(message.contains("confidence 1.5"), "{message}");
+            }
+            other => panic!("erreur attendue : {other:?}"),
+        }
+        let negative = r#"{"subj": "a", "rel": "r", "obj": "b", "confidence": -0.1}"#;
+        assert!(serde_json::from_str::<Fact>(negative).is_err());
+    }
+}
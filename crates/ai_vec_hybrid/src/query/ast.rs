@@ -0,0 +1,37 @@
+/// Terme d'un motif : variable `?x` ou constante (nom de nœud ou de relation).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Term {
+    Var(String),
+    Const(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Clause {
+    /// `sujet relation objet`
+    Triple { subj: Term, rel: Term, obj: Term },
+    /// `similar_to(?x, "texte", k)` : `?x` parmi les k plus proches voisins du texte.
+    SimilarTo { var: String, text: String, k: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderKey {
+    Var(String),
+    /// Somme des similarités des prédicats `similar_to`.
+    Score,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderBy {
+    pub key: OrderKey,
+    pub descending: bool,
+}
+
+/// `SELECT ?a ?b WHERE { ... } ORDER BY ... LIMIT n`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    /// Variables projetées (vide = `SELECT *`).
+    pub select: Vec<String>,
+    pub clauses: Vec<Clause>,
+    pub order: Option<OrderBy>,
+    pub limit: Option<usize>,
+}
@@ -0,0 +1,276 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::graph::Graph;
+use crate::query::ast::{Clause, OrderKey, Query, Term};
+use crate::query::parser::{clause_vars, parse_query};
+use crate::query::query_error::QueryResult;
+
+/// Une ligne de résultat : valeurs dans l'ordre de `QueryAnswer::vars`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Row {
+    pub values: Vec<String>,
+    /// Somme des similarités `similar_to` (None sans prédicat de similarité).
+    pub score: Option<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct QueryAnswer {
+    pub vars: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+impl fmt::Display for QueryAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header: Vec<String> = self.vars.iter().map(|v| format!("?{v}")).collect();
+        writeln!(f, "{}", header.join("\t"))?;
+        for row in &self.rows {
+            write!(f, "{}", row.values.join("\t"))?;
+            if let Some(s) = row.score {
+                write!(f, "\t(score={s:.3})")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "({} résultat(s))", self.rows.len())
+    }
+}
+
+type Bindings = HashMap<String, String>;
+
+impl Graph {
+    /// Analyse puis exécute une requête texte.
+    pub fn query_str(&self, src: &str) -> QueryResult<QueryAnswer> {
+        Ok(self.query(&parse_query(src)?))
+    }
+
+    /// Exécute une requête sur les faits courants (dérivés compris).
+    ///
+    /// Les prédicats `similar_to` sont évalués en premier (ils génèrent des
+    /// candidats), puis les motifs dans l'ordre d'écriture, par retour arrière.
+    /// Les lignes projetées identiques sont fusionnées (meilleur score gardé).
+    pub fn query(&self, q: &Query) -> QueryAnswer {
+        let mut clauses: Vec<&Clause> = q
+            .clauses
+            .iter()
+            .filter(|c| matches!(c, Clause::SimilarTo { .. }))
+            .collect();
+        clauses.extend(
+            q.clauses
+                .iter()
+                .filter(|c| matches!(c, Clause::Triple { .. })),
+        );
+
+        // k-NN calculés une seule fois par prédicat
+        let knn: Vec<Vec<(String, f32)>> = clauses
+            .iter()
+            .map(|c| match c {
+                Clause::SimilarTo { text, k, .. } => self.search_text(text, *k),
+                Clause::Triple { .. } => vec![],
+            })
+            .collect();
+
+        let vars: Vec<String> = if q.select.is_empty() {
+            let mut seen = HashSet::new();
+            q.clauses
+                .iter()
+                .flat_map(clause_vars)
+                .filter(|v| seen.insert(*v))
+                .map(str::to_string)
+                .collect()
+        } else {
+            q.select.clone()
+        };
+
+        let mut solutions: Vec<(Bindings, Option<f32>)> = vec![];
+        self.solve(
+            &clauses,
+            &knn,
+            0,
+            &mut Bindings::new(),
+            None,
+            &mut solutions,
+        );
+
+        let mut rows: Vec<Row> = vec![];
+        // position de chaque ligne projetée déjà vue
+        let mut seen: HashMap<Vec<String>, usize> = HashMap::new();
+        for (b, score) in solutions {
+            let values: Vec<String> = vars.iter().map(|v| b[v].clone()).collect();
+            match seen.get(&values) {
+                Some(&i) => {
+                    if score > rows[i].score {
+                        rows[i].score = score;
+                    }
+                }
+                None => {
+                    seen.insert(values.clone(), rows.len());
+                    rows.push(Row { values, score });
+                }
+            }
+        }
+
+        if let Some(order) = &q.order {
+            let idx = match &order.key {
+                OrderKey::Var(v) => vars.iter().position(|x| x == v),
+                OrderKey::Score => None,
+            };
+            rows.sort_by(|a, b| {
+                let ord = match idx {
+                    Some(i) => a.values[i].cmp(&b.values[i]),
+                    None => a
+                        .score
+                        .unwrap_or(f32::NEG_INFINITY)
+                        .total_cmp(&b.score.unwrap_or(f32::NEG_INFINITY)),
+                };
+                if order.descending {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            });
+        }
+        if let Some(n) = q.limit {
+            rows.truncate(n);
+        }
+        QueryAnswer { vars, rows }
+    }
+
+    fn solve(
+        &self,
+        clauses: &[&Clause],
+        knn: &[Vec<(String, f32)>],
+        i: usize,
+        b: &mut Bindings,
+        score: Option<f32>,
+        out: &mut Vec<(Bindings, Option<f32>)>,
+    ) {
+        let Some(clause) = clauses.get(i) else {
+            out.push((b.clone(), score));
+            return;
+        };
+        match clause {
+            Clause::SimilarTo { var, .. } => {
+                let hits = &knn[i];
+                let add = |s: f32| Some(score.unwrap_or(0.0) + s);
+                if let Some(bound) = b.get(var) {
+                    if let Some((_, s)) = hits.iter().find(|(n, _)| n == bound) {
+                        self.solve(clauses, knn, i + 1, b, add(*s), out);
+                    }
+                    return;
+                }
+                for (name, s) in hits {
+                    let s = *s;
+                    b.insert(var.clone(), name.clone());
+                    self.solve(clauses, knn, i + 1, b, add(s), out);
+                    b.remove(var);
+                }
+            }
+            Clause::Triple { subj, rel, obj } => {
+                let resolve = |t: &Term| -> Option<String> {
+                    match t {
+                        Term::Const(c) => Some(c.clone()),
+                        Term::Var(v) => b.get(v).cloned(),
+                    }
+                };
+                let (s, r, o) = (resolve(subj), resolve(rel), resolve(obj));
+                let candidates = match (&s, &r, &o) {
+                    (Some(name), _, _) => self.outgoing(name),
+                    (None, _, Some(name)) => self.incoming(name),
+                    (None, Some(rel), None) => self.edges_with_rel(rel),
+                    (None, None, None) => self.edges.iter().collect(),
+                };
+                for e in candidates {
+                    let (en, eo) = (&self.nodes[e.from].name, &self.nodes[e.to].name);
+                    if r.as_ref().is_some_and(|r| r != &e.rel)
+                        || o.as_ref().is_some_and(|o| o != eo)
+                        || s.as_ref().is_some_and(|s| s != en)
+                    {
+                        continue;
+                    }
+                    // lie les variables libres, en vérifiant les répétitions (?x rel ?x)
+                    let mut added: Vec<String> = vec![];
+                    let mut ok = true;
+                    for (t, val) in [(subj, en), (rel, &e.rel), (obj, eo)] {
+                        if let Term::Var(v) = t {
+                            match b.get(v) {
+                                Some(cur) if cur != val => ok = false,
+                                Some(_) => {}
+                                None => {
+                                    b.insert(v.clone(), val.clone());
+                                    added.push(v.clone());
+                                }
+                            }
+                        }
+                    }
+                    if ok {
+                        self.solve(clauses, knn, i + 1, b, score, out);
+                    }
+                    for v in added {
+                        b.remove(&v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solar_system() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Terre", "orbite", "Soleil");
+        g.add_edge("Mars", "orbite", "Soleil");
+        g.add_edge("Lune", "orbite", "Terre");
+        g.add_edge("Terre", "est_une", "planète");
+        g.add_edge("Mars", "est_une", "planète");
+        g
+    }
+
+    fn column(answer: &QueryAnswer, i: usize) -> Vec<&str> {
+        answer.rows.iter().map(|r| r.values[i].as_str()).collect()
+    }
+
+    #[test]
+    fn joins_patterns_and_orders_rows() {
+        let g = solar_system();
+        let a = g
+            .query_str("SELECT ?lune ?p WHERE { ?lune orbite ?p . ?p est_une planète }")
+            .unwrap();
+        assert_eq!(a.vars, ["lune", "p"]);
+        assert_eq!(a.rows.len(), 1);
+        assert_eq!(a.rows[0].values, ["Lune", "Terre"]);
+        assert_eq!(a.rows[0].score, None);
+
+        let a = g
+            .query_str("SELECT ?x WHERE { ?x orbite ?y } ORDER BY ?x DESC LIMIT 2")
+            .unwrap();
+        assert_eq!(column(&a, 0), ["Terre", "Mars"]);
+
+        // relation en variable, SELECT * dans l'ordre d'apparition
+        let a = g
+            .query_str("SELECT * WHERE { Terre ?r ?o } ORDER BY ?r")
+            .unwrap();
+        assert_eq!(a.vars, ["r", "o"]);
+        assert_eq!(column(&a, 0), ["est_une", "orbite"]);
+    }
+
+    #[test]
+    fn merges_identical_projected_rows() {
+        let g = solar_system();
+        // deux solutions (Terre, Mars) projetées sur la même ligne
+        let a = g
+            .query_str("SELECT ?c WHERE { ?x orbite Soleil . ?x est_une ?c }")
+            .unwrap();
+        assert_eq!(column(&a, 0), ["planète"]);
+
+        let a = g
+            .query_str("SELECT ?x WHERE { ?x orbite Soleil . similar_to(?x, \"Terre\", 3) } ORDER BY score")
+            .unwrap();
+        assert_eq!(a.rows[0].values, ["Terre"]);
+        assert!(a.rows.iter().all(|r| r.score.is_some()));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::query::query_error::{QueryError, QueryResult};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tok {
+    Var(String),
+    Ident(String),
+    Str(String),
+    Num(usize),
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Dot,
+    Comma,
+    Star,
+}
+
+/// Jeton avec sa position (ligne, colonne).
+#[derive(Clone, Debug)]
+pub struct Spanned {
+    pub tok: Tok,
+    pub line: usize,
+    pub col: usize,
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == ':'
+}
+
+pub fn tokenize(src: &str) -> QueryResult<Vec<Spanned>> {
+    let mut out = vec![];
+    let chars: Vec<char> = src.chars().collect();
+    let (mut i, mut line, mut col) = (0, 1, 1);
+    let err = |line, col, message: String| QueryError { line, col, message };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let (start_line, start_col) = (line, col);
+        let mut advance = |n: usize, i: &mut usize| {
+            for _ in 0..n {
+                if chars[*i] == '\n' {
+                    line += 1;
+                    col = 1;
+                } else {
+                    col += 1;
+                }
+                *i += 1;
+            }
+        };
+
+        if c.is_whitespace() {
+            advance(1, &mut i);
+            continue;
+        }
+        if c == '#' {
+            // commentaire jusqu'à la fin de ligne
+            while i < chars.len() && chars[i] != '\n' {
+                advance(1, &mut i);
+            }
+            continue;
+        }
+        let tok = match c {
+            '{' => Tok::LBrace,
+            '}' => Tok::RBrace,
+            '(' => Tok::LParen,
+            ')' => Tok::RParen,
+            '.' => Tok::Dot,
+            ',' => Tok::Comma,
+            '*' => Tok::Star,
+            '"' => {
+                let mut s = String::new();
+                let mut j = i + 1;
+                loop {
+                    match chars.get(j) {
+                        None => {
+                            return Err(err(start_line, start_col, "chaîne non terminée".into()))
+                        }
+                        Some('"') => break,
+                        Some('\\') if j + 1 < chars.len() => {
+                            s.push(chars[j + 1]);
+                            j += 2;
+                        }
+                        Some(&ch) => {
+                            s.push(ch);
+                            j += 1;
+                        }
+                    }
+                }
+                advance(j + 1 - i, &mut i);
+                out.push(Spanned {
+                    tok: Tok::Str(s),
+                    line: start_line,
+                    col: start_col,
+                });
+                continue;
+            }
+            '?' => {
+                let mut j = i + 1;
+                while j < chars.len() && is_ident_char(chars[j]) {
+                    j += 1;
+                }
+                if j == i + 1 {
+                    return Err(err(
+                        start_line,
+                        start_col,
+                        "nom de variable attendu après '?'".into(),
+                    ));
+                }
+                let name: String = chars[i + 1..j].iter().collect();
+                advance(j - i, &mut i);
+                out.push(Spanned {
+                    tok: Tok::Var(name),
+                    line: start_line,
+                    col: start_col,
+                });
+                continue;
+            }
+            c if is_ident_char(c) => {
+                let mut j = i;
+                while j < chars.len() && is_ident_char(chars[j]) {
+                    j += 1;
+                }
+                let word: String = chars[i..j].iter().collect();
+                advance(j - i, &mut i);
+                let tok = match word.parse::<usize>() {
+                    Ok(n) => Tok::Num(n),
+                    Err(_) => Tok::Ident(word),
+                };
+                out.push(Spanned {
+                    tok,
+                    line: start_line,
+                    col: start_col,
+                });
+                continue;
+            }
+            other => {
+                return Err(err(
+                    start_line,
+                    start_col,
+                    format!("caractère inattendu '{other}'"),
+                ))
+            }
+        };
+        advance(1, &mut i);
+        out.push(Spanned {
+            tok,
+            line: start_line,
+            col: start_col,
+        });
+    }
+    Ok(out)
+}
@@ -0,0 +1,10 @@
+pub mod ast;
+pub mod executor;
+pub mod lexer;
+pub mod parser;
+pub mod query_error;
+
+pub use ast::{Clause, OrderBy, OrderKey, Query, Term};
+pub use executor::{QueryAnswer, Row};
+pub use parser::parse_query;
+pub use query_error::{QueryError, QueryResult};
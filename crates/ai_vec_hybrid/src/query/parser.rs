@@ -0,0 +1,347 @@
+use crate::query::ast::{Clause, OrderBy, OrderKey, Query, Term};
+use crate::query::lexer::{tokenize, Spanned, Tok};
+use crate::query::query_error::{QueryError, QueryResult};
+
+/// Analyse une requête :
+///
+/// ```text
+/// SELECT ?x ?c WHERE {
+///     ?x est_une ?c .
+///     similar_to(?x, "pluton", 5)
+/// } ORDER BY score DESC LIMIT 10
+/// ```
+///
+/// Les mots-clés sont insensibles à la casse ; les constantes sont des
+/// identifiants nus (`super-classe`, `corps_céleste`) ou des chaînes entre
+/// guillemets.
+pub fn parse_query(src: &str) -> QueryResult<Query> {
+    let toks = tokenize(src)?;
+    let mut p = Parser { toks, pos: 0 };
+    let q = p.query()?;
+    if let Some(t) = p.peek() {
+        return Err(p.error_at(t, "fin de requête attendue"));
+    }
+    Ok(q)
+}
+
+struct Parser {
+    toks: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Spanned> {
+        self.toks.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Spanned> {
+        let t = self.toks.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn error_at(&self, t: &Spanned, message: &str) -> QueryError {
+        QueryError {
+            line: t.line,
+            col: t.col,
+            message: format!("{message} (trouvé {})", describe(&t.tok)),
+        }
+    }
+
+    fn error_eof(&self, message: &str) -> QueryError {
+        let (line, col) = self.toks.last().map(|t| (t.line, t.col)).unwrap_or((1, 1));
+        QueryError {
+            line,
+            col,
+            message: format!("{message} (fin de requête)"),
+        }
+    }
+
+    fn expect(&mut self, want: &Tok, what: &str) -> QueryResult<()> {
+        match self.next() {
+            Some(t) if &t.tok == want => Ok(()),
+            Some(t) => Err(self.error_at(&t, &format!("{what} attendu"))),
+            None => Err(self.error_eof(&format!("{what} attendu"))),
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(Spanned { tok: Tok::Ident(w), .. }) if w.eq_ignore_ascii_case(kw))
+    }
+
+    fn keyword(&mut self, kw: &str) -> QueryResult<()> {
+        if self.is_keyword(kw) {
+            self.pos += 1;
+            return Ok(());
+        }
+        match self.peek() {
+            Some(t) => Err(self.error_at(t, &format!("mot-clé {kw} attendu"))),
+            None => Err(self.error_eof(&format!("mot-clé {kw} attendu"))),
+        }
+    }
+
+    fn query(&mut self) -> QueryResult<Query> {
+        self.keyword("SELECT")?;
+        let mut select = vec![];
+        if matches!(self.peek(), Some(Spanned { tok: Tok::Star, .. })) {
+            self.pos += 1;
+        } else {
+            while let Some(Spanned {
+                tok: Tok::Var(v), ..
+            }) = self.peek()
+            {
+                select.push(v.clone());
+                self.pos += 1;
+            }
+            if select.is_empty() {
+                return Err(match self.peek() {
+                    Some(t) => self.error_at(t, "variables ou * attendus après SELECT"),
+                    None => self.error_eof("variables ou * attendus après SELECT"),
+                });
+            }
+        }
+
+        self.keyword("WHERE")?;
+        self.expect(&Tok::LBrace, "'{'")?;
+        let mut clauses = vec![];
+        loop {
+            match self.peek().map(|t| &t.tok) {
+                Some(Tok::RBrace) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(Tok::Dot) => {
+                    self.pos += 1;
+                }
+                Some(_) => clauses.push(self.clause()?),
+                None => return Err(self.error_eof("'}' attendu")),
+            }
+        }
+        if clauses.is_empty() {
+            return Err(self.error_eof("au moins un motif attendu dans WHERE"));
+        }
+
+        let mut order = None;
+        if self.is_keyword("ORDER") {
+            self.pos += 1;
+            self.keyword("BY")?;
+            let key = match self.next() {
+                Some(Spanned {
+                    tok: Tok::Var(v), ..
+                }) => OrderKey::Var(v),
+                Some(Spanned {
+                    tok: Tok::Ident(w), ..
+                }) if w.eq_ignore_ascii_case("score") => OrderKey::Score,
+                Some(t) => {
+                    return Err(self.error_at(&t, "variable ou SCORE attendu après ORDER BY"))
+                }
+                None => return Err(self.error_eof("variable ou SCORE attendu après ORDER BY")),
+            };
+            let mut descending = key == OrderKey::Score;
+            if self.is_keyword("ASC") {
+                self.pos += 1;
+                descending = false;
+            } else if self.is_keyword("DESC") {
+                self.pos += 1;
+                descending = true;
+            }
+            order = Some(OrderBy { key, descending });
+        }
+
+        let mut limit = None;
+        if self.is_keyword("LIMIT") {
+            self.pos += 1;
+            match self.next() {
+                Some(Spanned {
+                    tok: Tok::Num(n), ..
+                }) => limit = Some(n),
+                Some(t) => return Err(self.error_at(&t, "nombre attendu après LIMIT")),
+                None => return Err(self.error_eof("nombre attendu après LIMIT")),
+            }
+        }
+
+        let q = Query {
+            select,
+            clauses,
+            order,
+            limit,
+        };
+        self.check_vars(&q)?;
+        Ok(q)
+    }
+
+    fn clause(&mut self) -> QueryResult<Clause> {
+        if self.is_keyword("similar_to") {
+            self.pos += 1;
+            self.expect(&Tok::LParen, "'('")?;
+            let var = match self.next() {
+                Some(Spanned {
+                    tok: Tok::Var(v), ..
+                }) => v,
+                Some(t) => return Err(self.error_at(&t, "variable attendue")),
+                None => return Err(self.error_eof("variable attendue")),
+            };
+            self.expect(&Tok::Comma, "','")?;
+            let text = match self.next() {
+                Some(Spanned {
+                    tok: Tok::Str(s), ..
+                }) => s,
+                Some(t) => return Err(self.error_at(&t, "texte entre guillemets attendu")),
+                None => return Err(self.error_eof("texte entre guillemets attendu")),
+            };
+            self.expect(&Tok::Comma, "','")?;
+            let k = match self.next() {
+                Some(Spanned {
+                    tok: Tok::Num(n), ..
+                }) => n,
+                Some(t) => return Err(self.error_at(&t, "nombre de voisins attendu")),
+                None => return Err(self.error_eof("nombre de voisins attendu")),
+            };
+            self.expect(&Tok::RParen, "')'")?;
+            return Ok(Clause::SimilarTo { var, text, k });
+        }
+        let subj = self.term()?;
+        let rel = self.term()?;
+        let obj = self.term()?;
+        Ok(Clause::Triple { subj, rel, obj })
+    }
+
+    fn term(&mut self) -> QueryResult<Term> {
+        match self.next() {
+            Some(Spanned {
+                tok: Tok::Var(v), ..
+            }) => Ok(Term::Var(v)),
+            Some(Spanned {
+                tok: Tok::Ident(w), ..
+            }) => Ok(Term::Const(w)),
+            Some(Spanned {
+                tok: Tok::Str(s), ..
+            }) => Ok(Term::Const(s)),
+            Some(Spanned {
+                tok: Tok::Num(n), ..
+            }) => Ok(Term::Const(n.to_string())),
+            Some(t) => Err(self.error_at(&t, "terme attendu (variable ou constante)")),
+            None => Err(self.error_eof("terme attendu (variable ou constante)")),
+        }
+    }
+
+    /// Les variables projetées ou triées doivent apparaître dans WHERE.
+    fn check_vars(&self, q: &Query) -> QueryResult<()> {
+        let bound: Vec<&str> = q.clauses.iter().flat_map(clause_vars).collect();
+        let mut used: Vec<&String> = q.select.iter().collect();
+        if let Some(OrderBy {
+            key: OrderKey::Var(v),
+            ..
+        }) = &q.order
+        {
+            used.push(v);
+        }
+        for v in used {
+            if !bound.contains(&v.as_str()) {
+                let (line, col) = self.toks.first().map(|t| (t.line, t.col)).unwrap_or((1, 1));
+                return Err(QueryError {
+                    line,
+                    col,
+                    message: format!("variable ?{v} absente de WHERE"),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn clause_vars(c: &Clause) -> Vec<&str> {
+    match c {
+        Clause::Triple { subj, rel, obj } => [subj, rel, obj]
+            .into_iter()
+            .filter_map(|t| match t {
+                Term::Var(v) => Some(v.as_str()),
+                Term::Const(_) => None,
+            })
+            .collect(),
+        Clause::SimilarTo { var, .. } => vec![var.as_str()],
+    }
+}
+
+fn describe(t: &Tok) -> String {
+    match t {
+        Tok::Var(v) => format!("?{v}"),
+        Tok::Ident(w) => format!("'{w}'"),
+        Tok::Str(s) => format!("\"{s}\""),
+        Tok::Num(n) => n.to_string(),
+        Tok::LBrace => "'{'".into(),
+        Tok::RBrace => "'}'".into(),
+        Tok::LParen => "'('".into(),
+        Tok::RParen => "')'".into(),
+        Tok::Dot => "'.'".into(),
+        Tok::Comma => "','".into(),
+        Tok::Star => "'*'".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_patterns_similarity_order_and_limit() {
+        let q = parse_query(
+            "select ?x ?c where {\n  ?x est_une ?c .\n  similar_to(?x, \"pluton\", 5)\n} order by score limit 3",
+        )
+        .unwrap();
+        assert_eq!(q.select, ["x", "c"]);
+        assert_eq!(
+            q.clauses,
+            [
+                Clause::Triple {
+                    subj: Term::Var("x".into()),
+                    rel: Term::Const("est_une".into()),
+                    obj: Term::Var("c".into()),
+                },
+                Clause::SimilarTo {
+                    var: "x".into(),
+                    text: "pluton".into(),
+                    k: 5,
+                },
+            ]
+        );
+        // SCORE trie par défaut du plus au moins similaire
+        assert_eq!(
+            q.order,
+            Some(OrderBy {
+                key: OrderKey::Score,
+                descending: true
+            })
+        );
+        assert_eq!(q.limit, Some(3));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_token() {
+        let err = |src: &str| parse_query(src).unwrap_err();
+
+        let e = err("SELECT ?x WHERE {\n  ?x orbite\n}");
+        assert_eq!((e.line, e.col), (3, 1));
+        assert!(e.message.starts_with("terme attendu"), "{}", e.message);
+
+        let e = err("SELECT WHERE { ?x a b }");
+        assert_eq!((e.line, e.col), (1, 8));
+
+        let e = err("SELECT ?y WHERE { ?x a b }");
+        assert!(e.message.contains("?y absente de WHERE"), "{}", e.message);
+
+        let e = err("SELECT * WHERE { ?x a b } LIMIT beaucoup");
+        assert_eq!((e.line, e.col), (1, 33));
+
+        let e = err("SELECT * WHERE { similar_to(?x, pluton, 5) }");
+        assert!(
+            e.message.starts_with("texte entre guillemets"),
+            "{}",
+            e.message
+        );
+
+        assert!(err("SELECT * WHERE { }")
+            .message
+            .contains("au moins un motif"));
+    }
+}
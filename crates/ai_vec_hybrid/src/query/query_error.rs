@@ -0,0 +1,10 @@
+/// Erreur de syntaxe d'une requête, avec sa position (1-indexée).
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("ligne {line}, colonne {col} : {message}")]
+pub struct QueryError {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+pub type QueryResult<T> = Result<T, QueryError>;
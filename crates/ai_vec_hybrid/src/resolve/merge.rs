@@ -0,0 +1,308 @@
+use crate::edge::Edge;
+use crate::graph::Graph;
+use crate::graph_op::GraphOp;
+use crate::resolve::merge_error::{MergeError, MergeResult};
+use crate::resolve::merge_record::MergeRecord;
+use crate::time::{now, Timestamp};
+
+impl Graph {
+    /// Nœud désigné par `name` : nom exact ou alias, sinon l'unique nœud
+    /// actif de même forme normalisée (`pluton` → `Pluton`).
+    pub fn resolve_name(&self, name: &str) -> Option<usize> {
+        if let Some(&id) = self.name2id.get(name) {
+            return Some(id);
+        }
+        let mut found = self
+            .names
+            .exact(name)
+            .iter()
+            .filter(|&&id| self.nodes[id].merged_into.is_none());
+        let &first = found.next()?;
+        found.next().is_none().then_some(first)
+    }
+
+    /// Noms redirigés vers le nœud `id` par des fusions, triés.
+    pub fn aliases(&self, id: usize) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .name2id
+            .iter()
+            .filter(|(name, &to)| to == id && **name != self.nodes[id].name)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Journal des fusions (annulées comprises), dans l'ordre.
+    pub fn merges(&self) -> &[MergeRecord] {
+        &self.merges
+    }
+
+    /// Fusionne `merged` dans `keep` : ses faits (courants et historiques)
+    /// sont réécrits vers `keep`, un fait déjà présent sur `keep` n'est pas
+    /// dupliqué, un fait entre les deux nœuds est abandonné (il deviendrait
+    /// une boucle), et son nom devient un alias de `keep`. Le nœud fusionné
+    /// reste dans `nodes` (ids stables) mais sort des recherches.
+    /// Renvoie le numéro de la fusion, à passer à `revert_merge`.
+    pub fn merge_nodes(&mut self, keep: &str, merged: &str) -> MergeResult<usize> {
+        self.merge_nodes_at(keep, merged, now())
+    }
+
+    pub(crate) fn merge_nodes_at(
+        &mut self,
+        keep_name: &str,
+        merged_name: &str,
+        at: Timestamp,
+    ) -> MergeResult<usize> {
+        let id_of = |name: &str| {
+            self.name2id
+                .get(name)
+                .copied()
+                .ok_or_else(|| MergeError::UnknownNode(name.to_string()))
+        };
+        let (keep, merged) = (id_of(keep_name)?, id_of(merged_name)?);
+        if keep == merged {
+            return Err(MergeError::SameNode(
+                keep_name.to_string(),
+                merged_name.to_string(),
+            ));
+        }
+        let fwd = |id: usize| if id == merged { keep } else { id };
+        let touches = |e: &Edge| e.from == merged || e.to == merged;
+
+        // un fait déjà présent sur `keep` (repéré par l'index, avant
+        // réécriture) ou reliant les deux nœuds n'est pas déplacé
+        let drop: Vec<bool> = self
+            .edges
+            .iter()
+            .map(|e| {
+                let (from, to) = (fwd(e.from), fwd(e.to));
+                touches(e)
+                    && (from == to
+                        || self
+                            .position_of(from, &e.rel, to)
+                            .is_some_and(|p| !self.edges[p].derived))
+            })
+            .collect();
+        // les arêtes dérivées sont recalculées après la fusion
+        let (mut moved, mut dropped) = (vec![], vec![]);
+        let edges = std::mem::take(&mut self.edges);
+        for (e, drop) in edges.into_iter().zip(drop) {
+            if e.derived {
+                continue;
+            }
+            if !touches(&e) {
+                self.edges.push(e);
+            } else if drop {
+                dropped.push(e);
+            } else {
+                self.edges.push(Edge {
+                    from: fwd(e.from),
+                    to: fwd(e.to),
+                    ..e.clone()
+                });
+                moved.push(e);
+            }
+        }
+        let mut history = vec![];
+        for (pos, e) in self.history.iter_mut().enumerate() {
+            if touches(e) {
+                history.push((pos, e.from, e.to));
+                e.from = fwd(e.from);
+                e.to = fwd(e.to);
+            }
+        }
+
+        let mut names: Vec<String> = self
+            .name2id
+            .iter()
+            .filter(|(_, &id)| id == merged)
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort_unstable();
+        for name in &names {
+            self.name2id.insert(name.clone(), keep);
+        }
+        self.nodes[merged].merged_into = Some(keep);
+        self.merges.push(MergeRecord {
+            keep,
+            merged,
+            at,
+            moved,
+            dropped,
+            history,
+            names,
+            reverted_at: None,
+        });
+        self.journal.push(GraphOp::MergeNodes {
+            keep: self.nodes[keep].name.clone(),
+            merged: self.nodes[merged].name.clone(),
+            at,
+        });
+        self.rederive();
+        Ok(self.merges.len() - 1)
+    }
+
+    /// Annule la fusion `merge` : le nœud fusionné retrouve son nom, ses
+    /// alias et les versions de faits qu'il avait au moment de la fusion.
+    /// Les faits ajoutés ou corrigés depuis, via l'un ou l'autre nom, restent
+    /// sur le nœud conservé. Une fusion ultérieure impliquant l'un des deux
+    /// nœuds doit être annulée d'abord.
+    pub fn revert_merge(&mut self, merge: usize) -> MergeResult<()> {
+        self.revert_merge_at(merge, now())
+    }
+
+    pub(crate) fn revert_merge_at(&mut self, merge: usize, at: Timestamp) -> MergeResult<()> {
+        let rec = self
+            .merges
+            .get(merge)
+            .ok_or(MergeError::UnknownMerge(merge))?
+            .clone();
+        if !rec.is_active() {
+            return Err(MergeError::AlreadyReverted(merge));
+        }
+        let involved = |id: usize| id == rec.keep || id == rec.merged;
+        if let Some(by) = (merge + 1..self.merges.len()).find(|&i| {
+            let m = &self.merges[i];
+            m.is_active() && (involved(m.keep) || involved(m.merged))
+        }) {
+            return Err(MergeError::Blocked { merge, by });
+        }
+        let fwd = |id: usize| if id == rec.merged { rec.keep } else { id };
+
+        self.edges.retain(|e| !e.derived);
+        for o in &rec.moved {
+            let (from, to) = (fwd(o.from), fwd(o.to));
+            let same = |e: &&mut Edge| {
+                e.from == from
+                    && e.to == to
+                    && e.rel == o.rel
+                    && e.valid_from == o.valid_from
+                    && e.valid_to == o.valid_to
+                    && e.recorded_at == o.recorded_at
+            };
+            // la version a pu passer dans l'historique depuis la fusion
+            for e in self
+                .edges
+                .iter_mut()
+                .chain(self.history.iter_mut())
+                .filter(same)
+            {
+                e.from = o.from;
+                e.to = o.to;
+            }
+        }
+        self.edges.extend(rec.dropped.iter().cloned());
+        for &(pos, from, to) in &rec.history {
+            self.history[pos].from = from;
+            self.history[pos].to = to;
+        }
+        for name in &rec.names {
+            self.name2id.insert(name.clone(), rec.merged);
+        }
+        self.nodes[rec.merged].merged_into = None;
+        self.merges[merge].reverted_at = Some(at);
+        self.journal.push(GraphOp::RevertMerge { merge, at });
+        self.rederive();
+        Ok(())
+    }
+
+    /// Rétablit l'état des fusions après rechargement d'un snapshot (les
+    /// faits, eux, sont déjà réécrits).
+    pub(crate) fn restore_merges(&mut self, merges: Vec<MergeRecord>) {
+        for rec in &merges {
+            if rec.is_active() {
+                for name in &rec.names {
+                    self.name2id.insert(name.clone(), rec.keep);
+                }
+                self.nodes[rec.merged].merged_into = Some(rec.keep);
+            }
+        }
+        self.merges = merges;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn facts(g: &Graph) -> BTreeSet<(String, String, String)> {
+        g.edges
+            .iter()
+            .map(|e| {
+                let name = |id: usize| g.nodes[id].name.clone();
+                (name(e.from), e.rel.clone(), name(e.to))
+            })
+            .collect()
+    }
+
+    fn graph() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Pluton", "est_une", "planète_naine");
+        g.add_edge("134340", "orbite", "Soleil");
+        g.add_edge("134340", "découverte_par", "Tombaugh");
+        g.add_edge("Charon", "orbite", "134340");
+        g
+    }
+
+    #[test]
+    fn merge_then_revert_restores_the_edges() {
+        let mut g = graph();
+        let before = facts(&g);
+
+        let m = g.merge_nodes("Pluton", "134340").unwrap();
+        let pluton = g.name2id["Pluton"];
+        assert_eq!(g.resolve_name("134340"), Some(pluton));
+        assert_eq!(g.aliases(pluton), ["134340"]);
+        // `134340 orbite Soleil` était déjà sur Pluton : abandonné, pas dupliqué
+        assert_eq!(g.merges()[m].dropped.len(), 1);
+        assert_eq!(g.outgoing("Pluton").len(), 3);
+        assert_eq!(g.incoming("Pluton").len(), 1);
+
+        g.revert_merge(m).unwrap();
+        assert_eq!(facts(&g), before);
+        assert_ne!(g.resolve_name("134340"), Some(pluton));
+        assert_eq!(g.revert_merge(m), Err(MergeError::AlreadyReverted(m)));
+    }
+
+    #[test]
+    fn edges_between_merged_nodes_do_not_become_loops() {
+        let mut g = graph();
+        g.add_edge("Pluton", "même_objet_que", "134340");
+        let before = facts(&g);
+
+        let m = g.merge_nodes("Pluton", "134340").unwrap();
+        assert!(g.edges.iter().all(|e| e.from != e.to));
+        assert!(g.merges()[m]
+            .dropped
+            .iter()
+            .any(|e| e.rel == "même_objet_que"));
+
+        g.revert_merge(m).unwrap();
+        assert_eq!(facts(&g), before);
+    }
+
+    #[test]
+    fn overlapping_merges_revert_in_reverse_order() {
+        let mut g = graph();
+        assert!(matches!(
+            g.merge_nodes("Pluton", "Pluton"),
+            Err(MergeError::SameNode(..))
+        ));
+        let first = g.merge_nodes("Pluton", "134340").unwrap();
+        let second = g.merge_nodes("Pluton", "Charon").unwrap();
+        assert_eq!(
+            g.revert_merge(first),
+            Err(MergeError::Blocked {
+                merge: first,
+                by: second
+            })
+        );
+        g.revert_merge(second).unwrap();
+        g.revert_merge(first).unwrap();
+        assert_eq!(facts(&g), facts(&graph()));
+    }
+}
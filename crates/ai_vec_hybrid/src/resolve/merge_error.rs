@@ -0,0 +1,16 @@
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum MergeError {
+    #[error("nœud inconnu '{0}'")]
+    UnknownNode(String),
+    #[error("'{0}' et '{1}' désignent déjà le même nœud")]
+    SameNode(String, String),
+    #[error("fusion #{0} inconnue")]
+    UnknownMerge(usize),
+    #[error("fusion #{0} déjà annulée")]
+    AlreadyReverted(usize),
+    /// Les fusions s'annulent dans l'ordre inverse quand elles se chevauchent.
+    #[error("annuler d'abord la fusion #{by}, postérieure à #{merge}")]
+    Blocked { merge: usize, by: usize },
+}
+
+pub type MergeResult<T> = Result<T, MergeError>;
@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::embedding::cosine;
+use crate::graph::Graph;
+use crate::resolve::normalize::{name_similarity, normalize_name};
+
+/// Réglages de la détection de doublons.
+#[derive(Clone, Debug)]
+pub struct ResolveParams {
+    /// Score minimal pour proposer une fusion.
+    pub threshold: f32,
+    /// Poids de la similarité d'embedding (le reste va à la similarité de nom).
+    pub emb_weight: f32,
+    /// Voisins k-NN examinés par nœud.
+    pub candidates: usize,
+}
+
+impl Default for ResolveParams {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            emb_weight: 0.5,
+            candidates: 10,
+        }
+    }
+}
+
+/// Fusion suggérée de `merge` dans `keep`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MergeProposal {
+    pub keep: String,
+    pub merge: String,
+    pub name_sim: f32,
+    pub emb_sim: f32,
+    pub score: f32,
+}
+
+impl fmt::Display for MergeProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} <= {}  score={:.3} (nom={:.3}, emb={:.3})",
+            self.keep, self.merge, self.score, self.name_sim, self.emb_sim
+        )
+    }
+}
+
+impl Graph {
+    /// Paires de nœuds actifs qui désignent probablement la même entité.
+    ///
+    /// Candidats : voisins k-NN de chaque nœud et nœuds de même forme
+    /// normalisée. Score = `emb_weight * cosinus + (1 - emb_weight) *
+    /// similarité de nom` ; une forme normalisée identique vaut 1. Le nœud
+    /// gardé est le plus connecté (à égalité, le plus ancien).
+    pub fn propose_merges(&self, params: &ResolveParams) -> Vec<MergeProposal> {
+        let active = || self.nodes.iter().filter(|n| n.merged_into.is_none());
+        let norms: HashMap<usize, String> =
+            active().map(|n| (n.id, normalize_name(&n.name))).collect();
+
+        let mut pairs: HashSet<(usize, usize)> = HashSet::new();
+        let mut by_norm: HashMap<&str, Vec<usize>> = HashMap::new();
+        for n in active() {
+            by_norm.entry(norms[&n.id].as_str()).or_default().push(n.id);
+            for (id, _) in self.k_nn(&n.emb, params.candidates + 1) {
+                if id != n.id {
+                    pairs.insert((n.id.min(id), n.id.max(id)));
+                }
+            }
+        }
+        for ids in by_norm.values() {
+            for (i, &a) in ids.iter().enumerate() {
+                for &b in &ids[i + 1..] {
+                    pairs.insert((a.min(b), a.max(b)));
+                }
+            }
+        }
+
+        let mut degree = vec![0usize; self.nodes.len()];
+        for e in self.edges.iter().filter(|e| !e.derived) {
+            degree[e.from] += 1;
+            degree[e.to] += 1;
+        }
+        let mut out: Vec<MergeProposal> = pairs
+            .into_iter()
+            .filter_map(|(a, b)| {
+                let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+                let name_sim = name_similarity(&na.name, &nb.name);
+                let emb_sim = cosine(&na.emb, &nb.emb);
+                let score = if norms[&a] == norms[&b] {
+                    1.0
+                } else {
+                    params.emb_weight * emb_sim + (1.0 - params.emb_weight) * name_sim
+                };
+                if score < params.threshold {
+                    return None;
+                }
+                let (keep, merge) = if degree[b] > degree[a] {
+                    (nb, na)
+                } else {
+                    (na, nb)
+                };
+                Some(MergeProposal {
+                    keep: keep.name.clone(),
+                    merge: merge.name.clone(),
+                    name_sim,
+                    emb_sim,
+                    score,
+                })
+            })
+            .collect();
+        out.sort_by(|x, y| {
+            y.score
+                .total_cmp(&x.score)
+                .then_with(|| (&x.keep, &x.merge).cmp(&(&y.keep, &y.merge)))
+        });
+        out
+    }
+
+    /// Applique des propositions dans l'ordre ; celles devenues sans objet
+    /// (nœuds déjà fusionnés ensemble) sont ignorées. Renvoie les numéros
+    /// des fusions effectuées.
+    pub fn apply_merges(&mut self, proposals: &[MergeProposal]) -> Vec<usize> {
+        proposals
+            .iter()
+            .filter_map(|p| self.merge_nodes(&p.keep, &p.merge).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::edge::Edge;
+use crate::time::Timestamp;
+
+/// Entrée du journal des fusions : tout ce qu'il faut pour annuler la fusion
+/// de `merged` dans `keep`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MergeRecord {
+    pub keep: usize,
+    pub merged: usize,
+    pub at: Timestamp,
+    /// Faits courants de `merged` réécrits vers `keep` (état avant fusion).
+    pub moved: Vec<Edge>,
+    /// Faits courants abandonnés : déjà présents sur `keep`, ou reliant
+    /// `keep` et `merged`.
+    pub dropped: Vec<Edge>,
+    /// Versions historiques réécrites : (position dans `Graph::history`,
+    /// `from`, `to` d'origine).
+    pub history: Vec<(usize, usize, usize)>,
+    /// Noms redirigés vers `keep` : celui de `merged` et ses propres alias.
+    pub names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverted_at: Option<Timestamp>,
+}
+
+impl MergeRecord {
+    pub fn is_active(&self) -> bool {
+        self.reverted_at.is_none()
+    }
+}
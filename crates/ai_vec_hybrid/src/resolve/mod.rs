@@ -0,0 +1,10 @@
+pub mod merge;
+pub mod merge_error;
+pub mod merge_proposal;
+pub mod merge_record;
+pub mod normalize;
+
+pub use merge_error::{MergeError, MergeResult};
+pub use merge_proposal::{MergeProposal, ResolveParams};
+pub use merge_record::MergeRecord;
+pub use normalize::{name_similarity, normalize_name};
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use crate::edge::Edge;
+use crate::graph::Graph;
+use crate::time::{now, Timestamp};
+
+/// Plusieurs objets courants pour un sujet et une relation fonctionnelle.
+#[derive(Debug)]
+pub struct Conflict<'a> {
+    pub subj: usize,
+    pub rel: &'a str,
+    /// Du plus sûr au moins sûr ; le premier l'emporte.
+    pub edges: Vec<&'a Edge>,
+}
+
+impl Conflict<'_> {
+    pub fn winner(&self) -> &Edge {
+        self.edges[0]
+    }
+}
+
+/// Ordre de préférence : confiance, puis date d'enregistrement, puis rang
+/// d'insertion (le plus récent gagne à égalité).
+fn preferred(a: (usize, &Edge), b: (usize, &Edge)) -> std::cmp::Ordering {
+    a.1.confidence()
+        .total_cmp(&b.1.confidence())
+        .then(a.1.recorded_at.cmp(&b.1.recorded_at))
+        .then(a.0.cmp(&b.0))
+}
+
+impl Graph {
+    /// Faits de base courants en conflit sur une relation déclarée
+    /// `functional` (normalement aucun : ils sont résolus à l'insertion,
+    /// sauf s'ils précèdent la règle).
+    pub fn conflicts(&self) -> Vec<Conflict<'_>> {
+        let mut groups: BTreeMap<(usize, &str), Vec<(usize, &Edge)>> = BTreeMap::new();
+        // rang dans la liste du sujet : l'ordre d'insertion, comme pour
+        // `resolve_functional` (les positions dans `edges` ne le suivent pas)
+        for subj in 0..self.nodes.len() {
+            for (i, e) in self.out_edges(subj).enumerate() {
+                if !e.derived && self.rules.is_functional(&e.rel) {
+                    groups.entry((subj, &e.rel)).or_default().push((i, e));
+                }
+            }
+        }
+        groups
+            .into_iter()
+            .filter(|(_, edges)| edges.len() > 1)
+            .map(|((subj, rel), mut edges)| {
+                edges.sort_by(|&a, &b| preferred(b, a));
+                Conflict {
+                    subj,
+                    rel,
+                    edges: edges.into_iter().map(|(_, e)| e).collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Fait retenu pour `subj rel ?` : le plus sûr des faits courants.
+    pub fn best_fact(&self, subj: &str, rel: &str) -> Option<&Edge> {
+        let &id = self.name2id.get(subj)?;
+        self.out_edges(id)
+            .enumerate()
+            .filter(|(_, e)| e.rel == rel)
+            .max_by(|&a, &b| preferred(a, b))
+            .map(|(_, e)| e)
+    }
+
+    /// Résout tous les conflits existants ; renvoie le nombre de faits retirés.
+    pub fn resolve_conflicts(&mut self) -> usize {
+        let keys: Vec<(usize, String)> = self
+            .conflicts()
+            .iter()
+            .map(|c| (c.subj, c.rel.to_string()))
+            .collect();
+        let at = now();
+        keys.iter()
+            .map(|(subj, rel)| self.resolve_functional(*subj, rel, at))
+            .sum()
+    }
+
+    /// Ne garde que le fait préféré pour `subj rel ?` ; les autres sont
+    /// retirés (ils restent dans l'historique avec leur provenance).
+    pub(crate) fn resolve_functional(&mut self, subj: usize, rel: &str, at: Timestamp) -> usize {
+        let candidates = || {
+            self.out_edges(subj)
+                .enumerate()
+                .filter(|(_, e)| !e.derived && e.rel == rel)
+        };
+        let Some((keep, _)) = candidates().max_by(|&a, &b| preferred(a, b)) else {
+            return 0;
+        };
+        let losers: Vec<String> = candidates()
+            .filter(|(i, _)| *i != keep)
+            .map(|(_, e)| self.nodes[e.to].name.clone())
+            .collect();
+        let subj = self.nodes[subj].name.clone();
+        for obj in &losers {
+            self.retract_edge_at(&subj, rel, obj, at);
+        }
+        losers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fact::Fact;
+    use crate::provenance::Provenance;
+    use crate::rules::{Rule, RuleSet};
+
+    fn fact(obj: &str, confidence: f32, source: &str) -> Fact {
+        Fact::new("Pluton", "classe", obj)
+            .with_provenance(Provenance::new(confidence, Some(source.into())))
+    }
+
+    fn classes(g: &Graph) -> Vec<&str> {
+        g.outgoing("Pluton")
+            .into_iter()
+            .map(|e| g.nodes[e.to].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn the_most_confident_fact_wins_on_insertion() {
+        let mut g = Graph::new();
+        g.set_rules(RuleSet::new(vec![Rule::Functional {
+            rel: "classe".into(),
+        }]));
+        g.add_fact(&fact("planète", 0.6, "UAI 1930"));
+        g.add_fact(&fact("planète_naine", 0.9, "UAI 2006"));
+        assert_eq!(classes(&g), ["planète_naine"]);
+
+        // moins sûr : enregistré puis aussitôt écarté, avec sa provenance
+        g.add_fact(&fact("astéroïde", 0.3, "rumeur"));
+        assert_eq!(classes(&g), ["planète_naine"]);
+        let retracted: Vec<(&str, f32, Option<&str>)> = g
+            .history
+            .iter()
+            .filter(|e| e.retracted_at.is_some())
+            .map(|e| {
+                let name = g.nodes[e.to].name.as_str();
+                (name, e.confidence(), e.provenance.source.as_deref())
+            })
+            .collect();
+        assert_eq!(
+            retracted,
+            [
+                ("planète", 0.6, Some("UAI 1930")),
+                ("astéroïde", 0.3, Some("rumeur"))
+            ]
+        );
+        assert_eq!(g.best_fact("Pluton", "classe").unwrap().confidence(), 0.9);
+    }
+
+    #[test]
+    fn existing_conflicts_are_ordered_then_resolved() {
+        let mut g = Graph::new();
+        g.add_fact(&fact("planète", 0.6, "UAI 1930"));
+        g.add_fact(&fact("planète_naine", 0.9, "UAI 2006"));
+        // à confiance égale, le plus récent l'emporte
+        g.add_fact(&fact("plutoïde", 0.9, "UAI 2008"));
+        assert!(g.conflicts().is_empty());
+
+        // règle posée après coup : les conflits sont signalés, pas résolus
+        g.set_rules(RuleSet::new(vec![Rule::Functional {
+            rel: "classe".into(),
+        }]));
+        let conflicts = g.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        let order: Vec<f32> = conflicts[0].edges.iter().map(|e| e.confidence()).collect();
+        assert_eq!(order, [0.9, 0.9, 0.6]);
+        assert_eq!(g.nodes[conflicts[0].winner().to].name, "plutoïde");
+
+        assert_eq!(g.resolve_conflicts(), 2);
+        assert_eq!(classes(&g), ["plutoïde"]);
+        assert!(g.conflicts().is_empty());
+    }
+}
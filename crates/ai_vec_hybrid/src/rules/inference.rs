@@ -0,0 +1,325 @@
+use std::collections::HashSet;
+
+use crate::edge::Edge;
+use crate::graph::Graph;
+use crate::provenance::Provenance;
+use crate::rules::rule::Rule;
+use crate::rules::rule_set::RuleSet;
+use crate::time::{now, Timestamp};
+
+impl Graph {
+    /// Installe les règles et recalcule toutes les arêtes dérivées.
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+        self.rederive();
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Arêtes dérivées actuellement présentes.
+    pub fn derived_edges(&self) -> Vec<&Edge> {
+        self.edges.iter().filter(|e| e.derived).collect()
+    }
+
+    /// Oublie toutes les arêtes dérivées puis les recalcule à partir des
+    /// faits de base (changement de règles, fusion de nœuds).
+    pub(crate) fn rederive(&mut self) {
+        self.edges.retain(|e| !e.derived);
+        self.reindex();
+        self.infer((0..self.edges.len()).collect());
+    }
+
+    /// Chaînage avant semi-naïf jusqu'au point fixe : chaque tour ne joint
+    /// que les arêtes apparues au tour précédent (`delta`, des positions
+    /// dans `edges`) avec les arêtes courantes. Les nouvelles arêtes sont
+    /// marquées `derived` et ne sont pas journalisées (elles sont
+    /// recalculées au rechargement).
+    pub(crate) fn infer(&mut self, mut delta: Vec<usize>) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let at = now();
+        while !delta.is_empty() {
+            let new: Vec<Derivation> = delta
+                .iter()
+                .flat_map(|&p| self.consequences(&self.edges[p]))
+                .filter(|d| self.position_of(d.from, &d.rel, d.to).is_none())
+                .collect();
+            delta = vec![];
+            for d in new {
+                // une même conclusion peut sortir plusieurs fois d'un tour
+                if self.position_of(d.from, &d.rel, d.to).is_some() {
+                    continue;
+                }
+                delta.push(self.edges.len());
+                self.push_edge(d.into_edge(at));
+            }
+        }
+    }
+
+    /// Retire l'arête courante `pos` avec tout ce qui en a été dérivé,
+    /// directement ou non (sur-suppression, première moitié de DRed).
+    /// Renvoie l'arête retirée et les dérivations suspendues, à passer à
+    /// `restore_supported` une fois le graphe à jour.
+    pub(crate) fn take_with_derivations(&mut self, pos: usize) -> (Edge, Vec<Edge>) {
+        let mut marked: HashSet<usize> = HashSet::from([pos]);
+        let mut queue = vec![pos];
+        while let Some(p) = queue.pop() {
+            for d in self.consequences(&self.edges[p]) {
+                if let Some(q) = self.position_of(d.from, &d.rel, d.to) {
+                    if self.edges[q].derived && marked.insert(q) {
+                        queue.push(q);
+                    }
+                }
+            }
+        }
+        // du plus grand au plus petit : l'arête qui vient combler chaque trou
+        // n'est jamais une arête marquée encore à retirer
+        let mut marked: Vec<usize> = marked.into_iter().collect();
+        marked.sort_unstable_by(|a, b| b.cmp(a));
+        let mut removed = None;
+        let mut suspended = vec![];
+        for p in marked {
+            let e = self.swap_remove_edge(p);
+            if p == pos {
+                removed = Some(e);
+            } else {
+                suspended.push(e);
+            }
+        }
+        suspended.reverse();
+        (removed.expect("position courante"), suspended)
+    }
+
+    /// Seconde moitié de DRed : rétablit les dérivations suspendues qui ont
+    /// encore un support, puis propage à partir d'elles.
+    pub(crate) fn restore_supported(&mut self, suspended: Vec<Edge>) {
+        let mut delta = vec![];
+        for e in suspended {
+            if self.position_of(e.from, &e.rel, e.to).is_some() {
+                continue;
+            }
+            if let Some(confidence) = self.support(&e) {
+                delta.push(self.edges.len());
+                self.push_edge(Edge {
+                    provenance: Provenance::new(confidence, None),
+                    ..e
+                });
+            }
+        }
+        self.infer(delta);
+    }
+
+    /// Conclusions des règles dont `e` est l'une des prémisses, l'autre
+    /// étant une arête courante.
+    fn consequences(&self, e: &Edge) -> Vec<Derivation> {
+        let mut out = vec![];
+        // la confiance d'une dérivation est celle de sa prémisse la plus faible
+        let mut push = |from: usize, rel: &str, to: usize, confidence: f32| {
+            out.push(Derivation {
+                from,
+                rel: rel.to_string(),
+                to,
+                confidence,
+            });
+        };
+        let both = |other: &Edge| e.confidence().min(other.confidence());
+        for rule in &self.rules.rules {
+            match rule {
+                Rule::Transitive { rel } if &e.rel == rel => {
+                    for b in self.out_edges(e.to).filter(|x| &x.rel == rel) {
+                        if e.from != b.to {
+                            push(e.from, rel, b.to, both(b));
+                        }
+                    }
+                    for a in self.in_edges(e.from).filter(|x| &x.rel == rel) {
+                        if a.from != e.to {
+                            push(a.from, rel, e.to, both(a));
+                        }
+                    }
+                }
+                Rule::Inverse { rel, inverse } => {
+                    if &e.rel == rel {
+                        push(e.to, inverse, e.from, e.confidence());
+                    }
+                    if inverse != rel && &e.rel == inverse {
+                        push(e.to, rel, e.from, e.confidence());
+                    }
+                }
+                Rule::Subclass {
+                    instance_rel,
+                    subclass_rel,
+                } => {
+                    if &e.rel == instance_rel {
+                        for b in self.out_edges(e.to).filter(|x| &x.rel == subclass_rel) {
+                            push(e.from, instance_rel, b.to, both(b));
+                        }
+                    }
+                    if &e.rel == subclass_rel {
+                        for a in self.in_edges(e.from).filter(|x| &x.rel == instance_rel) {
+                            push(a.from, instance_rel, e.to, both(a));
+                        }
+                    }
+                }
+                Rule::Transitive { .. } | Rule::Functional { .. } => {}
+            }
+        }
+        out
+    }
+
+    /// Confiance d'une dérivation de `e` en un pas à partir des arêtes
+    /// courantes, si les règles en fournissent une.
+    fn support(&self, e: &Edge) -> Option<f32> {
+        let edge = |from: usize, rel: &str, to: usize| {
+            self.position_of(from, rel, to).map(|p| &self.edges[p])
+        };
+        self.rules.rules.iter().find_map(|rule| match rule {
+            Rule::Transitive { rel } if &e.rel == rel && e.from != e.to => self
+                .out_edges(e.from)
+                .filter(|a| &a.rel == rel)
+                .find_map(|a| edge(a.to, rel, e.to).map(|b| a.confidence().min(b.confidence()))),
+            Rule::Inverse { rel, inverse } if &e.rel == inverse => {
+                edge(e.to, rel, e.from).map(Edge::confidence)
+            }
+            Rule::Inverse { rel, inverse } if &e.rel == rel => {
+                edge(e.to, inverse, e.from).map(Edge::confidence)
+            }
+            Rule::Subclass {
+                instance_rel,
+                subclass_rel,
+            } if &e.rel == instance_rel => self
+                .out_edges(e.from)
+                .filter(|a| &a.rel == instance_rel)
+                .find_map(|a| {
+                    edge(a.to, subclass_rel, e.to).map(|b| a.confidence().min(b.confidence()))
+                }),
+            _ => None,
+        })
+    }
+}
+
+/// Conclusion d'une règle, pas encore insérée.
+struct Derivation {
+    from: usize,
+    rel: String,
+    to: usize,
+    confidence: f32,
+}
+
+impl Derivation {
+    fn into_edge(self, at: Timestamp) -> Edge {
+        Edge {
+            from: self.from,
+            rel: self.rel,
+            to: self.to,
+            derived: true,
+            valid_from: None,
+            valid_to: None,
+            recorded_at: at,
+            retracted_at: None,
+            provenance: Provenance::new(self.confidence, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn rules() -> RuleSet {
+        RuleSet::new(vec![
+            Rule::Transitive {
+                rel: "partie_de".into(),
+            },
+            Rule::Inverse {
+                rel: "orbite".into(),
+                inverse: "orbité_par".into(),
+            },
+            Rule::Subclass {
+                instance_rel: "est_une".into(),
+                subclass_rel: "sous_classe_de".into(),
+            },
+        ])
+    }
+
+    fn derived(g: &Graph) -> BTreeSet<(String, String, String)> {
+        g.derived_edges()
+            .iter()
+            .map(|e| {
+                let name = |id: usize| g.nodes[id].name.clone();
+                (name(e.from), e.rel.clone(), name(e.to))
+            })
+            .collect()
+    }
+
+    fn has(g: &Graph, from: &str, rel: &str, to: &str) -> bool {
+        g.outgoing(from)
+            .iter()
+            .any(|e| e.rel == rel && g.nodes[e.to].name == to)
+    }
+
+    #[test]
+    fn incremental_inference_matches_full_recomputation() {
+        let mut g = Graph::new();
+        g.set_rules(rules());
+        // chaîne ajoutée dans le désordre : la fermeture doit être complète
+        for i in (0..20).rev().step_by(2).chain((0..20).step_by(2)) {
+            g.add_edge(&format!("n{i}"), "partie_de", &format!("n{}", i + 1));
+        }
+        g.add_edge("Pluton", "est_une", "planète_naine");
+        g.add_edge("planète_naine", "sous_classe_de", "corps_céleste");
+        g.add_edge("Pluton", "orbite", "Soleil");
+
+        assert!(has(&g, "n0", "partie_de", "n20"));
+        assert!(has(&g, "Pluton", "est_une", "corps_céleste"));
+        assert!(has(&g, "Soleil", "orbité_par", "Pluton"));
+        // 21 nœuds en chaîne : 210 paires, dont 20 faits de base
+        let incremental = derived(&g);
+        assert_eq!(
+            incremental
+                .iter()
+                .filter(|(_, rel, _)| rel == "partie_de")
+                .count(),
+            190
+        );
+
+        g.set_rules(rules());
+        assert_eq!(derived(&g), incremental);
+    }
+
+    #[test]
+    fn retracting_a_premise_removes_its_derivations() {
+        let mut g = Graph::new();
+        g.set_rules(rules());
+        g.add_edge("a", "partie_de", "b");
+        g.add_edge("b", "partie_de", "c");
+        g.add_edge("c", "partie_de", "d");
+        g.add_edge("Pluton", "orbite", "Soleil");
+        assert!(has(&g, "a", "partie_de", "d"));
+
+        g.remove_edge_exact("b", "partie_de", "c");
+        assert!(!has(&g, "a", "partie_de", "c"));
+        assert!(!has(&g, "a", "partie_de", "d"));
+        assert!(!has(&g, "b", "partie_de", "d"));
+        // les dérivations sans lien avec le fait retiré restent
+        assert!(has(&g, "Soleil", "orbité_par", "Pluton"));
+
+        // une dérivation qui garde un autre support survit au retrait
+        g.add_edge("a", "partie_de", "x");
+        g.add_edge("x", "partie_de", "d");
+        g.add_edge("a", "partie_de", "c");
+        g.remove_edge_exact("a", "partie_de", "c");
+        assert!(has(&g, "a", "partie_de", "d"));
+
+        g.remove_edge_exact("Pluton", "orbite", "Soleil");
+        assert!(!has(&g, "Soleil", "orbité_par", "Pluton"));
+
+        let after_retractions = derived(&g);
+        g.set_rules(rules());
+        assert_eq!(derived(&g), after_retractions);
+    }
+}
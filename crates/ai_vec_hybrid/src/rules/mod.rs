@@ -0,0 +1,8 @@
+pub mod conflict;
+pub mod inference;
+pub mod rule;
+pub mod rule_set;
+
+pub use conflict::Conflict;
+pub use rule::Rule;
+pub use rule_set::RuleSet;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Règle d'inférence déclarée dans le fichier de règles.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rule {
+    /// `A rel B`, `B rel C` ⇒ `A rel C`.
+    Transitive { rel: String },
+    /// `A rel B` ⇔ `B inverse A`.
+    Inverse { rel: String, inverse: String },
+    /// `X instance_rel C`, `C subclass_rel D` ⇒ `X instance_rel D`.
+    Subclass {
+        instance_rel: String,
+        subclass_rel: String,
+    },
+    /// Au plus un objet courant par sujet : en cas de conflit, le fait le
+    /// plus sûr l'emporte (à confiance égale, le plus récent).
+    Functional { rel: String },
+}
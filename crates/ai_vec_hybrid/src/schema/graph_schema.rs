@@ -0,0 +1,58 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::relation_schema::RelationSchema;
+
+fn default_instance_rel() -> String {
+    "est_une".to_string()
+}
+
+fn default_subclass_rel() -> String {
+    "super-classe".to_string()
+}
+
+/// Schéma déclaratif des relations, chargé depuis un fichier JSON.
+///
+/// Le type d'un nœud est donné par ses faits `instance_rel` et remonte les
+/// faits `subclass_rel` : avec `Pluton est_une planète` et
+/// `planète super-classe corps_céleste`, Pluton est un `corps_céleste`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphSchema {
+    #[serde(default = "default_instance_rel")]
+    pub instance_rel: String,
+    #[serde(default = "default_subclass_rel")]
+    pub subclass_rel: String,
+    pub relations: Vec<RelationSchema>,
+}
+
+impl Default for GraphSchema {
+    fn default() -> Self {
+        Self {
+            instance_rel: default_instance_rel(),
+            subclass_rel: default_subclass_rel(),
+            relations: vec![],
+        }
+    }
+}
+
+impl GraphSchema {
+    pub fn new(relations: Vec<RelationSchema>) -> Self {
+        Self {
+            relations,
+            ..Self::default()
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Contraintes de `rel`, s'il y en a.
+    pub fn relation(&self, rel: &str) -> Option<&RelationSchema> {
+        self.relations.iter().find(|r| r.rel == rel)
+    }
+}
@@ -0,0 +1,12 @@
+pub mod graph_schema;
+pub mod relation_schema;
+pub mod schema_error;
+pub mod validator;
+pub mod violation;
+pub mod violation_report;
+
+pub use graph_schema::GraphSchema;
+pub use relation_schema::RelationSchema;
+pub use schema_error::{SchemaError, SchemaResult};
+pub use violation::{Side, Violation};
+pub use violation_report::ViolationReport;
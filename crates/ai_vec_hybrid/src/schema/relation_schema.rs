@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// Contraintes déclarées pour une relation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelationSchema {
+    pub rel: String,
+    /// Classe que doit avoir le sujet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// Classe que doit avoir l'objet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+    /// Nombre maximal d'objets par sujet (1 : relation fonctionnelle).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_objects: Option<usize>,
+    /// Nombre maximal de sujets par objet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_subjects: Option<usize>,
+    /// La relation ne doit pas former de cycle (`super-classe`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub acyclic: bool,
+}
@@ -0,0 +1,10 @@
+use crate::schema::violation_report::ViolationReport;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Le fait n'a pas été ajouté ; le rapport dit pourquoi.
+    #[error("fait rejeté par le schéma, {0}")]
+    Rejected(ViolationReport),
+}
+
+pub type SchemaResult<T> = Result<T, SchemaError>;
@@ -0,0 +1,592 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use crate::edge::Edge;
+use crate::fact::Fact;
+use crate::graph::Graph;
+use crate::schema::graph_schema::GraphSchema;
+use crate::schema::schema_error::{SchemaError, SchemaResult};
+use crate::schema::violation::{Side, Violation};
+use crate::schema::violation_report::ViolationReport;
+use crate::time::{now, Timestamp};
+
+/// Fait de base sans fin de validité déclarée. Une version bornée (fermée
+/// par `update_fact` mais encore valide) cohabite avec celle qui lui
+/// succède : elle ne compte pas dans les cardinalités.
+fn is_open(e: &Edge) -> bool {
+    !e.derived && e.valid_to.is_none()
+}
+
+/// Fait remplacé par une correction, ignoré pendant la validation.
+type Replaced<'a> = Option<(&'a str, &'a str)>;
+
+impl Graph {
+    /// Installe un schéma et valide tout le graphe. Les faits en infraction
+    /// restent en place : le rapport dit lesquels corriger.
+    pub fn set_schema(&mut self, schema: GraphSchema) -> ViolationReport {
+        self.schema = Some(schema);
+        self.violations.clear();
+        self.check_schema()
+    }
+
+    pub fn clear_schema(&mut self) {
+        self.schema = None;
+        self.violations.clear();
+    }
+
+    pub fn schema(&self) -> Option<&GraphSchema> {
+        self.schema.as_ref()
+    }
+
+    /// Classes d'un nœud (faits `instance_rel` puis ancêtres par
+    /// `subclass_rel`), triées. Vide sans schéma.
+    pub fn types_of(&self, name: &str) -> Vec<String> {
+        let (Some(schema), Some(&id)) = (&self.schema, self.name2id.get(name)) else {
+            return vec![];
+        };
+        let mut types: Vec<String> = self
+            .type_ids(schema, id)
+            .into_iter()
+            .map(|c| self.nodes[c].name.clone())
+            .collect();
+        types.sort();
+        types
+    }
+
+    fn type_ids(&self, schema: &GraphSchema, id: usize) -> BTreeSet<usize> {
+        let mut types = BTreeSet::new();
+        let mut queue: VecDeque<usize> = self
+            .out_edges(id)
+            .filter(|e| e.rel == schema.instance_rel)
+            .map(|e| e.to)
+            .collect();
+        while let Some(c) = queue.pop_front() {
+            if types.insert(c) {
+                queue.extend(
+                    self.out_edges(c)
+                        .filter(|e| e.rel == schema.subclass_rel)
+                        .map(|e| e.to),
+                );
+            }
+        }
+        types
+    }
+
+    /// Domaine ou image : `None` si `name` a la classe `expected`, sinon
+    /// ses classes connues.
+    fn missing_class(
+        &self,
+        schema: &GraphSchema,
+        name: &str,
+        expected: &str,
+    ) -> Option<Vec<String>> {
+        let has = match (self.name2id.get(name), self.name2id.get(expected)) {
+            (Some(&id), Some(&class)) => self.type_ids(schema, id).contains(&class),
+            _ => false,
+        };
+        (!has).then(|| self.types_of(name))
+    }
+
+    /// Validation complète : domaines et images des faits de base courants,
+    /// cardinalités, cycles des relations acycliques.
+    pub fn check_schema(&self) -> ViolationReport {
+        let Some(schema) = &self.schema else {
+            return ViolationReport::default();
+        };
+        let mut report = ViolationReport::default();
+        for rs in &schema.relations {
+            let edges: Vec<&Edge> = self
+                .edges_with_rel(&rs.rel)
+                .into_iter()
+                .filter(|e| !e.derived)
+                .collect();
+            report.checked += edges.len();
+            for e in &edges {
+                let (subj, obj) = (&self.nodes[e.from].name, &self.nodes[e.to].name);
+                if let Some(expected) = &rs.domain {
+                    if let Some(types) = self.missing_class(schema, subj, expected) {
+                        report.violations.push(Violation::Domain {
+                            subj: subj.clone(),
+                            rel: rs.rel.clone(),
+                            obj: obj.clone(),
+                            expected: expected.clone(),
+                            types,
+                        });
+                    }
+                }
+                if let Some(expected) = &rs.range {
+                    if let Some(types) = self.missing_class(schema, obj, expected) {
+                        report.violations.push(Violation::Range {
+                            subj: subj.clone(),
+                            rel: rs.rel.clone(),
+                            obj: obj.clone(),
+                            expected: expected.clone(),
+                            types,
+                        });
+                    }
+                }
+            }
+            for (max, side) in [
+                (rs.max_objects, Side::Objects),
+                (rs.max_subjects, Side::Subjects),
+            ] {
+                let Some(max) = max else { continue };
+                let mut groups: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+                for e in edges.iter().filter(|e| is_open(e)) {
+                    let (from, to) = (
+                        self.nodes[e.from].name.as_str(),
+                        self.nodes[e.to].name.as_str(),
+                    );
+                    let (node, value) = match side {
+                        Side::Objects => (from, to),
+                        Side::Subjects => (to, from),
+                    };
+                    groups.entry(node).or_default().insert(value);
+                }
+                for (node, values) in groups {
+                    if values.len() > max {
+                        report.violations.push(Violation::Cardinality {
+                            node: node.to_string(),
+                            rel: rs.rel.clone(),
+                            side,
+                            max,
+                            values: values.into_iter().map(String::from).collect(),
+                        });
+                    }
+                }
+            }
+            if rs.acyclic {
+                for path in self.cycles(&edges) {
+                    report.violations.push(Violation::Cycle {
+                        rel: rs.rel.clone(),
+                        path,
+                    });
+                }
+            }
+        }
+        report
+    }
+
+    /// Un cycle par composante fortement connexe non triviale (Kosaraju),
+    /// donné comme le plus court chemin de son plus petit nœud à lui-même.
+    fn cycles(&self, edges: &[&Edge]) -> Vec<Vec<String>> {
+        let mut succ: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let mut pred: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for e in edges {
+            succ.entry(e.from).or_default().push(e.to);
+            pred.entry(e.to).or_default().push(e.from);
+            succ.entry(e.to).or_default();
+            pred.entry(e.from).or_default();
+        }
+
+        // ordre de fin de parcours en profondeur (itératif)
+        let mut order = vec![];
+        let mut visited = BTreeSet::new();
+        for &start in succ.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut stack = vec![(start, 0)];
+            while let Some((n, i)) = stack.pop() {
+                match succ[&n].get(i) {
+                    Some(&next) => {
+                        stack.push((n, i + 1));
+                        if visited.insert(next) {
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => order.push(n),
+                }
+            }
+        }
+
+        let mut component: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = vec![];
+        for &root in order.iter().rev() {
+            if component.contains_key(&root) {
+                continue;
+            }
+            let c = components.len();
+            let mut members = vec![];
+            let mut stack = vec![root];
+            component.insert(root, c);
+            while let Some(n) = stack.pop() {
+                members.push(n);
+                for &p in &pred[&n] {
+                    if let Entry::Vacant(slot) = component.entry(p) {
+                        slot.insert(c);
+                        stack.push(p);
+                    }
+                }
+            }
+            components.push(members);
+        }
+
+        let mut cycles = vec![];
+        for (c, members) in components.iter().enumerate() {
+            let start = *members.iter().min().expect("composante non vide");
+            let self_loop = succ[&start].contains(&start);
+            if members.len() < 2 && !self_loop {
+                continue;
+            }
+            let within = |n: &usize| component[n] == c;
+            let path = if self_loop {
+                vec![start, start]
+            } else {
+                let first = succ[&start].iter().copied().filter(within).min().unwrap();
+                let mut path = vec![start];
+                path.extend(self.shortest_path(&succ, first, start, within));
+                path
+            };
+            cycles.push(
+                path.into_iter()
+                    .map(|n| self.nodes[n].name.clone())
+                    .collect(),
+            );
+        }
+        cycles
+    }
+
+    /// Plus court chemin `from → … → to` (bornes comprises) par `succ`,
+    /// limité aux nœuds acceptés par `keep`.
+    fn shortest_path(
+        &self,
+        succ: &BTreeMap<usize, Vec<usize>>,
+        from: usize,
+        to: usize,
+        keep: impl Fn(&usize) -> bool,
+    ) -> Vec<usize> {
+        let mut parent: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        let mut seen = BTreeSet::from([from]);
+        while let Some(n) = queue.pop_front() {
+            if n == to {
+                let mut path = vec![to];
+                let mut cur = to;
+                while let Some(&p) = parent.get(&cur) {
+                    path.push(p);
+                    cur = p;
+                }
+                path.reverse();
+                return path;
+            }
+            for next in succ.get(&n).into_iter().flatten() {
+                if keep(next) && seen.insert(*next) {
+                    parent.insert(*next, n);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        vec![]
+    }
+
+    /// Violations qu'entraînerait le fait (ou qu'il entraîne, s'il est déjà
+    /// présent), sans modifier le graphe.
+    pub fn validate_fact(&self, fact: &Fact) -> Vec<Violation> {
+        self.fact_violations(&fact.subj, &fact.rel, &fact.obj, None)
+    }
+
+    fn fact_violations(
+        &self,
+        subj: &str,
+        rel: &str,
+        obj: &str,
+        replaced: Replaced,
+    ) -> Vec<Violation> {
+        let Some(schema) = &self.schema else {
+            return vec![];
+        };
+        let Some(rs) = schema.relation(rel) else {
+            return vec![];
+        };
+        let mut violations = vec![];
+        let fact = || (subj.to_string(), rel.to_string(), obj.to_string());
+        if let Some(expected) = &rs.domain {
+            if let Some(types) = self.missing_class(schema, subj, expected) {
+                let (subj, rel, obj) = fact();
+                violations.push(Violation::Domain {
+                    subj,
+                    rel,
+                    obj,
+                    expected: expected.clone(),
+                    types,
+                });
+            }
+        }
+        if let Some(expected) = &rs.range {
+            if let Some(types) = self.missing_class(schema, obj, expected) {
+                let (subj, rel, obj) = fact();
+                violations.push(Violation::Range {
+                    subj,
+                    rel,
+                    obj,
+                    expected: expected.clone(),
+                    types,
+                });
+            }
+        }
+        let is_replaced =
+            |e: &Edge| replaced.is_some_and(|(r, o)| e.rel == r && self.nodes[e.to].name == o);
+        if let Some(max) = rs.max_objects {
+            let mut values: BTreeSet<&str> = self
+                .outgoing_rel(subj, rel)
+                .into_iter()
+                .filter(|e| is_open(e) && !is_replaced(e))
+                .map(|e| self.nodes[e.to].name.as_str())
+                .collect();
+            values.insert(obj);
+            if values.len() > max {
+                violations.push(Violation::Cardinality {
+                    node: subj.to_string(),
+                    rel: rel.to_string(),
+                    side: Side::Objects,
+                    max,
+                    values: values.into_iter().map(String::from).collect(),
+                });
+            }
+        }
+        if let Some(max) = rs.max_subjects {
+            let mut values: BTreeSet<&str> = self
+                .incoming(obj)
+                .into_iter()
+                .filter(|e| e.rel == rel && is_open(e))
+                .filter(|e| !(self.nodes[e.from].name == subj && is_replaced(e)))
+                .map(|e| self.nodes[e.from].name.as_str())
+                .collect();
+            values.insert(subj);
+            if values.len() > max {
+                violations.push(Violation::Cardinality {
+                    node: obj.to_string(),
+                    rel: rel.to_string(),
+                    side: Side::Subjects,
+                    max,
+                    values: values.into_iter().map(String::from).collect(),
+                });
+            }
+        }
+        if rs.acyclic {
+            if let Some(path) = self.path_back(subj, rel, obj, replaced) {
+                violations.push(Violation::Cycle {
+                    rel: rel.to_string(),
+                    path,
+                });
+            }
+        }
+        violations
+    }
+
+    /// Cycle que ferme `subj rel obj` : `[subj, obj, …, subj]` si `subj`
+    /// est atteignable depuis `obj` par des faits de base `rel`.
+    fn path_back(
+        &self,
+        subj: &str,
+        rel: &str,
+        obj: &str,
+        replaced: Replaced,
+    ) -> Option<Vec<String>> {
+        if subj == obj {
+            return Some(vec![subj.to_string(), obj.to_string()]);
+        }
+        let (&s, &o) = (self.name2id.get(subj)?, self.name2id.get(obj)?);
+        let mut succ: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for e in self.edges_with_rel(rel) {
+            let replaced = e.from == s
+                && replaced.is_some_and(|(r, to)| r == rel && self.nodes[e.to].name == to);
+            if !e.derived && !replaced {
+                succ.entry(e.from).or_default().push(e.to);
+            }
+        }
+        let path = self.shortest_path(&succ, o, s, |_| true);
+        if path.is_empty() {
+            return None;
+        }
+        let mut names = vec![subj.to_string()];
+        names.extend(path.into_iter().map(|n| self.nodes[n].name.clone()));
+        Some(names)
+    }
+
+    /// Ajoute le fait seulement s'il respecte le schéma.
+    pub fn add_fact_checked(
+        &mut self,
+        fact: &Fact,
+        valid_from: Option<Timestamp>,
+        valid_to: Option<Timestamp>,
+    ) -> SchemaResult<()> {
+        // un fait déjà terminé va dans l'historique : rien à vérifier
+        if valid_to.is_none_or(|t| t > now()) {
+            self.reject(self.validate_fact(fact))?;
+        }
+        self.add_fact_valid(fact, valid_from, valid_to);
+        Ok(())
+    }
+
+    /// `update_fact_at` seulement si le nouveau fait respecte le schéma,
+    /// l'ancien étant considéré comme clos.
+    pub fn update_fact_checked(
+        &mut self,
+        subj: &str,
+        old_rel: &str,
+        old_obj: &str,
+        new_rel: &str,
+        new_obj: &str,
+        valid_at: Timestamp,
+    ) -> SchemaResult<()> {
+        self.reject(self.fact_violations(subj, new_rel, new_obj, Some((old_rel, old_obj))))?;
+        self.update_fact_at(subj, old_rel, old_obj, new_rel, new_obj, valid_at);
+        Ok(())
+    }
+
+    fn reject(&self, violations: Vec<Violation>) -> SchemaResult<()> {
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(SchemaError::Rejected(ViolationReport {
+            checked: 1,
+            violations,
+        }))
+    }
+
+    /// Violations relevées par les ajouts depuis le dernier appel (les
+    /// ajouts non vérifiés passent toujours, mais sont signalés ici).
+    pub fn take_violations(&mut self) -> Vec<Violation> {
+        std::mem::take(&mut self.violations)
+    }
+
+    /// Vérifie un fait qui vient d'entrer dans le graphe. Seul ce fait est
+    /// examiné : `check_schema` revoit l'ensemble (un nouveau type peut
+    /// rendre valides des faits signalés plus tôt).
+    pub(crate) fn record_violations(&mut self, subj: &str, rel: &str, obj: &str) {
+        let violations = self.fact_violations(subj, rel, obj, None);
+        self.violations.extend(violations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::relation_schema::RelationSchema;
+    use crate::time::from_ymd;
+
+    fn schema() -> GraphSchema {
+        GraphSchema::new(vec![
+            RelationSchema {
+                rel: "orbite".into(),
+                domain: Some("corps_céleste".into()),
+                range: Some("étoile".into()),
+                ..RelationSchema::default()
+            },
+            RelationSchema {
+                rel: "capitale".into(),
+                max_objects: Some(1),
+                max_subjects: Some(1),
+                ..RelationSchema::default()
+            },
+            RelationSchema {
+                rel: "super-classe".into(),
+                acyclic: true,
+                ..RelationSchema::default()
+            },
+        ])
+    }
+
+    fn graph() -> Graph {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "est_une", "planète_naine");
+        g.add_edge("planète_naine", "super-classe", "corps_céleste");
+        g.add_edge("Soleil", "est_une", "étoile");
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g
+    }
+
+    fn kind(v: &Violation) -> &'static str {
+        match v {
+            Violation::Domain { .. } => "domain",
+            Violation::Range { .. } => "range",
+            Violation::Cardinality {
+                side: Side::Objects,
+                ..
+            } => "objects",
+            Violation::Cardinality {
+                side: Side::Subjects,
+                ..
+            } => "subjects",
+            Violation::Cycle { .. } => "cycle",
+        }
+    }
+
+    #[test]
+    fn types_follow_subclasses() {
+        let mut g = graph();
+        g.set_schema(schema());
+        assert_eq!(g.types_of("Pluton"), ["corps_céleste", "planète_naine"]);
+        assert!(g.types_of("Charon").is_empty());
+    }
+
+    #[test]
+    fn check_schema_reports_each_violation_kind() {
+        let mut g = graph();
+        assert!(g.set_schema(schema()).is_empty());
+
+        g.add_edge("Paris", "orbite", "Soleil");
+        g.add_edge("Pluton", "orbite", "Charon");
+        g.add_edge("France", "capitale", "Paris");
+        g.add_edge("France", "capitale", "Lyon");
+        g.add_edge("Wessex", "capitale", "Winchester");
+        g.add_edge("Angleterre", "capitale", "Winchester");
+        g.add_edge("corps_céleste", "super-classe", "planète_naine");
+
+        let report = g.check_schema();
+        let mut kinds: Vec<&str> = report.violations.iter().map(kind).collect();
+        kinds.sort_unstable();
+        assert_eq!(kinds, ["cycle", "domain", "objects", "range", "subjects"]);
+        assert!(report.violations.contains(&Violation::Domain {
+            subj: "Paris".into(),
+            rel: "orbite".into(),
+            obj: "Soleil".into(),
+            expected: "corps_céleste".into(),
+            types: vec![],
+        }));
+        assert!(report.violations.contains(&Violation::Cycle {
+            rel: "super-classe".into(),
+            path: vec![
+                "planète_naine".into(),
+                "corps_céleste".into(),
+                "planète_naine".into()
+            ],
+        }));
+        // les ajouts non vérifiés passent mais sont signalés
+        let mut recorded: Vec<&str> = g.take_violations().iter().map(kind).collect();
+        recorded.sort_unstable();
+        assert_eq!(recorded, kinds);
+        assert!(g.take_violations().is_empty());
+    }
+
+    #[test]
+    fn add_fact_checked_rejects_without_adding() {
+        let mut g = graph();
+        g.set_schema(schema());
+
+        let fact = Fact::new("Pluton", "orbite", "Charon");
+        let Err(SchemaError::Rejected(report)) = g.add_fact_checked(&fact, None, None) else {
+            panic!("fait accepté malgré l'image");
+        };
+        assert_eq!(
+            report.violations.iter().map(kind).collect::<Vec<_>>(),
+            ["range"]
+        );
+        assert_eq!(g.outgoing("Pluton").len(), 2);
+        assert!(g.take_violations().is_empty());
+
+        g.add_fact_checked(&Fact::new("France", "capitale", "Paris"), None, None)
+            .unwrap();
+        let second = Fact::new("France", "capitale", "Lyon");
+        assert!(g.add_fact_checked(&second, None, None).is_err());
+        // une validité déjà terminée n'entre pas en concurrence
+        let past = Some(from_ymd(1200, 1, 1));
+        g.add_fact_checked(&second, None, past).unwrap();
+        // une correction remplace l'ancienne capitale
+        g.update_fact_checked("France", "capitale", "Paris", "capitale", "Lyon", now())
+            .unwrap();
+        assert!(g.check_schema().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Extrémité d'une relation dont la cardinalité est dépassée.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    /// Trop d'objets pour un même sujet.
+    Objects,
+    /// Trop de sujets pour un même objet.
+    Subjects,
+}
+
+/// Contrainte du schéma non respectée.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Violation {
+    /// Le sujet n'a pas la classe attendue (`types` : ses classes connues).
+    Domain {
+        subj: String,
+        rel: String,
+        obj: String,
+        expected: String,
+        types: Vec<String>,
+    },
+    /// L'objet n'a pas la classe attendue.
+    Range {
+        subj: String,
+        rel: String,
+        obj: String,
+        expected: String,
+        types: Vec<String>,
+    },
+    /// `node` a plus de `max` voisins par `rel` (`values`, triés).
+    Cardinality {
+        node: String,
+        rel: String,
+        side: Side,
+        max: usize,
+        values: Vec<String>,
+    },
+    /// Cycle `path[0] → … → path[0]` sur une relation acyclique.
+    Cycle { rel: String, path: Vec<String> },
+}
+
+impl Violation {
+    pub fn rel(&self) -> &str {
+        match self {
+            Violation::Domain { rel, .. }
+            | Violation::Range { rel, .. }
+            | Violation::Cardinality { rel, .. }
+            | Violation::Cycle { rel, .. } => rel,
+        }
+    }
+}
+
+fn types(types: &[String]) -> String {
+    if types.is_empty() {
+        "aucun type".to_string()
+    } else {
+        format!("types : {}", types.join(", "))
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Domain {
+                subj,
+                rel,
+                obj,
+                expected,
+                types: t,
+            } => write!(
+                f,
+                "domaine : {subj} --{rel}--> {obj}, le sujet n'est pas un(e) {expected} ({})",
+                types(t)
+            ),
+            Violation::Range {
+                subj,
+                rel,
+                obj,
+                expected,
+                types: t,
+            } => write!(
+                f,
+                "image : {subj} --{rel}--> {obj}, l'objet n'est pas un(e) {expected} ({})",
+                types(t)
+            ),
+            Violation::Cardinality {
+                node,
+                rel,
+                side,
+                max,
+                values,
+            } => {
+                let what = match side {
+                    Side::Objects => "objets",
+                    Side::Subjects => "sujets",
+                };
+                write!(
+                    f,
+                    "cardinalité : {node} a {} {what} par {rel} (max {max}) : {}",
+                    values.len(),
+                    values.join(", ")
+                )
+            }
+            Violation::Cycle { rel, path } => {
+                write!(f, "cycle sur {rel} : {}", path.join(" → "))
+            }
+        }
+    }
+}
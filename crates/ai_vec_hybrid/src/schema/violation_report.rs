@@ -0,0 +1,37 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::violation::Violation;
+
+/// Résultat d'une validation : nombre de faits examinés et violations.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViolationReport {
+    pub checked: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ViolationReport {
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+}
+
+impl fmt::Display for ViolationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} fait(s) vérifié(s), {} violation(s)",
+            self.checked,
+            self.violations.len()
+        )?;
+        for v in &self.violations {
+            write!(f, "\n  {v}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::server::http::Response;
+
+/// Erreur renvoyée au client : statut HTTP et message (`{"error": ...}`).
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("{status} : {message}")]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl ApiError {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(404, message)
+    }
+
+    pub fn to_response(&self) -> Response {
+        Response::json(self.status, &serde_json::json!({ "error": self.message }))
+    }
+}
@@ -0,0 +1,341 @@
+use std::io::{self, BufReader};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::graph::Graph;
+use crate::server::handlers::{handle, ServerState};
+use crate::server::http::Request;
+use crate::store::GraphStore;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Threads servant les connexions.
+const WORKERS: usize = 8;
+/// Connexions acceptées en attente d'un thread libre ; au-delà, `accept`
+/// attend et le système met les nouvelles connexions en file.
+const PENDING: usize = 64;
+
+/// Serveur HTTP/JSON local au-dessus d'un `Graph`, servi par un nombre
+/// borné de threads.
+///
+/// Routes :
+/// - `GET /health`
+/// - `GET /search?q=texte&k=5[&hybrid=true][&as_of=AAAA-MM-JJ]`
+/// - `GET /nodes/{nom}?hops=1[&rel=r][&as_of=AAAA-MM-JJ]` : voisinage
+/// - `POST /query` `{"query": "SELECT ..."}`
+/// - `POST /facts` `{"subj", "rel", "obj", "valid_from"?, "valid_to"?, "confidence"?, "source"?}`
+/// - `DELETE /facts` `{"subj", "rel", "obj"}`
+/// - `POST /facts/correct` `{"subj", "rel", "old_obj", "new_obj", "new_rel"?, "at"?}`
+/// - `POST /embeddings` `{"node", "text", "alpha"?}` ou `{"node", "vector"}`
+pub struct GraphServer {
+    listener: TcpListener,
+    state: Arc<RwLock<ServerState>>,
+}
+
+impl GraphServer {
+    /// Les écritures sont persistées dans `store` s'il est fourni.
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        graph: Graph,
+        store: Option<GraphStore>,
+    ) -> io::Result<GraphServer> {
+        Ok(GraphServer {
+            listener: TcpListener::bind(addr)?,
+            state: Arc::new(RwLock::new(ServerState { graph, store })),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn state(&self) -> Arc<RwLock<ServerState>> {
+        Arc::clone(&self.state)
+    }
+
+    /// Boucle d'acceptation bloquante.
+    pub fn serve(self) -> io::Result<()> {
+        self.accept_loop(&AtomicBool::new(false))
+    }
+
+    /// Lance la boucle dans un thread ; `ServerHandle::shutdown` l'arrête.
+    pub fn spawn(self) -> io::Result<ServerHandle> {
+        let addr = self.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            let _ = self.accept_loop(&flag);
+        });
+        Ok(ServerHandle {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    fn accept_loop(&self, stop: &AtomicBool) -> io::Result<()> {
+        let (tx, rx) = mpsc::sync_channel::<TcpStream>(PENDING);
+        let rx = Arc::new(Mutex::new(rx));
+        let workers: Vec<JoinHandle<()>> = (0..WORKERS)
+            .map(|_| {
+                let (state, rx) = (Arc::clone(&self.state), Arc::clone(&rx));
+                thread::spawn(move || worker(&state, &rx))
+            })
+            .collect();
+        for stream in self.listener.incoming() {
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else { continue };
+            if tx.send(stream).is_err() {
+                break;
+            }
+        }
+        // les connexions déjà acceptées sont servies avant l'arrêt
+        drop(tx);
+        for w in workers {
+            let _ = w.join();
+        }
+        Ok(())
+    }
+}
+
+/// Sert les connexions de la file jusqu'à sa fermeture.
+fn worker(state: &RwLock<ServerState>, rx: &Mutex<Receiver<TcpStream>>) {
+    loop {
+        let next = rx.lock().unwrap_or_else(PoisonError::into_inner).recv();
+        let Ok(stream) = next else { return };
+        // un gestionnaire qui panique ne doit pas retirer un thread du pool
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| serve_connection(state, stream)));
+    }
+}
+
+fn serve_connection(state: &RwLock<ServerState>, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let response = match Request::read_from(&mut reader) {
+        Ok(Some(req)) => handle(state, &req),
+        Ok(None) => return Ok(()),
+        Err(e) => e.to_response(),
+    };
+    response.write_to(&mut &stream)
+}
+
+/// Serveur lancé par `GraphServer::spawn`.
+pub struct ServerHandle {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ServerHandle {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Arrête d'accepter des connexions (les requêtes en cours se terminent).
+    pub fn shutdown(mut self) {
+        self.stop_now();
+    }
+
+    fn stop_now(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // réveille `accept`
+        let _ = TcpStream::connect(self.addr);
+        if let Some(t) = self.thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        self.stop_now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    use serde_json::{json, Value};
+
+    fn call(addr: SocketAddr, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+        let mut s = TcpStream::connect(addr).unwrap();
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        write!(
+            s,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut raw = String::new();
+        s.read_to_string(&mut raw).unwrap();
+        let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn server() -> ServerHandle {
+        let mut g = Graph::new();
+        g.add_edge("Pluton", "est_une", "planète");
+        g.add_edge("Pluton", "orbite", "Soleil");
+        g.add_edge("Terre", "orbite", "Soleil");
+        GraphServer::bind("127.0.0.1:0", g, None)
+            .unwrap()
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn read_and_write_endpoints() {
+        let srv = server();
+        let addr = srv.addr();
+
+        let (status, health) = call(addr, "GET", "/health", None);
+        assert_eq!(status, 200);
+        assert_eq!(health["facts"], 3);
+
+        let (_, hits) = call(addr, "GET", "/search?q=pluton&k=2", None);
+        assert_eq!(hits[0]["name"], "Pluton");
+
+        let (_, n) = call(addr, "GET", "/nodes/Soleil", None);
+        assert_eq!(n["facts"].as_array().unwrap().len(), 2);
+
+        let (status, _) = call(
+            addr,
+            "POST",
+            "/facts",
+            Some(json!({"subj": "Mars", "rel": "orbite", "obj": "Soleil"})),
+        );
+        assert_eq!(status, 201);
+        let (status, _) = call(
+            addr,
+            "POST",
+            "/facts/correct",
+            Some(
+                json!({"subj": "pluton", "rel": "est_une", "old_obj": "planète",
+                        "new_obj": "planète_naine", "at": "2006-08-24"}),
+            ),
+        );
+        assert_eq!(status, 200);
+        let (_, n) = call(addr, "GET", "/nodes/Pluton?rel=est_une", None);
+        assert_eq!(n["facts"][0]["obj"], "planète_naine");
+        let (_, n) = call(
+            addr,
+            "GET",
+            "/nodes/Pluton?rel=est_une&as_of=2000-01-01",
+            None,
+        );
+        assert_eq!(n["facts"][0]["obj"], "planète");
+
+        let (status, e) = call(
+            addr,
+            "POST",
+            "/embeddings",
+            Some(json!({"node": "Pluton", "text": "dwarf planet", "alpha": 0.5})),
+        );
+        assert_eq!(status, 200);
+        assert!(e["similarity_to_previous"].as_f64().unwrap() < 1.0);
+
+        let (_, q) = call(
+            addr,
+            "POST",
+            "/query",
+            Some(json!({"query": "SELECT ?x WHERE { ?x orbite Soleil } ORDER BY ?x"})),
+        );
+        assert_eq!(q["rows"].as_array().unwrap().len(), 3);
+
+        assert_eq!(call(addr, "GET", "/nodes/Vulcain", None).0, 404);
+        assert_eq!(call(addr, "GET", "/nope", None).0, 404);
+        assert_eq!(call(addr, "PUT", "/facts", None).0, 405);
+        assert_eq!(
+            call(addr, "POST", "/facts", Some(json!({"subj": 1}))).0,
+            400
+        );
+        srv.shutdown();
+    }
+
+    #[test]
+    fn added_facts_reuse_existing_nodes() {
+        let srv = server();
+        let addr = srv.addr();
+        let nodes = |addr| call(addr, "GET", "/health", None).1["nodes"].clone();
+        let before = nodes(addr);
+
+        let (status, f) = call(
+            addr,
+            "POST",
+            "/facts",
+            Some(json!({"subj": "pluton", "rel": "orbite", "obj": "soleil"})),
+        );
+        assert_eq!(status, 201);
+        assert_eq!(
+            (f["subj"].as_str(), f["obj"].as_str()),
+            (Some("Pluton"), Some("Soleil"))
+        );
+        assert_eq!(nodes(addr), before);
+
+        let (_, c) = call(
+            addr,
+            "POST",
+            "/facts/correct",
+            Some(
+                json!({"subj": "Terre", "rel": "orbite", "old_obj": "Soleil", "new_obj": "pluton"}),
+            ),
+        );
+        assert_eq!(c["added"]["obj"], "Pluton");
+        assert_eq!(nodes(addr), before);
+    }
+
+    #[test]
+    fn more_connections_than_workers_are_queued() {
+        let srv = server();
+        let addr = srv.addr();
+        // toutes les connexions sont ouvertes avant qu'aucune réponse ne soit lue
+        let mut streams: Vec<TcpStream> = (0..WORKERS * 3)
+            .map(|_| {
+                let mut s = TcpStream::connect(addr).unwrap();
+                write!(s, "GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+                s
+            })
+            .collect();
+        for s in &mut streams {
+            let mut raw = String::new();
+            s.read_to_string(&mut raw).unwrap();
+            assert!(raw.starts_with("HTTP/1.1 200"), "{raw}");
+        }
+        srv.shutdown();
+    }
+
+    #[test]
+    fn concurrent_reads_and_writes() {
+        let srv = server();
+        let addr = srv.addr();
+        let workers: Vec<_> = (0..8)
+            .map(|i| {
+                thread::spawn(move || {
+                    for j in 0..10 {
+                        if i % 2 == 0 {
+                            let fact = json!({"subj": format!("lune_{i}_{j}"), "rel": "orbite", "obj": "Terre"});
+                            assert_eq!(call(addr, "POST", "/facts", Some(fact)).0, 201);
+                        } else {
+                            assert_eq!(call(addr, "GET", "/search?q=lune", None).0, 200);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for w in workers {
+            w.join().unwrap();
+        }
+        let (_, n) = call(addr, "GET", "/nodes/Terre?rel=orbite", None);
+        // 40 lunes + Terre --orbite--> Soleil
+        assert_eq!(n["facts"].as_array().unwrap().len(), 41);
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::{BTreeSet, HashSet};
+use std::sync::{PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::edge::Edge;
+use crate::embedding::cosine;
+use crate::fact::Fact;
+use crate::fact_row::FactRow;
+use crate::feedback::{FeedbackError, FeedbackParams};
+use crate::graph::Graph;
+use crate::hybrid::HybridQuery;
+use crate::provenance::Provenance;
+use crate::schema::SchemaError;
+use crate::server::api_error::{ApiError, ApiResult};
+use crate::server::http::{Request, Response};
+use crate::store::GraphStore;
+use crate::time::{self, Timestamp};
+
+/// Source des faits ajoutés par l'API quand la requête n'en donne pas.
+const DEFAULT_SOURCE: &str = "http";
+
+/// État partagé du serveur : le graphe et, s'il y en a un, son stockage.
+/// Les lectures se font en parallèle sous verrou partagé ; chaque écriture
+/// prend le verrou exclusif le temps de la mutation et du `commit`.
+pub struct ServerState {
+    pub graph: Graph,
+    pub store: Option<GraphStore>,
+}
+
+impl ServerState {
+    fn commit(&mut self) -> ApiResult<()> {
+        if let Some(store) = &mut self.store {
+            store
+                .commit(&mut self.graph)
+                .map_err(|e| ApiError::new(500, e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct NewFact {
+    subj: String,
+    rel: String,
+    obj: String,
+    #[serde(default)]
+    valid_from: Option<String>,
+    #[serde(default)]
+    valid_to: Option<String>,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FactKey {
+    subj: String,
+    rel: String,
+    obj: String,
+}
+
+#[derive(Deserialize)]
+struct Correction {
+    subj: String,
+    rel: String,
+    old_obj: String,
+    new_obj: String,
+    #[serde(default)]
+    new_rel: Option<String>,
+    #[serde(default)]
+    at: Option<String>,
+}
+
+/// Mise à jour EMA depuis un texte, ou remplacement par un vecteur.
+#[derive(Deserialize)]
+struct EmbeddingUpdate {
+    node: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    alpha: Option<f32>,
+    #[serde(default)]
+    vector: Option<Vec<f32>>,
+}
+
+/// Résultats jugés pour une recherche `query`.
+#[derive(Deserialize)]
+struct Feedback {
+    query: String,
+    #[serde(default)]
+    relevant: Vec<String>,
+    #[serde(default)]
+    irrelevant: Vec<String>,
+    #[serde(default)]
+    max_step: Option<f32>,
+    #[serde(default)]
+    max_drift: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct Rollback {
+    node: String,
+    version: usize,
+}
+
+#[derive(Deserialize)]
+struct QueryBody {
+    query: String,
+}
+
+#[derive(Serialize)]
+struct SearchHit {
+    name: String,
+    score: f32,
+    /// Sources des faits qui mentionnent le nœud.
+    sources: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Neighbourhood {
+    node: String,
+    aliases: Vec<String>,
+    hops: usize,
+    nodes: Vec<String>,
+    facts: Vec<FactRow>,
+}
+
+/// Traite une requête ; les erreurs deviennent des réponses `{"error": ...}`.
+pub fn handle(state: &RwLock<ServerState>, req: &Request) -> Response {
+    route(state, req).unwrap_or_else(|e| e.to_response())
+}
+
+fn route(state: &RwLock<ServerState>, req: &Request) -> ApiResult<Response> {
+    let read = || state.read().unwrap_or_else(PoisonError::into_inner);
+    let write = || state.write().unwrap_or_else(PoisonError::into_inner);
+    let segs: Vec<&str> = req.segments.iter().map(String::as_str).collect();
+    match (req.method.as_str(), segs.as_slice()) {
+        ("GET", ["health"]) => {
+            let s = read();
+            Ok(Response::ok(&json!({
+                "status": "ok",
+                "nodes": s.graph.nodes.iter().filter(|n| n.merged_into.is_none()).count(),
+                "facts": s.graph.edges.iter().filter(|e| !e.derived).count(),
+                "seq": s.store.as_ref().map(GraphStore::seq),
+            })))
+        }
+        ("GET", ["search"]) => search(&read().graph, req),
+        ("GET", ["names"]) => names(&read().graph, req),
+        ("GET", ["schema", "check"]) => Ok(Response::ok(&read().graph.check_schema())),
+        ("GET", ["nodes", name]) => neighbourhood(&read().graph, name, req),
+        ("GET", ["nodes", name, "history"]) => embedding_history(&read().graph, name),
+        ("POST", ["query"]) => {
+            let body: QueryBody = req.json()?;
+            let answer = read()
+                .graph
+                .query_str(&body.query)
+                .map_err(|e| ApiError::bad_request(format!("requête invalide, {e}")))?;
+            Ok(Response::ok(&answer))
+        }
+        ("POST", ["facts"]) => add_fact(&mut write(), req),
+        ("DELETE", ["facts"]) => retract_fact(&mut write(), req),
+        ("POST", ["facts", "correct"]) => correct_fact(&mut write(), req),
+        ("POST", ["embeddings"]) => update_embedding(&mut write(), req),
+        ("POST", ["embeddings", "rollback"]) => rollback_embedding(&mut write(), req),
+        ("POST", ["feedback"]) => feedback(&mut write(), req),
+        (_, ["health" | "search" | "names" | "query" | "facts" | "embeddings" | "feedback"])
+        | (_, ["nodes", _])
+        | (_, ["nodes", _, "history"])
+        | (_, ["facts", "correct"])
+        | (_, ["embeddings", "rollback"])
+        | (_, ["schema", "check"]) => Err(ApiError::new(405, "méthode non autorisée")),
+        _ => Err(ApiError::not_found("route inconnue")),
+    }
+}
+
+fn date(value: Option<&str>, field: &str) -> ApiResult<Option<Timestamp>> {
+    match value {
+        Some(v) => time::parse_date(v)
+            .map(Some)
+            .ok_or_else(|| ApiError::bad_request(format!("{field} : date invalide '{v}'"))),
+        None => Ok(None),
+    }
+}
+
+fn node_name(g: &Graph, name: &str) -> ApiResult<String> {
+    g.resolve_name(name)
+        .map(|id| g.nodes[id].name.clone())
+        .ok_or_else(|| ApiError::not_found(unknown_node(g, name)))
+}
+
+/// « nœud inconnu », avec les noms les plus proches s'il y en a.
+fn unknown_node(g: &Graph, name: &str) -> String {
+    let close: Vec<String> = g
+        .lookup_names(name, 3)
+        .into_iter()
+        .map(|m| m.node)
+        .collect();
+    if close.is_empty() {
+        format!("nœud inconnu '{name}'")
+    } else {
+        format!("nœud inconnu '{name}' (proches : {})", close.join(", "))
+    }
+}
+
+fn names(g: &Graph, req: &Request) -> ApiResult<Response> {
+    let q = req
+        .param("q")
+        .ok_or_else(|| ApiError::bad_request("paramètre 'q' manquant"))?;
+    let k = req.param_or("k", 10usize)?;
+    let matches = match (req.param_or("prefix", false)?, req.param("distance")) {
+        (true, _) => g.complete(q, k),
+        (false, Some(_)) => g.fuzzy_names(q, req.param_or("distance", 0usize)?, k),
+        (false, None) => g.lookup_names(q, k),
+    };
+    Ok(Response::ok(&matches))
+}
+
+fn search(g: &Graph, req: &Request) -> ApiResult<Response> {
+    let q = req
+        .param("q")
+        .ok_or_else(|| ApiError::bad_request("paramètre 'q' manquant"))?;
+    let k = req.param_or("k", 5usize)?;
+    if req.param_or("hybrid", false)? {
+        let hq = HybridQuery {
+            limit: k,
+            ..HybridQuery::default()
+        };
+        return Ok(Response::ok(&g.hybrid_search(q, &hq)));
+    }
+    let hits = match date(req.param("as_of"), "as_of")? {
+        Some(t) => g.search_text_as_of(q, k, t),
+        None => g.search_text(q, k),
+    };
+    let hits: Vec<SearchHit> = hits
+        .into_iter()
+        .map(|(name, score)| SearchHit {
+            sources: g.sources_of(&name).into_iter().map(String::from).collect(),
+            name,
+            score,
+        })
+        .collect();
+    Ok(Response::ok(&hits))
+}
+
+/// Voisinage à `hops` sauts (arêtes dans les deux sens), filtrable par `rel`.
+fn neighbourhood(g: &Graph, name: &str, req: &Request) -> ApiResult<Response> {
+    let node = node_name(g, name)?;
+    let hops = req.param_or("hops", 1usize)?.min(5);
+    let rel = req.param("rel");
+    let as_of = date(req.param("as_of"), "as_of")?;
+
+    let mut seen: BTreeSet<String> = BTreeSet::from([node.clone()]);
+    let mut frontier = vec![node.clone()];
+    let mut facts: Vec<&Edge> = vec![];
+    // une même version peut être atteinte depuis ses deux extrémités
+    let mut fact_set = HashSet::new();
+    for _ in 0..hops {
+        let mut next = vec![];
+        for n in &frontier {
+            let edges = match as_of {
+                Some(t) => [g.outgoing_as_of(n, t), g.incoming_as_of(n, t)].concat(),
+                None => [g.outgoing(n), g.incoming(n)].concat(),
+            };
+            for e in edges {
+                let key = (e.from, e.rel.as_str(), e.to, e.valid_from, e.recorded_at);
+                if rel.is_some_and(|r| r != e.rel) || !fact_set.insert(key) {
+                    continue;
+                }
+                facts.push(e);
+                for id in [e.from, e.to] {
+                    let other = &g.nodes[id].name;
+                    if seen.insert(other.clone()) {
+                        next.push(other.clone());
+                    }
+                }
+            }
+        }
+        frontier = next;
+    }
+    let id = g.name2id[&node];
+    Ok(Response::ok(&Neighbourhood {
+        aliases: g.aliases(id).into_iter().map(String::from).collect(),
+        hops,
+        nodes: seen.into_iter().collect(),
+        facts: facts.into_iter().map(|e| FactRow::new(g, e)).collect(),
+        node,
+    }))
+}
+
+fn add_fact(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let f: NewFact = req.json()?;
+    if f.subj.is_empty() || f.rel.is_empty() || f.obj.is_empty() {
+        return Err(ApiError::bad_request("subj, rel et obj sont requis"));
+    }
+    let valid_from = date(f.valid_from.as_deref(), "valid_from")?;
+    let valid_to = date(f.valid_to.as_deref(), "valid_to")?;
+    let confidence = f.confidence.unwrap_or(1.0);
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err(ApiError::bad_request("confidence doit être entre 0 et 1"));
+    }
+    let source = f.source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+    // « pluton » désigne le nœud « Pluton » existant, sans le dupliquer
+    let subj = existing_or_new(&s.graph, &f.subj);
+    let obj = existing_or_new(&s.graph, &f.obj);
+    let fact =
+        Fact::new(&subj, &f.rel, &obj).with_provenance(Provenance::new(confidence, Some(source)));
+    if let Err(e) = s.graph.add_fact_checked(&fact, valid_from, valid_to) {
+        return Ok(rejected(e));
+    }
+    s.commit()?;
+    // un fait moins sûr qu'un fait concurrent (relation fonctionnelle) est
+    // enregistré mais aussitôt écarté
+    let current = has_base_fact(&s.graph, &subj, &f.rel, &obj);
+    Ok(Response::json(
+        201,
+        &json!({
+            "subj": fact.subj,
+            "rel": fact.rel,
+            "obj": fact.obj,
+            "confidence": fact.provenance.confidence,
+            "source": fact.provenance.source,
+            "current": current,
+        }),
+    ))
+}
+
+/// 422 avec le rapport du schéma.
+fn rejected(e: SchemaError) -> Response {
+    let SchemaError::Rejected(report) = &e;
+    Response::json(
+        422,
+        &json!({ "error": e.to_string(), "violations": report.violations }),
+    )
+}
+
+/// Nom canonique d'un nœud existant, ou `name` tel quel pour un nouveau nœud.
+fn existing_or_new(g: &Graph, name: &str) -> String {
+    g.resolve_name(name)
+        .map_or_else(|| name.to_string(), |id| g.nodes[id].name.clone())
+}
+
+fn has_base_fact(g: &Graph, subj: &str, rel: &str, obj: &str) -> bool {
+    g.outgoing(subj)
+        .iter()
+        .any(|e| !e.derived && e.rel == rel && g.nodes[e.to].name == obj)
+}
+
+fn retract_fact(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let f: FactKey = req.json()?;
+    let subj = node_name(&s.graph, &f.subj)?;
+    let obj = node_name(&s.graph, &f.obj)?;
+    if !has_base_fact(&s.graph, &subj, &f.rel, &obj) {
+        return Err(ApiError::not_found(format!(
+            "fait introuvable : {subj} --{}--> {obj}",
+            f.rel
+        )));
+    }
+    s.graph.remove_edge_exact(&subj, &f.rel, &obj);
+    s.commit()?;
+    Ok(Response::ok(
+        &json!({ "retracted": { "subj": subj, "rel": f.rel, "obj": obj } }),
+    ))
+}
+
+fn correct_fact(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let c: Correction = req.json()?;
+    let subj = node_name(&s.graph, &c.subj)?;
+    let old = node_name(&s.graph, &c.old_obj)?;
+    if !has_base_fact(&s.graph, &subj, &c.rel, &old) {
+        return Err(ApiError::not_found(format!(
+            "fait introuvable : {subj} --{}--> {old}",
+            c.rel
+        )));
+    }
+    let at = date(c.at.as_deref(), "at")?.unwrap_or_else(time::now);
+    let new_rel = c.new_rel.unwrap_or_else(|| c.rel.clone());
+    let new_obj = existing_or_new(&s.graph, &c.new_obj);
+    if let Err(e) = s
+        .graph
+        .update_fact_checked(&subj, &c.rel, &old, &new_rel, &new_obj, at)
+    {
+        return Ok(rejected(e));
+    }
+    s.commit()?;
+    Ok(Response::ok(&json!({
+        "at": time::format_date(at),
+        "closed": { "subj": subj, "rel": c.rel, "obj": old },
+        "added": { "subj": subj, "rel": new_rel, "obj": new_obj },
+    })))
+}
+
+fn update_embedding(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let u: EmbeddingUpdate = req.json()?;
+    let node = node_name(&s.graph, &u.node)?;
+    let id = s.graph.name2id[&node];
+    let before = s.graph.nodes[id].emb.clone();
+    match (&u.text, &u.vector) {
+        (Some(text), None) => {
+            let alpha = u.alpha.unwrap_or(0.3);
+            if !(0.0..=1.0).contains(&alpha) {
+                return Err(ApiError::bad_request("alpha doit être dans [0, 1]"));
+            }
+            s.graph.set_node_embedding_ema(&node, text, alpha);
+        }
+        (None, Some(v)) => {
+            let dim = s.graph.embedder().dim();
+            if v.len() != dim {
+                return Err(ApiError::bad_request(format!(
+                    "vecteur de dimension {} (attendu {dim})",
+                    v.len()
+                )));
+            }
+            s.graph.set_node_embedding(&node, v);
+        }
+        _ => return Err(ApiError::bad_request("fournir 'text' ou 'vector'")),
+    }
+    s.commit()?;
+    Ok(Response::ok(&json!({
+        "node": node,
+        "similarity_to_previous": cosine(&before, &s.graph.nodes[id].emb),
+    })))
+}
+
+fn feedback_error(e: FeedbackError) -> ApiError {
+    match e {
+        FeedbackError::UnknownNode(_) => ApiError::not_found(e.to_string()),
+        _ => ApiError::bad_request(e.to_string()),
+    }
+}
+
+fn feedback(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let fb: Feedback = req.json()?;
+    if fb.relevant.is_empty() && fb.irrelevant.is_empty() {
+        return Err(ApiError::bad_request("fournir 'relevant' ou 'irrelevant'"));
+    }
+    let names = |list: &[String]| -> ApiResult<Vec<String>> {
+        list.iter().map(|n| node_name(&s.graph, n)).collect()
+    };
+    let relevant = names(&fb.relevant)?;
+    let irrelevant = names(&fb.irrelevant)?;
+    let mut params = FeedbackParams::default();
+    params.max_step = fb.max_step.unwrap_or(params.max_step);
+    params.max_drift = fb.max_drift.unwrap_or(params.max_drift);
+
+    let relevant: Vec<&str> = relevant.iter().map(String::as_str).collect();
+    let irrelevant: Vec<&str> = irrelevant.iter().map(String::as_str).collect();
+    let updates = s
+        .graph
+        .apply_feedback(&fb.query, &relevant, &irrelevant, &params)
+        .map_err(feedback_error)?;
+    s.commit()?;
+    Ok(Response::ok(
+        &json!({ "query": fb.query, "updates": updates }),
+    ))
+}
+
+fn embedding_history(g: &Graph, name: &str) -> ApiResult<Response> {
+    let node = node_name(g, name)?;
+    Ok(Response::ok(&json!({
+        "node": node,
+        "version": g.nodes[g.name2id[&node]].emb_history.len(),
+        "updates": g.embedding_history(&node),
+    })))
+}
+
+fn rollback_embedding(s: &mut ServerState, req: &Request) -> ApiResult<Response> {
+    let r: Rollback = req.json()?;
+    let node = node_name(&s.graph, &r.node)?;
+    let update = s
+        .graph
+        .rollback_embedding(&node, r.version)
+        .map_err(feedback_error)?;
+    s.commit()?;
+    Ok(Response::ok(&update))
+}
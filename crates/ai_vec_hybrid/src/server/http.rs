@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::server::api_error::{ApiError, ApiResult};
+
+/// Taille maximale d'un corps de requête.
+pub const MAX_BODY: usize = 1 << 20;
+const MAX_HEADER_LINES: usize = 100;
+
+/// Requête HTTP/1.1 minimale : chemin décodé en segments, paramètres de
+/// requête décodés, corps brut.
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub segments: Vec<String>,
+    pub query: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Lit une requête ; `Ok(None)` si la connexion se ferme avant.
+    pub fn read_from(r: &mut impl BufRead) -> ApiResult<Option<Request>> {
+        let mut line = String::new();
+        if read_line(r, &mut line)? == 0 {
+            return Ok(None);
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target), Some(_version)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ApiError::bad_request("ligne de requête invalide"));
+        };
+        let method = method.to_string();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(percent_decode)
+            .collect();
+        let query = query
+            .split('&')
+            .filter(|kv| !kv.is_empty())
+            .map(|kv| {
+                let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+                (percent_decode(k), percent_decode(v))
+            })
+            .collect();
+
+        let mut len = 0;
+        for _ in 0..MAX_HEADER_LINES {
+            line.clear();
+            read_line(r, &mut line)?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                let mut body = vec![0; len];
+                r.read_exact(&mut body)
+                    .map_err(|_| ApiError::bad_request("corps de requête incomplet"))?;
+                return Ok(Some(Request {
+                    method,
+                    segments,
+                    query,
+                    body,
+                }));
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    len = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| ApiError::bad_request("Content-Length invalide"))?;
+                    if len > MAX_BODY {
+                        return Err(ApiError::new(413, "corps de requête trop volumineux"));
+                    }
+                }
+            }
+        }
+        Err(ApiError::new(431, "trop d'en-têtes"))
+    }
+
+    /// Corps JSON désérialisé.
+    pub fn json<T: DeserializeOwned>(&self) -> ApiResult<T> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| ApiError::bad_request(format!("corps JSON invalide : {e}")))
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(String::as_str)
+    }
+
+    /// Paramètre de requête converti, ou `default` s'il est absent.
+    pub fn param_or<T: std::str::FromStr>(&self, name: &str, default: T) -> ApiResult<T> {
+        match self.param(name) {
+            Some(v) => v
+                .parse()
+                .map_err(|_| ApiError::bad_request(format!("paramètre '{name}' invalide : {v}"))),
+            None => Ok(default),
+        }
+    }
+}
+
+fn read_line(r: &mut impl BufRead, buf: &mut String) -> ApiResult<usize> {
+    // borne la ligne pour ne pas lire indéfiniment un flux sans fin de ligne
+    let n = r
+        .take(8 * 1024)
+        .read_line(buf)
+        .map_err(|_| ApiError::bad_request("requête illisible"))?;
+    if n > 0 && !buf.ends_with('\n') {
+        return Err(ApiError::new(431, "ligne trop longue"));
+    }
+    Ok(n)
+}
+
+/// `%XX` et `+` (espace) ; une séquence invalide est gardée telle quelle.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => match s
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                Some(b) => {
+                    out.push(b);
+                    i += 2;
+                }
+                None => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Réponse JSON ; la connexion est fermée après l'envoi.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json<T: Serialize + ?Sized>(status: u16, value: &T) -> Self {
+        Self {
+            status,
+            body: serde_json::to_string(value).expect("réponse sérialisable"),
+        }
+    }
+
+    pub fn ok<T: Serialize + ?Sized>(value: &T) -> Self {
+        Self::json(200, value)
+    }
+
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        write!(
+            w,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            self.body.len(),
+            self.body
+        )?;
+        w.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
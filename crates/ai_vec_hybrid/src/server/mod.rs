@@ -0,0 +1,8 @@
+pub mod api_error;
+pub mod graph_server;
+pub mod handlers;
+pub mod http;
+
+pub use api_error::{ApiError, ApiResult};
+pub use graph_server::{GraphServer, ServerHandle};
+pub use handlers::ServerState;
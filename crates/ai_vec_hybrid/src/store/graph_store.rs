@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::graph::Graph;
+use crate::graph_op::GraphOp;
+use crate::store::snapshot::Snapshot;
+use crate::store::store_error::{io_err, StoreResult};
+use crate::store::wal::{Wal, WalRecord};
+
+const WAL_FILE: &str = "wal.jsonl";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const DEFAULT_SNAPSHOT_EVERY: usize = 1000;
+
+/// Stockage durable d'un `Graph` : snapshot périodique + WAL append-only.
+///
+/// Au démarrage on charge le dernier snapshot puis on rejoue les
+/// enregistrements du WAL dont la séquence est postérieure.
+pub struct GraphStore {
+    dir: PathBuf,
+    wal: Wal,
+    seq: u64,
+    snapshot_every: usize,
+}
+
+impl GraphStore {
+    /// Ouvre le répertoire `dir` (créé au besoin) et reconstruit le graphe.
+    pub fn open(dir: impl AsRef<Path>) -> StoreResult<(GraphStore, Graph)> {
+        Self::open_with(dir, Graph::new())
+    }
+
+    /// Comme `open`, mais recharge dans `g` (vide), déjà configuré
+    /// (embedder, index ANN…).
+    pub fn open_with(dir: impl AsRef<Path>, mut g: Graph) -> StoreResult<(GraphStore, Graph)> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(io_err(&dir))?;
+
+        let mut seq = 0;
+        if let Some(snap) = Snapshot::load(&dir.join(SNAPSHOT_FILE))? {
+            snap.restore_into(&mut g);
+            seq = snap.seq;
+        }
+        let (wal, records) = Wal::open(&dir.join(WAL_FILE))?;
+        for rec in records {
+            // déjà inclus dans le snapshot si crash entre snapshot et troncature
+            if rec.seq <= seq {
+                continue;
+            }
+            rec.op.apply(&mut g);
+            seq = rec.seq;
+        }
+        g.take_journal();
+
+        Ok((
+            GraphStore {
+                dir,
+                wal,
+                seq,
+                snapshot_every: DEFAULT_SNAPSHOT_EVERY,
+            },
+            g,
+        ))
+    }
+
+    /// Noms des nœuds stockés dans `dir`, lus dans le snapshot puis le WAL
+    /// sans reconstruire le graphe (aucun embedding n'est calculé).
+    pub fn node_names(dir: impl AsRef<Path>) -> StoreResult<Vec<String>> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut names = vec![];
+        let mut seq = 0;
+        if let Some(snap) = Snapshot::load(&dir.join(SNAPSHOT_FILE))? {
+            names.extend(snap.nodes.into_iter().map(|n| n.name));
+            seq = snap.seq;
+        }
+        let (_, records) = Wal::open(&dir.join(WAL_FILE))?;
+        for rec in records.into_iter().filter(|r| r.seq > seq) {
+            match rec.op {
+                GraphOp::AddNode { name } => names.push(name),
+                GraphOp::AddEdge { from, to, .. } => names.extend([from, to]),
+                _ => {}
+            }
+        }
+        let mut seen = HashSet::new();
+        names.retain(|n| seen.insert(n.clone()));
+        Ok(names)
+    }
+
+    /// Nombre d'enregistrements WAL au-delà duquel `commit` prend un snapshot.
+    pub fn with_snapshot_every(mut self, n: usize) -> Self {
+        self.snapshot_every = n.max(1);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Dernière séquence persistée.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Persiste les mutations en attente de `g` dans le WAL. Si l'écriture
+    /// échoue, elles restent en attente (et `seq` inchangé) : un `commit`
+    /// ultérieur les réessaie.
+    pub fn commit(&mut self, g: &mut Graph) -> StoreResult<()> {
+        let ops = g.take_journal();
+        if ops.is_empty() {
+            return Ok(());
+        }
+        let records: Vec<WalRecord> = ops
+            .into_iter()
+            .zip(self.seq + 1..)
+            .map(|(op, seq)| WalRecord { seq, op })
+            .collect();
+        if let Err(e) = self.wal.append(&records) {
+            let later = std::mem::take(&mut g.journal);
+            g.journal = records.into_iter().map(|r| r.op).chain(later).collect();
+            return Err(e);
+        }
+        self.seq += records.len() as u64;
+        if self.wal.len() >= self.snapshot_every {
+            self.snapshot(g)?;
+        }
+        Ok(())
+    }
+
+    /// Écrit un snapshot complet puis vide le WAL.
+    pub fn snapshot(&mut self, g: &mut Graph) -> StoreResult<()> {
+        // les mutations non journalisées sont couvertes par le snapshot lui-même
+        self.seq += g.take_journal().len() as u64;
+        Snapshot::capture(g, self.seq).save(&self.dir.join(SNAPSHOT_FILE))?;
+        self.wal.truncate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    use super::*;
+    use crate::fact::Fact;
+    use crate::store::StoreError;
+
+    fn temp_store(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("graph_store_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn facts(g: &Graph) -> BTreeSet<(String, String, String)> {
+        g.edges
+            .iter()
+            .filter(|e| !e.derived)
+            .map(|e| {
+                let name = |id: usize| g.nodes[id].name.clone();
+                (name(e.from), e.rel.clone(), name(e.to))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reopening_replays_the_snapshot_then_the_wal() {
+        let dir = temp_store("replay");
+        let (mut store, mut g) = GraphStore::open(&dir).unwrap();
+        for obj in ["Soleil", "planète_naine", "Kuiper"] {
+            g.add_fact(&Fact::new("Pluton", "lié_à", obj));
+        }
+        store.commit(&mut g).unwrap();
+        store.snapshot(&mut g).unwrap();
+        assert!(dir.join(SNAPSHOT_FILE).exists());
+        assert!(store.wal.is_empty());
+        g.add_fact(&Fact::new("Charon", "orbite", "Pluton"));
+        g.remove_edge_exact("Pluton", "lié_à", "Kuiper");
+        store.commit(&mut g).unwrap();
+        assert!(!store.wal.is_empty());
+        let (seq, expected) = (store.seq(), facts(&g));
+        drop(store);
+
+        let (store, g) = GraphStore::open(&dir).unwrap();
+        assert_eq!(store.seq(), seq);
+        assert_eq!(facts(&g), expected);
+        assert!(g.journal.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn node_names_are_read_without_replaying() {
+        let dir = temp_store("names");
+        assert!(GraphStore::node_names(&dir).unwrap().is_empty());
+        let (mut store, mut g) = GraphStore::open(&dir).unwrap();
+        g.add_fact(&Fact::new("Pluton", "orbite", "Soleil"));
+        store.commit(&mut g).unwrap();
+        store.snapshot(&mut g).unwrap();
+        g.add_fact(&Fact::new("Charon", "orbite", "Pluton"));
+        g.add_node("Kuiper");
+        store.commit(&mut g).unwrap();
+
+        let names = GraphStore::node_names(&dir).unwrap();
+        assert_eq!(names, ["Pluton", "Soleil", "Charon", "Kuiper"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn out_of_range_confidence_is_rejected_on_reopen() {
+        let dir = temp_store("confidence");
+        let (mut store, mut g) = GraphStore::open(&dir).unwrap();
+        g.add_fact(&Fact::new("Pluton", "orbite", "Soleil"));
+        store.commit(&mut g).unwrap();
+        drop(store);
+        let written = fs::read_to_string(dir.join(WAL_FILE))
+            .unwrap()
+            .lines()
+            .count();
+        // ligne complète mais invalide : pas une écriture interrompue
+        let mut wal = OpenOptions::new()
+            .append(true)
+            .open(dir.join(WAL_FILE))
+            .unwrap();
+        writeln!(
+            wal,
+            r#"{{"seq":9,"op":"add_edge","from":"Charon","rel":"orbite","to":"Pluton","confidence":2.0}}"#
+        )
+        .unwrap();
+        drop(wal);
+        match GraphStore::open(&dir) {
+            Err(StoreError::CorruptWal { line, message, .. }) => {
+                assert_eq!(line, written + 1);
+                assert!(message.contains("confidence 2"), "{message}");
+            }
+            other => panic!("WAL corrompu attendu : {:?}", other.err()),
+        }
+
+        fs::remove_file(dir.join(WAL_FILE)).unwrap();
+        let snapshot =
+            r#"{"seq":1,"nodes":[],"edges":[{"from":"a","rel":"r","to":"b","confidence":-1}]}"#;
+        fs::write(dir.join(SNAPSHOT_FILE), snapshot).unwrap();
+        match GraphStore::open(&dir) {
+            Err(StoreError::CorruptSnapshot { message, .. }) => {
+                assert!(message.contains("confidence -1"), "{message}");
+                assert!(message.contains("line 1"), "{message}");
+            }
+            other => panic!("snapshot corrompu attendu : {:?}", other.err()),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_torn_tail_is_truncated_and_later_commits_stay_readable() {
+        let dir = temp_store("torn");
+        let (mut store, mut g) = GraphStore::open(&dir).unwrap();
+        g.add_fact(&Fact::new("Pluton", "orbite", "Soleil"));
+        store.commit(&mut g).unwrap();
+        drop(store);
+        // crash au milieu d'une écriture
+        let mut wal = OpenOptions::new()
+            .append(true)
+            .open(dir.join(WAL_FILE))
+            .unwrap();
+        wal.write_all(br#"{"seq":99,"op":"add_ed"#).unwrap();
+        drop(wal);
+
+        let (mut store, mut g) = GraphStore::open(&dir).unwrap();
+        assert_eq!(facts(&g).len(), 1);
+        g.add_fact(&Fact::new("Charon", "orbite", "Pluton"));
+        store.commit(&mut g).unwrap();
+        drop(store);
+
+        let (_, g) = GraphStore::open(&dir).unwrap();
+        assert_eq!(facts(&g).len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+pub mod graph_store;
+pub mod snapshot;
+pub mod store_error;
+pub mod wal;
+
+pub use graph_store::GraphStore;
+pub use store_error::{StoreError, StoreResult};
@@ -0,0 +1,176 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::edge::Edge;
+use crate::fact::Fact;
+use crate::feedback::EmbeddingUpdate;
+use crate::graph::Graph;
+use crate::provenance::Provenance;
+use crate::resolve::MergeRecord;
+use crate::store::store_error::{io_err, StoreError, StoreResult};
+use crate::time::Timestamp;
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotNode {
+    pub name: String,
+    pub emb: Vec<f32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emb_history: Vec<(Timestamp, Vec<f32>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotEdge {
+    pub from: String,
+    pub rel: String,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<Timestamp>,
+    #[serde(default)]
+    pub recorded_at: Timestamp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retracted_at: Option<Timestamp>,
+    #[serde(flatten)]
+    pub provenance: Provenance,
+}
+
+impl SnapshotEdge {
+    fn capture(g: &Graph, e: &Edge) -> Self {
+        Self {
+            from: g.nodes[e.from].name.clone(),
+            rel: e.rel.clone(),
+            to: g.nodes[e.to].name.clone(),
+            valid_from: e.valid_from,
+            valid_to: e.valid_to,
+            recorded_at: e.recorded_at,
+            retracted_at: e.retracted_at,
+            provenance: e.provenance.clone(),
+        }
+    }
+}
+
+/// Image complète du graphe à la séquence `seq` du WAL.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub seq: u64,
+    /// Embedder ayant produit les vecteurs (informatif).
+    #[serde(default)]
+    pub embedder: String,
+    #[serde(default)]
+    pub dim: usize,
+    pub nodes: Vec<SnapshotNode>,
+    pub edges: Vec<SnapshotEdge>,
+    /// Versions closes ou retirées.
+    #[serde(default)]
+    pub history: Vec<SnapshotEdge>,
+    /// Journal des fusions de nœuds (ids identiques après rechargement).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merges: Vec<MergeRecord>,
+    /// Piste d'audit des embeddings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embedding_log: Vec<EmbeddingUpdate>,
+}
+
+impl Snapshot {
+    pub fn capture(g: &Graph, seq: u64) -> Self {
+        Self {
+            seq,
+            embedder: g.embedder().name().to_string(),
+            dim: g.embedder().dim(),
+            nodes: g
+                .nodes
+                .iter()
+                .map(|n| SnapshotNode {
+                    name: n.name.clone(),
+                    emb: n.emb.to_vec(),
+                    emb_history: n.emb_history.clone(),
+                })
+                .collect(),
+            // les arêtes dérivées sont recalculées au rechargement ; l'ordre
+            // des listes de chaque sujet (ordre d'insertion) est conservé
+            edges: (0..g.nodes.len())
+                .flat_map(|id| g.out_edges(id))
+                .filter(|e| !e.derived)
+                .map(|e| SnapshotEdge::capture(g, e))
+                .collect(),
+            history: g
+                .history
+                .iter()
+                .map(|e| SnapshotEdge::capture(g, e))
+                .collect(),
+            merges: g.merges().to_vec(),
+            embedding_log: g.embedding_log().to_vec(),
+        }
+    }
+
+    /// Recharge le contenu dans `g` (vide ; ids de nœuds identiques,
+    /// journal vidé). Les vecteurs d'une autre dimension que l'embedder de
+    /// `g` sont ignorés : les nœuds gardent alors leur encodage par nom.
+    pub fn restore_into(&self, g: &mut Graph) {
+        for n in &self.nodes {
+            let id = g.add_node(&n.name);
+            g.set_node_embedding(&n.name, &n.emb);
+            g.nodes[id].emb_history = n.emb_history.clone();
+        }
+        for e in &self.edges {
+            g.add_fact_recorded(
+                &Fact::new(&e.from, &e.rel, &e.to).with_provenance(e.provenance.clone()),
+                e.valid_from,
+                e.valid_to,
+                e.recorded_at,
+            );
+        }
+        for e in &self.history {
+            let edge = Edge {
+                from: g.add_node(&e.from),
+                rel: e.rel.clone(),
+                to: g.add_node(&e.to),
+                derived: false,
+                valid_from: e.valid_from,
+                valid_to: e.valid_to,
+                recorded_at: e.recorded_at,
+                retracted_at: e.retracted_at,
+                provenance: e.provenance.clone(),
+            };
+            g.history.push(edge);
+        }
+        g.restore_merges(self.merges.clone());
+        // remplace les entrées produites par la restauration des vecteurs
+        g.embedding_log = self.embedding_log.clone();
+        g.take_journal();
+    }
+
+    pub fn load(path: &Path) -> StoreResult<Option<Snapshot>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read(path).map_err(io_err(path))?;
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|e| StoreError::CorruptSnapshot {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Écriture atomique : fichier temporaire, fsync, puis renommage.
+    pub fn save(&self, path: &Path) -> StoreResult<()> {
+        let tmp = path.with_extension("json.tmp");
+        let data = serde_json::to_vec(self).expect("Snapshot est toujours sérialisable");
+        let mut f = File::create(&tmp).map_err(io_err(&tmp))?;
+        f.write_all(&data).map_err(io_err(&tmp))?;
+        f.sync_all().map_err(io_err(&tmp))?;
+        fs::rename(&tmp, path).map_err(io_err(path))?;
+        // best effort : rendre le renommage durable (non supporté partout)
+        if let Some(dir) = path.parent() {
+            if let Ok(d) = File::open(dir) {
+                let _ = d.sync_all();
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreError {
+    #[error("io error on {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("corrupt WAL {} at line {line}: {message}", .path.display())]
+    CorruptWal {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    #[error("corrupt snapshot {}: {message}", .path.display())]
+    CorruptSnapshot { path: PathBuf, message: String },
+}
+
+pub type StoreResult<T> = Result<T, StoreError>;
+
+/// Raccourci pour attacher le chemin concerné à une erreur d'E/S.
+pub(crate) fn io_err(path: &std::path::Path) -> impl FnOnce(std::io::Error) -> StoreError + '_ {
+    move |source| StoreError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
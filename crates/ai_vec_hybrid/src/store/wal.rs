@@ -0,0 +1,136 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph_op::GraphOp;
+use crate::store::store_error::{io_err, StoreError, StoreResult};
+
+/// Une ligne du WAL : numéro de séquence + opération.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WalRecord {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub op: GraphOp,
+}
+
+/// Journal append-only, une opération JSON par ligne.
+pub struct Wal {
+    path: PathBuf,
+    file: File,
+    len: usize,
+    // taille des enregistrements complets, restaurée après une écriture ratée
+    bytes: u64,
+}
+
+impl Wal {
+    /// Ouvre (ou crée) le WAL et relit les enregistrements existants.
+    ///
+    /// Une dernière ligne incomplète est considérée comme une écriture
+    /// interrompue par un crash : elle est tronquée. Une ligne incomplète
+    /// ailleurs, ou une ligne complète mais invalide, est une corruption et
+    /// fait échouer l'ouverture.
+    pub fn open(path: &Path) -> StoreResult<(Wal, Vec<WalRecord>)> {
+        let mut records = Vec::new();
+        let mut valid_len: u64 = 0;
+        let mut torn: Option<(usize, String)> = None;
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(path).map_err(io_err(path))?);
+            for (i, line) in reader.split(b'\n').enumerate() {
+                let line = line.map_err(io_err(path))?;
+                if let Some((line_no, message)) = torn.take() {
+                    // une ligne valide suit une ligne cassée : vraie corruption
+                    return Err(StoreError::CorruptWal {
+                        path: path.to_path_buf(),
+                        line: line_no,
+                        message,
+                    });
+                }
+                // une ligne tronquée se termine avant la fin du JSON (ou au
+                // milieu d'un caractère) ; une ligne complète mais refusée est
+                // toujours une corruption
+                let parsed = match std::str::from_utf8(&line) {
+                    Ok(s) => serde_json::from_str::<WalRecord>(s)
+                        .map_err(|e| (e.is_eof(), e.to_string())),
+                    Err(e) => Err((true, e.to_string())),
+                };
+                match parsed {
+                    Ok(rec) => {
+                        valid_len += line.len() as u64 + 1;
+                        records.push(rec);
+                    }
+                    Err((true, message)) => torn = Some((i + 1, message)),
+                    Err((false, message)) => {
+                        return Err(StoreError::CorruptWal {
+                            path: path.to_path_buf(),
+                            line: i + 1,
+                            message,
+                        })
+                    }
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_err(path))?;
+        if torn.is_some() {
+            file.set_len(valid_len).map_err(io_err(path))?;
+            file.sync_data().map_err(io_err(path))?;
+        }
+        let len = records.len();
+        Ok((
+            Wal {
+                path: path.to_path_buf(),
+                file,
+                len,
+                bytes: valid_len,
+            },
+            records,
+        ))
+    }
+
+    /// Ajoute des enregistrements et force leur écriture sur disque. En cas
+    /// d'échec, le fichier est ramené à sa dernière taille valide : une
+    /// ligne à moitié écrite ne doit pas rester au milieu du journal.
+    pub fn append(&mut self, records: &[WalRecord]) -> StoreResult<()> {
+        let mut buf = Vec::new();
+        for rec in records {
+            serde_json::to_writer(&mut buf, rec).expect("WalRecord est toujours sérialisable");
+            buf.push(b'\n');
+        }
+        let written = self
+            .file
+            .write_all(&buf)
+            .and_then(|()| self.file.sync_data());
+        if let Err(e) = written {
+            let _ = self.file.set_len(self.bytes);
+            return Err(io_err(&self.path)(e));
+        }
+        self.len += records.len();
+        self.bytes += buf.len() as u64;
+        Ok(())
+    }
+
+    /// Vide le journal (après un snapshot).
+    pub fn truncate(&mut self) -> StoreResult<()> {
+        self.file.set_len(0).map_err(io_err(&self.path))?;
+        self.file.sync_data().map_err(io_err(&self.path))?;
+        self.len = 0;
+        self.bytes = 0;
+        Ok(())
+    }
+
+    /// Nombre d'enregistrements actuellement dans le journal.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
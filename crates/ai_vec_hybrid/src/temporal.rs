@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+use crate::edge::Edge;
+use crate::embedding::cosine;
+use crate::graph::Graph;
+use crate::time::Timestamp;
+
+// --------- Requêtes « as-of » ---------
+
+impl Graph {
+    /// Toutes les versions de faits de base (courantes puis historiques).
+    fn all_versions(&self) -> impl Iterator<Item = &Edge> {
+        self.edges
+            .iter()
+            .filter(|e| !e.derived)
+            .chain(self.history.iter())
+    }
+
+    /// Faits sortants valides à la date `t`, selon ce que l'on sait aujourd'hui.
+    /// Les arêtes dérivées (calculées sur l'état courant) sont exclues.
+    pub fn outgoing_as_of(&self, name: &str, t: Timestamp) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.all_versions()
+            .filter(|e| e.from == id && e.retracted_at.is_none() && e.valid_at(t))
+            .collect()
+    }
+
+    /// Faits entrants valides à la date `t` (pendant de `outgoing_as_of`).
+    pub fn incoming_as_of(&self, name: &str, t: Timestamp) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.all_versions()
+            .filter(|e| e.to == id && e.retracted_at.is_none() && e.valid_at(t))
+            .collect()
+    }
+
+    /// Requête bitemporelle : faits valides à `valid_t` tels qu'ils étaient
+    /// enregistrés à `known_t`.
+    pub fn outgoing_bitemporal(
+        &self,
+        name: &str,
+        valid_t: Timestamp,
+        known_t: Timestamp,
+    ) -> Vec<&Edge> {
+        let Some(&id) = self.name2id.get(name) else {
+            return vec![];
+        };
+        self.all_versions()
+            .filter(|e| e.from == id && e.valid_at(valid_t) && e.known_at(known_t))
+            .collect()
+    }
+
+    /// Embedding du nœud `id` tel qu'il était à la date `t`.
+    pub fn embedding_as_of(&self, id: usize, t: Timestamp) -> &[f32] {
+        let node = &self.nodes[id];
+        node.emb_history
+            .iter()
+            .find(|(replaced_at, _)| *replaced_at > t)
+            .map(|(_, emb)| emb.as_slice())
+            .unwrap_or(&node.emb)
+    }
+
+    /// `search_text` à la date `t` : seuls les nœuds touchés par un fait
+    /// valide à `t` sont candidats, comparés avec leur embedding d'alors
+    /// (scan exact, l'index ANN ne connaît que l'état courant).
+    pub fn search_text_as_of(&self, query: &str, k: usize, t: Timestamp) -> Vec<(String, f32)> {
+        let q = self.embed(query);
+        let alive: HashSet<usize> = self
+            .all_versions()
+            .filter(|e| e.retracted_at.is_none() && e.valid_at(t))
+            .flat_map(|e| [e.from, e.to])
+            .collect();
+        let mut sims: Vec<(usize, f32)> = alive
+            .into_iter()
+            .map(|id| (id, cosine(self.embedding_as_of(id, t), &q)))
+            .collect();
+        sims.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        sims.truncate(k);
+        sims.into_iter()
+            .map(|(id, s)| (self.nodes[id].name.clone(), s))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fact::Fact;
+    use crate::time::from_ymd;
+
+    fn objects(g: &Graph, edges: Vec<&Edge>) -> Vec<String> {
+        edges.iter().map(|e| g.nodes[e.to].name.clone()).collect()
+    }
+
+    /// Pluton, planète depuis sa découverte, reclassée planète naine en 2006.
+    fn pluton() -> Graph {
+        let mut g = Graph::new();
+        let discovered = from_ymd(1930, 2, 18);
+        let reclassified = from_ymd(2006, 8, 24);
+        let fact = Fact::new("Pluton", "est_une", "planète");
+        g.add_fact_recorded(&fact, Some(discovered), None, from_ymd(1930, 3, 13));
+        g.close_edge_at(
+            "Pluton",
+            "est_une",
+            "planète",
+            reclassified,
+            reclassified + 86400,
+        );
+        let fact = Fact::new("Pluton", "est_une", "planète_naine");
+        g.add_fact_recorded(&fact, Some(reclassified), None, reclassified + 86400);
+        g
+    }
+
+    #[test]
+    fn as_of_before_and_after_a_correction() {
+        let g = pluton();
+        let at = |y| objects(&g, g.outgoing_as_of("Pluton", from_ymd(y, 1, 1)));
+        assert!(at(1900).is_empty());
+        assert_eq!(at(2000), ["planète"]);
+        assert_eq!(at(2010), ["planète_naine"]);
+        assert_eq!(objects(&g, g.outgoing("Pluton")), ["planète_naine"]);
+        let subjects: Vec<&str> = g
+            .incoming_as_of("planète", from_ymd(2000, 1, 1))
+            .iter()
+            .map(|e| g.nodes[e.from].name.as_str())
+            .collect();
+        assert_eq!(subjects, ["Pluton"]);
+    }
+
+    #[test]
+    fn bitemporal_reads_what_was_believed_then() {
+        let g = pluton();
+        let (y2000, y2010) = (from_ymd(2000, 1, 1), from_ymd(2010, 1, 1));
+        // en 2000, on croyait Pluton planète pour toujours
+        assert_eq!(
+            objects(&g, g.outgoing_bitemporal("Pluton", y2010, y2000)),
+            ["planète"]
+        );
+        assert_eq!(
+            objects(&g, g.outgoing_bitemporal("Pluton", y2010, y2010)),
+            ["planète_naine"]
+        );
+        assert_eq!(
+            objects(&g, g.outgoing_bitemporal("Pluton", y2000, y2010)),
+            ["planète"]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Secondes depuis l'epoch Unix (UTC).
+pub type Timestamp = i64;
+
+pub fn now() -> Timestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as Timestamp)
+        .unwrap_or(0)
+}
+
+/// Minuit UTC du jour `y-m-d` (calendrier grégorien proleptique).
+pub fn from_ymd(y: i64, m: u32, d: u32) -> Timestamp {
+    // algorithme « days_from_civil » de H. Hinnant
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = m as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146097 + doe - 719468) * 86400
+}
+
+/// Date `YYYY-MM-DD` (UTC) d'un timestamp.
+pub fn format_date(t: Timestamp) -> String {
+    // inverse de `from_ymd` (« civil_from_days »)
+    let z = t.div_euclid(86400) + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Accepte `YYYY-MM-DD` ou un timestamp Unix brut.
+pub fn parse_date(s: &str) -> Option<Timestamp> {
+    if let Ok(t) = s.parse::<Timestamp>() {
+        return Some(t);
+    }
+    let mut parts = s.splitn(3, '-');
+    let y = parts.next()?.parse().ok()?;
+    let m = parts.next()?.parse().ok()?;
+    let d = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=days_in_month(y, m)).contains(&d) {
+        return None;
+    }
+    Some(from_ymd(y, m, d))
+}
+
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates_and_raw_timestamps() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("1161734400"), Some(1161734400));
+        let t = parse_date("2006-08-24").unwrap();
+        assert_eq!(t, from_ymd(2006, 8, 24));
+        assert_eq!(format_date(t), "2006-08-24");
+        // années bissextiles : divisibles par 4, sauf siècles non divisibles par 400
+        assert_eq!(format_date(parse_date("2004-02-29").unwrap()), "2004-02-29");
+        assert_eq!(format_date(parse_date("2000-02-29").unwrap()), "2000-02-29");
+    }
+
+    #[test]
+    fn rejects_impossible_dates() {
+        for s in [
+            "2006-02-31",
+            "2006-02-29",
+            "1900-02-29",
+            "2006-04-31",
+            "2006-13-01",
+            "2006-00-10",
+            "2006-08-00",
+            "2006-08",
+            "24/08/2006",
+        ] {
+            assert_eq!(parse_date(s), None, "{s}");
+        }
+    }
+}